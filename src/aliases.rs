@@ -0,0 +1,209 @@
+//! `aliases.rs`
+//!
+//! 技エイリアス（別名）の追加提案・重複判定・`aliases.json` への書き込みを提供する。
+//! 提案はフィードバックとして記録され、開発者の承認後に書き込まれる。
+
+use crate::error::Result;
+use crate::{find, utils, MoveAliases, MoveInfo};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// エイリアス最大文字数
+pub const MAX_ALIAS_LEN: usize = 32;
+
+/// エイリアス追加提案
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasSuggestion {
+    /// 正式なキャラクター名
+    pub character: String,
+    /// 対象技の入力（技データの `input`）
+    pub input: String,
+    /// 追加するエイリアス
+    pub alias: String,
+}
+
+/// 比較用正規化関数（大文字小文字・空白を無視）
+fn normalize(text: &str) -> String {
+    text.to_lowercase().replace(' ', "")
+}
+
+/// エイリアス重複判定関数
+///
+/// # 概要
+/// 既存のエイリアス・技名・技入力と（大文字小文字・空白を無視して）一致する場合に、その内容を返す。
+///
+/// # 戻り値
+/// 重複時は重複内容の説明、重複なしは None
+pub fn alias_conflict(alias: &str, aliases: &[MoveAliases], moves: &[MoveInfo]) -> Option<String> {
+    let key = normalize(alias);
+    for entry in aliases {
+        if let Some(existing) = entry.aliases.iter().find(|a| normalize(a) == key) {
+            return Some(format!(
+                "`{existing}` is already an alias of `{}`",
+                entry.input
+            ));
+        }
+    }
+    moves
+        .iter()
+        .find(|m| normalize(&m.input) == key || normalize(&m.name) == key)
+        .map(|m| format!("`{alias}` is already the input or name of `{}`", m.input))
+}
+
+/// エイリアス形式検証関数
+pub fn validate_alias(alias: &str) -> std::result::Result<(), String> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err("Alias cannot be empty.".to_string());
+    }
+    if alias.chars().count() > MAX_ALIAS_LEN {
+        return Err(format!("Alias must be at most {MAX_ALIAS_LEN} characters."));
+    }
+    if alias.contains(utils::SEQUENCE_SEPARATOR) || alias.contains(',') {
+        return Err(format!(
+            "Alias cannot contain `{}` or `,`.",
+            utils::SEQUENCE_SEPARATOR
+        ));
+    }
+    Ok(())
+}
+
+/// エイリアス一覧へ追加する関数
+///
+/// 対象技の既存エントリがあれば末尾に追加し、無ければ新規エントリを作成する。
+/// 既存エントリの入力は "2D" 等の短い表記のため、技検索と同じ規則で技を特定して照合する。
+pub fn insert_alias(aliases: &mut Vec<MoveAliases>, moves: &[MoveInfo], input: &str, alias: &str) {
+    let key = normalize(input);
+    let target = find::match_move_index(input, moves);
+    match aliases.iter_mut().find(|entry| {
+        normalize(&entry.input) == key
+            || target.is_some_and(|t| find::match_move_index(&entry.input, moves) == Some(t))
+    }) {
+        Some(entry) => entry.aliases.push(alias.to_string()),
+        None => aliases.push(MoveAliases {
+            input: input.to_string(),
+            aliases: vec![alias.to_string()],
+        }),
+    }
+}
+
+/// エイリアス提案適用関数
+///
+/// # 概要
+/// 最新の `aliases.json` と技データで重複を再確認した上で、エイリアスを追加して保存する。
+/// 検索処理は呼び出し毎に `aliases.json` を読み込むため、保存後すぐに反映される。
+///
+/// # 戻り値
+/// 重複していた場合は `Ok(Some(重複内容))`、追加した場合は `Ok(None)`
+pub fn apply_suggestion(data_dir: &str, suggestion: &AliasSuggestion) -> Result<Option<String>> {
+    let char_dir = Path::new(data_dir).join(&suggestion.character);
+    let moves: Vec<MoveInfo> =
+        utils::read_json_file(char_dir.join(suggestion.character.clone() + ".json"))?;
+    let mut aliases = utils::load_move_aliases(data_dir, &suggestion.character)?;
+    if let Some(conflict) = alias_conflict(&suggestion.alias, &aliases, &moves) {
+        return Ok(Some(conflict));
+    }
+    insert_alias(&mut aliases, &moves, &suggestion.input, &suggestion.alias);
+    write_aliases(&char_dir.join("aliases.json"), &aliases)?;
+    Ok(None)
+}
+
+/// `aliases.json` 書き込み関数（既存ファイルに合わせてタブインデント）
+fn write_aliases(path: &Path, aliases: &[MoveAliases]) -> Result<()> {
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    aliases.serialize(&mut serializer)?;
+    fs::write(path, buf)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_move_aliases, create_test_move_info, MoveInfoBuilder};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_alias_conflict() {
+        let aliases = create_test_move_aliases();
+        let moves = create_test_move_info();
+        assert!(alias_conflict("fire ball", &aliases, &moves).is_some());
+        assert!(alias_conflict("stunedge", &aliases, &moves).is_some());
+        assert!(alias_conflict("236k", &aliases, &moves).is_some());
+        assert!(alias_conflict("飛び道具", &aliases, &moves).is_none());
+    }
+
+    #[test]
+    fn test_validate_alias() {
+        assert!(validate_alias("飛び道具").is_ok());
+        assert!(validate_alias("  ").is_err());
+        assert!(validate_alias("5K > 2D").is_err());
+        assert!(validate_alias(&"a".repeat(MAX_ALIAS_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_insert_alias() {
+        let moves = create_test_move_info();
+        let mut aliases = create_test_move_aliases();
+        insert_alias(&mut aliases, &moves, "236 k", "飛び道具");
+        assert_eq!(aliases[1].aliases.last().unwrap(), "飛び道具");
+        insert_alias(&mut aliases, &moves, "2D", "足払い");
+        assert_eq!(aliases.len(), 3);
+        assert_eq!(aliases[2].input, "2D");
+    }
+
+    #[test]
+    fn test_insert_alias_short_key() {
+        // 実データと同じく技データは "足払い(2D)"、aliases.json は "2D" の表記
+        let moves: Vec<MoveInfo> = ["足払い(2D)", "ダスト(5D)", "近S(近S)", "5K(5K)"]
+            .iter()
+            .map(|input| {
+                let mut info = MoveInfoBuilder::new(input).build();
+                info.name = input.split('(').next().unwrap().to_string();
+                info
+            })
+            .collect();
+        let mut aliases: Vec<MoveAliases> = serde_json::from_str(
+            r#"[
+                {"input": "5D", "aliases": ["D", "ダスト"]},
+                {"input": "2D", "aliases": ["足払い"]},
+                {"input": "近S", "aliases": ["c.S", "cS"]}
+            ]"#,
+        )
+        .unwrap();
+
+        insert_alias(&mut aliases, &moves, "足払い(2D)", "スイープ");
+        insert_alias(&mut aliases, &moves, "近S(近S)", "close S");
+        assert_eq!(aliases.len(), 3);
+        assert_eq!(aliases[1].aliases, vec!["足払い", "スイープ"]);
+        assert_eq!(aliases[2].aliases.last().unwrap(), "close S");
+
+        // エントリの無い技は技データの入力で追加
+        insert_alias(&mut aliases, &moves, "5K(5K)", "遠K");
+        assert_eq!(aliases.len(), 4);
+        assert_eq!(aliases[3].input, "5K(5K)");
+    }
+
+    #[test]
+    fn test_apply_suggestion() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let char_dir = dir.path().join("Test");
+        fs::create_dir(&char_dir).unwrap();
+        utils::write_json_file(char_dir.join("Test.json"), &create_test_move_info()).unwrap();
+
+        let suggestion = AliasSuggestion {
+            character: "Test".to_string(),
+            input: "236K".to_string(),
+            alias: "飛び道具".to_string(),
+        };
+        assert_eq!(apply_suggestion(data_dir, &suggestion).unwrap(), None);
+        let aliases = utils::load_move_aliases(data_dir, "Test").unwrap();
+        assert_eq!(aliases[0].aliases, vec!["飛び道具".to_string()]);
+
+        // 2 回目は重複
+        assert!(apply_suggestion(data_dir, &suggestion).unwrap().is_some());
+    }
+}
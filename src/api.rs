@@ -0,0 +1,455 @@
+//! `api.rs`
+//!
+//! 技データを JSON で返す HTTP API を提供する（`api` 機能有効時のみ）。
+//! Bot と同じ検索処理（`find`）でキャラクター・技を解決し、エイリアス・画像 URL を付与した正規化データを返す。
+//! 読み取り専用で、GET 以外のメソッドは受け付けない。
+
+use crate::error::{AppError, Result};
+use crate::move_export::{self, ExportMove};
+use crate::{find, nickname_store, utils, CHARS};
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// 検索結果の既定件数
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// 検索結果の最大件数
+const MAX_SEARCH_LIMIT: usize = 200;
+
+/// 元ファイルの状態（更新時刻, サイズ）
+type SourceStamp = Vec<Option<(SystemTime, u64)>>;
+
+/// 出力用技データのキャッシュ（キャラクターディレクトリ → 元ファイルの状態, 技データ）
+type MovesCache = BTreeMap<PathBuf, (SourceStamp, Arc<Vec<ExportMove>>)>;
+
+/// 出力用技データのキャッシュ本体
+static MOVES_CACHE: Mutex<MovesCache> = Mutex::new(BTreeMap::new());
+
+/// API 応答
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    /// HTTP ステータスコード
+    pub status: u16,
+    /// 応答本文
+    pub body: Value,
+}
+
+impl ApiResponse {
+    /// 正常応答を作成する。
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    /// エラー応答を作成する（本文は `{"error": "..."}`）。
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+// AppError型からAPI応答への変換（未検出は 404、それ以外は 500）
+impl From<AppError> for ApiResponse {
+    fn from(error: AppError) -> Self {
+        match error {
+            AppError::CharacterNotFound(message) | AppError::MoveNotFound(message) => {
+                Self::error(404, message)
+            }
+            other => Self::error(500, other.to_string()),
+        }
+    }
+}
+
+/// URL エンコード解除関数（`plus_as_space` が true の場合は "+" を空白とする）
+pub fn percent_decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = hex {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                decoded.push(b'%');
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// クエリ文字列の解析関数
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key, true), percent_decode(value, true))
+        })
+        .collect()
+}
+
+/// キャラクター解決関数（"all" は対象外）
+async fn resolve_character(data_dir: &str, id: &str) -> Result<String> {
+    let character = find::find_character(data_dir, id, None).await?;
+    if character.is_empty() {
+        return Err(AppError::CharacterNotFound(format!(
+            "Character `{id}` was not found!"
+        )));
+    }
+    Ok(character)
+}
+
+/// 技データ・エイリアス・画像ファイルの状態を返す。
+fn source_stamp(char_dir: &Path, character: &str) -> SourceStamp {
+    [
+        character.to_owned() + ".json",
+        "aliases.json".to_string(),
+        "images.json".to_string(),
+    ]
+    .iter()
+    .map(|file| {
+        let metadata = fs::metadata(char_dir.join(file)).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    })
+    .collect()
+}
+
+/// 出力用技データ読み込み関数
+///
+/// # 概要
+/// 読み込んだ技データはキャッシュし、元ファイルが更新されていなければ再利用する。
+fn load_moves(data_dir: &str, character: &str) -> Result<Arc<Vec<ExportMove>>> {
+    let char_dir = Path::new(data_dir).join(character);
+    let stamp = source_stamp(&char_dir, character);
+    let mut cache = MOVES_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_stamp, moves)) = cache.get(&char_dir) {
+        if *cached_stamp == stamp {
+            return Ok(Arc::clone(moves));
+        }
+    }
+
+    let moves_info = utils::load_move_info(data_dir, character)?;
+    let aliases_data = utils::load_move_aliases(data_dir, character)?;
+    let image_links = utils::load_image_links(data_dir, character)?;
+    let moves = Arc::new(move_export::build_export(
+        &moves_info,
+        &aliases_data,
+        &image_links,
+    ));
+    cache.insert(char_dir, (stamp, Arc::clone(&moves)));
+    Ok(moves)
+}
+
+/// 技データが存在するキャラクター一覧を返す。
+fn available_characters(data_dir: &str) -> Vec<&'static str> {
+    CHARS
+        .into_iter()
+        .filter(|c| {
+            Path::new(data_dir)
+                .join(c)
+                .join(c.to_string() + ".json")
+                .exists()
+        })
+        .collect()
+}
+
+/// キャラクター一覧の応答本文生成関数
+fn characters(data_dir: &str) -> Value {
+    let nicknames = nickname_store::load(data_dir).unwrap_or_default();
+    let list: Vec<Value> = available_characters(data_dir)
+        .into_iter()
+        .map(|id| {
+            let names = nicknames
+                .iter()
+                .find(|n| n.character == id)
+                .map(|n| n.nicknames.clone())
+                .unwrap_or_default();
+            json!({ "id": id, "name": id.replace('_', " "), "nicknames": names })
+        })
+        .collect();
+    Value::Array(list)
+}
+
+/// 技検索関数
+///
+/// # 概要
+/// 技入力・技名・エイリアスのいずれかに検索語を含む技を返す（大文字小文字・空白は無視）。
+///
+/// # 引数
+/// * `entries` - キャラクター毎の技データ
+/// * `query` - 検索語
+/// * `limit` - 最大件数
+///
+/// # 戻り値
+/// `{"character": ..., "move": ...}` の一覧
+pub fn search(entries: &[(String, Arc<Vec<ExportMove>>)], query: &str, limit: usize) -> Vec<Value> {
+    let normalize = |s: &str| s.to_lowercase().replace(' ', "");
+    let query = normalize(query);
+    entries
+        .iter()
+        .flat_map(|(character, moves)| moves.iter().map(move |m| (character, m)))
+        .filter(|(_, m)| {
+            normalize(&m.move_info.input).contains(&query)
+                || normalize(&m.move_info.name).contains(&query)
+                || m.aliases.iter().any(|a| normalize(a).contains(&query))
+        })
+        .take(limit)
+        .map(|(character, m)| json!({ "character": character, "move": m }))
+        .collect()
+}
+
+/// 検索エンドポイント処理
+async fn search_endpoint(data_dir: &str, params: &[(String, String)]) -> Result<ApiResponse> {
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let Some(query) = param("q") else {
+        return Ok(ApiResponse::error(400, "Missing query parameter `q`."));
+    };
+    let limit = match param("limit").map(str::parse::<usize>) {
+        None => DEFAULT_SEARCH_LIMIT,
+        Some(Ok(limit)) => limit.min(MAX_SEARCH_LIMIT),
+        Some(Err(_)) => return Ok(ApiResponse::error(400, "Invalid `limit`.")),
+    };
+    let characters = match param("character") {
+        Some(id) => vec![resolve_character(data_dir, id).await?],
+        None => available_characters(data_dir)
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    };
+
+    let mut entries = Vec::new();
+    for character in characters {
+        let moves = load_moves(data_dir, &character)?;
+        entries.push((character, moves));
+    }
+    Ok(ApiResponse::ok(Value::Array(search(
+        &entries, query, limit,
+    ))))
+}
+
+/// リクエスト処理関数
+///
+/// # 概要
+/// 以下のエンドポイントを提供する：
+/// - `/characters`: キャラクター一覧（id・表示名・愛称）
+/// - `/characters/{id}`: キャラクター情報（id は愛称でも可）
+/// - `/characters/{id}/moves`: 技一覧（エイリアス・画像 URL 付き）
+/// - `/characters/{id}/moves/{query}`: 技名・入力・エイリアスで解決した技 1 件
+/// - `/search?q=...&character=...&limit=...`: 技検索
+///
+/// # 引数
+/// * `data_dir` - データディレクトリ
+/// * `method` - HTTP メソッド
+/// * `url` - パスとクエリ文字列
+///
+/// # 戻り値
+/// API 応答
+pub async fn route(data_dir: &str, method: &str, url: &str) -> ApiResponse {
+    if !method.eq_ignore_ascii_case("GET") {
+        return ApiResponse::error(405, "Only GET is supported.");
+    }
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| percent_decode(s, false))
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let result = match segments.as_slice() {
+        [] => Ok(ApiResponse::ok(json!({
+            "endpoints": [
+                "/characters",
+                "/characters/{id}",
+                "/characters/{id}/moves",
+                "/characters/{id}/moves/{query}",
+                "/search?q={query}&character={id}&limit={n}"
+            ]
+        }))),
+        ["characters"] => Ok(ApiResponse::ok(characters(data_dir))),
+        ["characters", id] => character_info(data_dir, id).await,
+        ["characters", id, "moves"] => match resolve_character(data_dir, id).await {
+            Ok(character) => load_moves(data_dir, &character)
+                .and_then(|moves| Ok(ApiResponse::ok(serde_json::to_value(&*moves)?))),
+            Err(err) => Err(err),
+        },
+        ["characters", id, "moves", query] => find_move(data_dir, id, query).await,
+        ["search"] => search_endpoint(data_dir, &parse_query(query)).await,
+        _ => Ok(ApiResponse::error(404, "Not found.")),
+    };
+    result.unwrap_or_else(ApiResponse::from)
+}
+
+/// キャラクター情報の応答生成関数
+async fn character_info(data_dir: &str, id: &str) -> Result<ApiResponse> {
+    let character = resolve_character(data_dir, id).await?;
+    let nicknames = nickname_store::load(data_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|n| n.character == character)
+        .map(|n| n.nicknames)
+        .unwrap_or_default();
+    let info = utils::load_character_info(data_dir, &character).ok();
+    let moves = utils::load_move_info(data_dir, &character)?.len();
+    Ok(ApiResponse::ok(json!({
+        "id": character,
+        "name": character.replace('_', " "),
+        "nicknames": nicknames,
+        "info": info,
+        "moves": moves,
+    })))
+}
+
+/// 技 1 件の応答生成関数（`find::find_move_index` と同じ解決方法）
+async fn find_move(data_dir: &str, id: &str, query: &str) -> Result<ApiResponse> {
+    let character = resolve_character(data_dir, id).await?;
+    let moves_info = utils::load_move_info(data_dir, &character)?;
+    let index =
+        find::find_move_index(data_dir, &character, query.to_string(), &moves_info, None).await?;
+    let aliases_data = utils::load_move_aliases(data_dir, &character)?;
+    let image_links = utils::load_image_links(data_dir, &character)?;
+    let found =
+        move_export::build_export(&moves_info, &aliases_data, &image_links).swap_remove(index);
+    Ok(ApiResponse::ok(
+        json!({ "character": character, "move": found }),
+    ))
+}
+
+/// HTTP API 起動関数
+///
+/// # 概要
+/// 指定アドレスで待ち受け、専用スレッドでリクエストを順に処理する。
+/// 応答には `Access-Control-Allow-Origin: *` を付与する（配信オーバーレイ・Web ページからの利用向け）。
+///
+/// # 引数
+/// * `addr` - 待ち受けアドレス（例："127.0.0.1:8080"）
+/// * `data_dir` - データディレクトリ
+pub fn spawn(addr: &str, data_dir: String) -> Result<()> {
+    let server = tiny_http::Server::http(addr)
+        .map_err(|e| AppError::Config(format!("HTTP API を起動できません ({addr}): {e}")))?;
+    let headers = [
+        tiny_http::Header::from_bytes("Content-Type", "application/json; charset=utf-8"),
+        tiny_http::Header::from_bytes("Access-Control-Allow-Origin", "*"),
+    ];
+
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let response = futures::executor::block_on(route(
+                &data_dir,
+                request.method().as_str(),
+                request.url(),
+            ));
+            let mut http_response = tiny_http::Response::from_data(response.body.to_string())
+                .with_status_code(response.status);
+            for header in headers.iter().flatten() {
+                http_response.add_header(header.clone());
+            }
+            if let Err(e) = request.respond(http_response) {
+                eprintln!("{}", format!("HTTP API 応答送信失敗: {e}").red());
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_move_aliases, create_test_move_info};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_percent_decode_and_query() {
+        assert_eq!(percent_decode("Gun%20Flame", false), "Gun Flame");
+        assert_eq!(percent_decode("j.D+K", false), "j.D+K");
+        assert_eq!(percent_decode("%E3%82%BD%E3%83%AB", false), "ソル");
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%zz%E3", false), "%zz\u{FFFD}");
+        assert_eq!(
+            parse_query("q=gun+flame&limit=5&flag"),
+            vec![
+                ("q".to_string(), "gun flame".to_string()),
+                ("limit".to_string(), "5".to_string()),
+                ("flag".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let moves =
+            move_export::build_export(&create_test_move_info(), &create_test_move_aliases(), &[]);
+        let entries = vec![("Sol_Badguy".to_string(), Arc::new(moves))];
+        let results = search(&entries, "punch", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["character"], "Sol_Badguy");
+        assert_eq!(results[0]["move"]["input"], "5P");
+        assert!(search(&entries, "missing", 10).is_empty());
+        assert_eq!(search(&entries, "5", 1).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_route() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let sol_dir = dir.path().join("Sol_Badguy");
+        std::fs::create_dir(&sol_dir).unwrap();
+        utils::write_json_file(sol_dir.join("Sol_Badguy.json"), &create_test_move_info()).unwrap();
+
+        let response = route(data_dir, "GET", "/characters").await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body[0]["id"], "Sol_Badguy");
+        assert_eq!(response.body[0]["name"], "Sol Badguy");
+
+        assert_eq!(route(data_dir, "POST", "/characters").await.status, 405);
+        assert_eq!(route(data_dir, "GET", "/unknown").await.status, 404);
+        assert_eq!(route(data_dir, "GET", "/search").await.status, 400);
+        assert_eq!(
+            route(data_dir, "GET", "/search?q=5P&limit=x").await.status,
+            400
+        );
+        assert_eq!(route(data_dir, "GET", "/").await.status, 200);
+    }
+
+    #[test]
+    fn test_load_moves_cache() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let sol_dir = dir.path().join("Sol_Badguy");
+        std::fs::create_dir(&sol_dir).unwrap();
+        let moves_path = sol_dir.join("Sol_Badguy.json");
+        utils::write_json_file(&moves_path, &create_test_move_info()).unwrap();
+
+        let first = load_moves(data_dir, "Sol_Badguy").unwrap();
+        let second = load_moves(data_dir, "Sol_Badguy").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // 元ファイル更新時は読み直す
+        utils::write_json_file(&moves_path, &create_test_move_info()[..1].to_vec()).unwrap();
+        let updated = load_moves(data_dir, "Sol_Badguy").unwrap();
+        assert_eq!(updated.len(), 1);
+    }
+}
@@ -9,7 +9,6 @@ use futures::future::Future;
 use std::time::{Duration, Instant};
 use tokio::{task, time};
 use tracing::{error, info};
-#[cfg(test)]
 
 /// ログ付き非同期タスクを生成する
 ///
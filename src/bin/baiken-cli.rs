@@ -0,0 +1,563 @@
+//! `baiken-cli.rs`
+//!
+//! このファイルは、Discord を介さずに端末からフレームデータを参照するコマンドラインツールを実装する。
+//! Bot と同じ検索処理（`find`）・データモデル・技情報の表示項目を用い、結果を表形式（または TSV）で出力する。
+//!
+//! # 注意
+//! キャラクター・技の検索は Bot と同じく環境変数 `DATA_DIR` で指定したデータフォルダを参照する。
+//! 未設定の場合は実行ディレクトリ直下の `data` フォルダを用いる。
+
+use baiken::commands::moves::utils::move_category;
+use baiken::commands::moves::TypeChoice;
+use baiken::commands::update::pipeline::{self, UpdateKind, UpdateOptions};
+use baiken::common::embed::{EmbedView, MoveField};
+use baiken::common::table::format_table;
+use baiken::{
+    find, nickname_store, utils, AppError, ImageLinks, MoveAliases, MoveInfo, Result, CHARS,
+};
+use poise::ChoiceParameter;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// 使用方法
+const USAGE: &str = "\
+Usage: baiken-cli [--tsv] <command> [args...]
+
+Commands:
+  frames <character> <move> [--advanced]   Frame data of a move
+  moves <character> [all|normals|specials|supers]
+                                           Moves, aliases and basic frame data
+  hitboxes <character> <move>              Hitbox image URLs of a move
+  nicknames [character]                    Character nicknames
+  compare <character> <move> <character> <move>
+                                           Two moves side by side
+  update [--character <name>]... [--frames-only | --images-only | --info-only]
+         [--dry-run] [--output-dir <dir>]  Fetch data from Dustloop and print the changes
+
+Options:
+  --tsv   Print tab-separated values instead of a table
+
+Update options:
+  --character <name>   Character name or nickname, repeatable (default: all)
+  --frames-only        Only update frame data
+  --images-only        Only update image links
+  --info-only          Only update character stats (character.json)
+  --dry-run            Print the changes without writing files
+  --output-dir <dir>   Folder to compare against and write to (default: DATA_DIR)
+
+Environment:
+  DATA_DIR   Data folder (default: data)";
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// 等幅表示の表
+    Table,
+    /// タブ区切り
+    Tsv,
+}
+
+/// コマンド出力（見出しと行）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Output {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    /// 一部の処理が失敗したか否か（終了コードに反映）
+    failed: bool,
+}
+
+impl Output {
+    /// 見出しを指定して出力を作成する。
+    fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| (*h).to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// 出力文字列生成関数
+    fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Table => format_table(&self.headers, &self.rows),
+            OutputFormat::Tsv => std::iter::once(&self.headers)
+                .chain(&self.rows)
+                .map(|row| {
+                    row.iter()
+                        .map(|value| value.replace(['\t', '\n'], " "))
+                        .collect::<Vec<_>>()
+                        .join("\t")
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+/// 引数取得関数（不足時は使用方法付きのエラー）
+fn arg<'a>(args: &'a [String], index: usize, name: &str) -> Result<&'a str> {
+    args.get(index)
+        .map(String::as_str)
+        .ok_or_else(|| AppError::Config(format!("Missing <{name}>.\n\n{USAGE}")))
+}
+
+/// キャラクター名と技情報の検索関数
+async fn resolve_move(data_dir: &str, character: &str, query: &str) -> Result<(String, MoveInfo)> {
+    let character_arg_altered = find::find_character(data_dir, character, None).await?;
+    let moves_info = utils::load_move_info(data_dir, &character_arg_altered)?;
+    let index = find::find_move_index(
+        data_dir,
+        &character_arg_altered,
+        query.to_string(),
+        &moves_info,
+        None,
+    )
+    .await?;
+    Ok((character_arg_altered, moves_info[index].clone()))
+}
+
+/// 技情報の項目一覧出力生成関数（入力・技名の後に指定形式の既定項目を並べる）
+fn frames_output(character_arg_altered: &str, move_info: &MoveInfo, view: EmbedView) -> Output {
+    let mut output = Output::new(&["field", "value"]);
+    output.rows.push(vec![
+        "character".to_string(),
+        character_arg_altered.to_string(),
+    ]);
+    output
+        .rows
+        .push(vec!["input".to_string(), move_info.input.clone()]);
+    output
+        .rows
+        .push(vec!["name".to_string(), move_info.name.clone()]);
+    for field in view.default_fields() {
+        output
+            .rows
+            .push(vec![field.key().to_string(), field.value(move_info)]);
+    }
+    output
+}
+
+/// 技一覧出力生成関数（JSON 内の順序のまま、指定種別のみ）
+fn moves_output(
+    moves_info: &[MoveInfo],
+    aliases_data: &[MoveAliases],
+    category: TypeChoice,
+) -> Output {
+    let mut output = Output::new(&[
+        "type", "input", "name", "startup", "on_block", "guard", "aliases",
+    ]);
+    let aliases_by_move = find::aliases_by_move(moves_info, aliases_data);
+    for (move_info, aliases) in moves_info.iter().zip(&aliases_by_move) {
+        let move_type = move_category(move_info);
+        if category != TypeChoice::All && move_type != category {
+            continue;
+        }
+        output.rows.push(vec![
+            move_type.name().to_string(),
+            move_info.input.clone(),
+            move_info.name.clone(),
+            MoveField::Startup.value(move_info),
+            move_info.on_block.clone(),
+            move_info.guard.clone(),
+            aliases.join(", "),
+        ]);
+    }
+    output
+}
+
+/// ヒットボックス画像 URL 出力生成関数
+fn hitboxes_output(move_info: &MoveInfo, image_links: &[ImageLinks]) -> Output {
+    let mut output = Output::new(&["#", "url"]);
+    if let Some(index) = find::find_image_index(&move_info.input, image_links) {
+        for (i, url) in image_links[index].hitbox_img.iter().enumerate() {
+            output.rows.push(vec![(i + 1).to_string(), url.clone()]);
+        }
+    }
+    output
+}
+
+/// 愛称一覧出力生成関数（キャラクター指定時はそのキャラクターのみ）
+fn nicknames_output(nicknames: &[baiken::Nicknames], character: Option<&str>) -> Output {
+    let mut output = Output::new(&["character", "nicknames"]);
+    output.rows = nicknames
+        .iter()
+        .filter(|entry| character.is_none_or(|c| entry.character == c))
+        .map(|entry| vec![entry.character.clone(), entry.nicknames.join(", ")])
+        .collect();
+    output
+}
+
+/// 2 技の比較出力生成関数（全項目、値が異なる行に "*" を付ける）
+fn compare_output(a: (&str, &MoveInfo), b: (&str, &MoveInfo)) -> Output {
+    let mut output = Output::new(&[
+        "field",
+        &format!("{} {}", a.0, a.1.input),
+        &format!("{} {}", b.0, b.1.input),
+        "diff",
+    ]);
+    for field in MoveField::ALL {
+        let (left, right) = (field.value(a.1), field.value(b.1));
+        let diff = if left == right { "" } else { "*" };
+        output
+            .rows
+            .push(vec![field.key().to_string(), left, right, diff.to_string()]);
+    }
+    output
+}
+
+/// 更新設定の解析関数（キャラクターは愛称から正式名へ変換、未指定・"all" は全キャラクター）
+async fn update_options(args: &[String], data_dir: &str) -> Result<UpdateOptions> {
+    let mut characters = Vec::new();
+    let mut kinds = vec![UpdateKind::Frames, UpdateKind::Images, UpdateKind::Info];
+    let mut dry_run = false;
+    let mut output_dir = PathBuf::from(data_dir);
+
+    let mut iter = args.iter();
+    while let Some(option) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| AppError::Config(format!("Missing <{name}> for `{option}`.")))
+        };
+        match option.as_str() {
+            "--character" => {
+                let character = find::find_character(data_dir, &value("name")?, None).await?;
+                if character.is_empty() {
+                    characters = CHARS.iter().map(|c| (*c).to_string()).collect();
+                } else if !characters.contains(&character) {
+                    characters.push(character);
+                }
+            }
+            "--frames-only" => kinds = vec![UpdateKind::Frames],
+            "--images-only" => kinds = vec![UpdateKind::Images],
+            "--info-only" => kinds = vec![UpdateKind::Info],
+            "--dry-run" => dry_run = true,
+            "--output-dir" => output_dir = PathBuf::from(value("dir")?),
+            other => {
+                return Err(AppError::Config(format!(
+                    "Unknown update option `{other}`.\n\n{USAGE}"
+                )))
+            }
+        }
+    }
+    if characters.is_empty() {
+        characters = CHARS.iter().map(|c| (*c).to_string()).collect();
+    }
+    Ok(UpdateOptions {
+        characters,
+        kinds,
+        dry_run,
+        output_dir,
+    })
+}
+
+/// 更新結果の差分出力生成関数
+fn update_output(reports: &[pipeline::UpdateReport], failed: bool) -> Output {
+    let mut output = Output::new(&["character", "kind", "change", "input", "detail"]);
+    for report in reports {
+        for change in &report.changes {
+            let detail = change
+                .fields
+                .iter()
+                .map(|(field, old, new)| format!("{field}: {old} -> {new}"))
+                .collect::<Vec<_>>()
+                .join("; ");
+            output.rows.push(vec![
+                report.character.clone(),
+                report.kind.name().to_string(),
+                change.kind.symbol().to_string(),
+                change.input.clone(),
+                detail,
+            ]);
+        }
+    }
+    output.failed = failed;
+    output
+}
+
+/// コマンド実行関数
+///
+/// # 引数
+/// * `data_dir` - データディレクトリ
+/// * `args` - コマンド名以降の引数（オプション除去済み）
+///
+/// # 戻り値
+/// コマンド出力
+async fn run(data_dir: &str, args: &[String]) -> Result<Output> {
+    // 値付きオプションを持つ update は個別に解析
+    if args.first().is_some_and(|a| a == "update") {
+        let options = update_options(&args[1..], data_dir).await?;
+        let (reports, failures) = pipeline::run_update(&options).await;
+        return Ok(update_output(&reports, !failures.is_empty()));
+    }
+
+    let flag = |name: &str| args.iter().any(|a| a == name);
+    let args: Vec<String> = args
+        .iter()
+        .filter(|a| !a.starts_with("--"))
+        .cloned()
+        .collect();
+
+    match arg(&args, 0, "command")? {
+        "frames" => {
+            let (character, move_info) = resolve_move(
+                data_dir,
+                arg(&args, 1, "character")?,
+                arg(&args, 2, "move")?,
+            )
+            .await?;
+            let view = if flag("--advanced") {
+                EmbedView::Advanced
+            } else {
+                EmbedView::Simple
+            };
+            Ok(frames_output(&character, &move_info, view))
+        }
+        "moves" => {
+            let character =
+                find::find_character(data_dir, arg(&args, 1, "character")?, None).await?;
+            let category = match args.get(2) {
+                Some(name) => TypeChoice::from_name(name).ok_or_else(|| {
+                    AppError::Config(format!(
+                        "Unknown move type `{name}`. Available: all, normals, specials, supers"
+                    ))
+                })?,
+                None => TypeChoice::All,
+            };
+            let moves_info = utils::load_move_info(data_dir, &character)?;
+            let aliases_data = utils::load_move_aliases(data_dir, &character)?;
+            Ok(moves_output(&moves_info, &aliases_data, category))
+        }
+        "hitboxes" => {
+            let (character, move_info) = resolve_move(
+                data_dir,
+                arg(&args, 1, "character")?,
+                arg(&args, 2, "move")?,
+            )
+            .await?;
+            let image_links: Vec<ImageLinks> =
+                utils::read_json_file(Path::new(data_dir).join(&character).join("images.json"))?;
+            Ok(hitboxes_output(&move_info, &image_links))
+        }
+        "nicknames" => {
+            let character = match args.get(1) {
+                Some(character) => Some(find::find_character(data_dir, character, None).await?),
+                None => None,
+            };
+            let nicknames = nickname_store::load(data_dir)?;
+            Ok(nicknames_output(&nicknames, character.as_deref()))
+        }
+        "compare" => {
+            let a = resolve_move(
+                data_dir,
+                arg(&args, 1, "character")?,
+                arg(&args, 2, "move")?,
+            )
+            .await?;
+            let b = resolve_move(
+                data_dir,
+                arg(&args, 3, "character")?,
+                arg(&args, 4, "move")?,
+            )
+            .await?;
+            Ok(compare_output((&a.0, &a.1), (&b.0, &b.1)))
+        }
+        command => Err(AppError::Config(format!(
+            "Unknown command `{command}`.\n\n{USAGE}"
+        ))),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let format = if args.iter().any(|a| a == "--tsv") {
+        args.retain(|a| a != "--tsv");
+        OutputFormat::Tsv
+    } else {
+        OutputFormat::Table
+    };
+    let data_dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "data".to_string());
+
+    match run(&data_dir, &args).await {
+        Ok(output) => {
+            println!("{}", output.render(format));
+            if output.failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use baiken::test_utils::MoveInfoBuilder;
+
+    #[test]
+    fn test_frames_output() {
+        let output = frames_output(
+            "Sol_Badguy",
+            &MoveInfoBuilder::new("5K")
+                .move_type("normal")
+                .frames(5, "3", 9)
+                .build(),
+            EmbedView::Simple,
+        );
+        assert_eq!(output.rows[1], vec!["input", "5K"]);
+        assert!(output
+            .rows
+            .contains(&vec!["startup".to_string(), "5".to_string()]));
+        assert_eq!(
+            output.rows.len(),
+            3 + EmbedView::Simple.default_fields().len()
+        );
+    }
+
+    #[test]
+    fn test_moves_output() {
+        let moves = vec![
+            MoveInfoBuilder::new("236K")
+                .move_type("special")
+                .frames(13, "3", 9)
+                .build(),
+            MoveInfoBuilder::new("5K")
+                .move_type("normal")
+                .frames(5, "3", 9)
+                .build(),
+            MoveInfoBuilder::new("足払い(2D)")
+                .move_type("normal")
+                .frames(10, "3", 20)
+                .build(),
+        ];
+        let aliases = vec![
+            MoveAliases {
+                input: "236K".to_string(),
+                aliases: vec!["Gunflame".to_string(), "GF".to_string()],
+            },
+            MoveAliases {
+                input: "2D".to_string(),
+                aliases: vec!["sweep".to_string()],
+            },
+        ];
+        let output = moves_output(&moves, &aliases, TypeChoice::All);
+        assert_eq!(output.rows.len(), 3);
+        assert_eq!(output.rows[0][0], "specials");
+        assert_eq!(output.rows[0][6], "Gunflame, GF");
+        assert_eq!(output.rows[2][6], "sweep");
+
+        let normals = moves_output(&moves, &aliases, TypeChoice::Normals);
+        assert_eq!(normals.rows.len(), 2);
+        assert_eq!(normals.rows[0][1], "5K");
+    }
+
+    #[test]
+    fn test_compare_output_and_render() {
+        let output = compare_output(
+            (
+                "Sol_Badguy",
+                &MoveInfoBuilder::new("5K")
+                    .move_type("normal")
+                    .frames(5, "3", 9)
+                    .build(),
+            ),
+            (
+                "Ky_Kiske",
+                &MoveInfoBuilder::new("5K")
+                    .move_type("normal")
+                    .frames(6, "3", 9)
+                    .build(),
+            ),
+        );
+        assert_eq!(output.headers[1], "Sol_Badguy 5K");
+        let startup = output.rows.iter().find(|r| r[0] == "startup").unwrap();
+        assert_eq!(startup[3], "*");
+        let damage = output.rows.iter().find(|r| r[0] == "damage").unwrap();
+        assert_eq!(damage[3], "");
+
+        let tsv = output.render(OutputFormat::Tsv);
+        assert!(tsv.starts_with("field\tSol_Badguy 5K\tKy_Kiske 5K\tdiff\n"));
+    }
+
+    #[test]
+    fn test_update_output() {
+        let report = pipeline::UpdateReport {
+            character: "Sol_Badguy".to_string(),
+            kind: UpdateKind::Frames,
+            path: PathBuf::from("data/Sol_Badguy/Sol_Badguy.json"),
+            changes: vec![pipeline::Change {
+                kind: pipeline::ChangeKind::Modified,
+                input: "5K".to_string(),
+                fields: vec![
+                    ("startup".to_string(), "5".to_string(), "6".to_string()),
+                    ("on_block".to_string(), "-1".to_string(), "-2".to_string()),
+                ],
+            }],
+            written: false,
+        };
+        let output = update_output(&[report], true);
+        assert!(output.failed);
+        assert_eq!(
+            output.rows,
+            vec![vec![
+                "Sol_Badguy",
+                "frames",
+                "~",
+                "5K",
+                "startup: 5 -> 6; on_block: -1 -> -2"
+            ]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_options() {
+        let args: Vec<String> = ["--images-only", "--dry-run", "--output-dir", "/tmp/out"]
+            .iter()
+            .map(|a| (*a).to_string())
+            .collect();
+        let options = update_options(&args, "data").await.unwrap();
+        assert_eq!(options.kinds, vec![UpdateKind::Images]);
+        assert!(options.dry_run);
+        assert_eq!(options.output_dir, PathBuf::from("/tmp/out"));
+        assert_eq!(options.characters.len(), CHARS.len());
+
+        let missing = vec!["--output-dir".to_string()];
+        assert!(update_options(&missing, "data").await.is_err());
+        let unknown = vec!["--bogus".to_string()];
+        assert!(update_options(&unknown, "data").await.is_err());
+    }
+
+    #[test]
+    fn test_hitboxes_and_nicknames_output() {
+        let links = vec![ImageLinks {
+            input: "5K".to_string(),
+            move_img: String::new(),
+            hitbox_img: vec!["a.png".to_string(), "b.png".to_string()],
+        }];
+        let output = hitboxes_output(
+            &MoveInfoBuilder::new("5K")
+                .move_type("normal")
+                .frames(5, "3", 9)
+                .build(),
+            &links,
+        );
+        assert_eq!(output.rows, vec![vec!["1", "a.png"], vec!["2", "b.png"]]);
+
+        let nicknames = vec![baiken::Nicknames {
+            character: "Sol_Badguy".to_string(),
+            nicknames: vec!["sol".to_string()],
+        }];
+        assert_eq!(nicknames_output(&nicknames, Some("Ky_Kiske")).rows.len(), 0);
+        assert_eq!(nicknames_output(&nicknames, None).rows[0][1], "sol");
+    }
+}
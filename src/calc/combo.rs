@@ -0,0 +1,263 @@
+//! `combo.rs`
+//!
+//! コンボダメージを計算する機能を提供する。
+//! 技毎の基礎ダメージへ補正（始動補正・強制補正・ヒット数補正）、根性値、防御係数、カウンター補正を適用し、
+//! 1 ヒット毎の内訳と合計ダメージ・獲得テンションを求める。
+
+use crate::CharInfo;
+use crate::MoveInfo;
+
+/// 全キャラクター共通の最大体力
+pub const MAX_HEALTH: u32 = 420;
+
+/// ヒット数補正が掛かり始めるヒット数（このヒットから補正）
+const HIT_SCALING_START: usize = 3;
+
+/// ヒット数補正の 1 ヒット毎の倍率
+const HIT_SCALING_RATE: f64 = 0.9;
+
+/// 補正の下限
+const MIN_PRORATION: f64 = 0.1;
+
+/// カウンターヒット時のダメージ倍率
+const COUNTER_DAMAGE_RATE: f64 = 1.1;
+
+/// 根性値の既定ランク（キャラクター情報が無い場合）
+const DEFAULT_GUTS_RANK: usize = 2;
+
+/// 根性補正の体力閾値（%、降順）
+const GUTS_THRESHOLDS: [u32; 5] = [50, 40, 30, 20, 10];
+
+/// 根性ランク毎の被ダメージ倍率（体力が各閾値以下の時、閾値順）
+const GUTS_TABLE: [[f64; 5]; 6] = [
+    [1.00, 0.94, 0.88, 0.82, 0.76],
+    [0.97, 0.91, 0.85, 0.79, 0.73],
+    [0.94, 0.88, 0.82, 0.76, 0.70],
+    [0.91, 0.85, 0.79, 0.73, 0.67],
+    [0.88, 0.82, 0.76, 0.70, 0.64],
+    [0.85, 0.79, 0.73, 0.67, 0.61],
+];
+
+/// 被コンボ側のキャラクター情報
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Defender {
+    /// 防御係数（被ダメージ倍率）
+    pub defense: f64,
+    /// 根性ランク（0〜5）
+    pub guts: usize,
+    /// コンボ開始時の体力
+    pub health: u32,
+}
+
+impl Default for Defender {
+    fn default() -> Self {
+        Self {
+            defense: 1.0,
+            guts: DEFAULT_GUTS_RANK,
+            health: MAX_HEALTH,
+        }
+    }
+}
+
+impl Defender {
+    /// キャラクター情報と体力割合（%）から生成する。
+    pub fn from_char_info(info: &CharInfo, health_percent: u32) -> Self {
+        Self {
+            defense: info.defense.unwrap_or(1.0),
+            guts: info.guts.map_or(DEFAULT_GUTS_RANK, |guts| {
+                guts.round().clamp(0.0, 5.0) as usize
+            }),
+            health: health_from_percent(health_percent),
+        }
+    }
+}
+
+/// 体力割合（%）から体力値を返す関数
+pub fn health_from_percent(percent: u32) -> u32 {
+    MAX_HEALTH * percent.min(100) / 100
+}
+
+/// 根性補正倍率関数
+/// 現在体力の割合が閾値以下であれば、該当する最も低い閾値の倍率を返す。
+pub fn guts_rate(guts: usize, health: u32) -> f64 {
+    let percent = health * 100 / MAX_HEALTH;
+    let row = &GUTS_TABLE[guts.min(GUTS_TABLE.len() - 1)];
+    GUTS_THRESHOLDS
+        .iter()
+        .zip(row)
+        .rfind(|(threshold, _)| percent <= **threshold)
+        .map_or(1.0, |(_, rate)| *rate)
+}
+
+/// コンボ 1 段分の入力
+#[derive(Debug, Clone)]
+pub struct ComboStep {
+    /// 技情報
+    pub move_info: MoveInfo,
+    /// カウンターヒットか否か
+    pub counter: bool,
+}
+
+/// コンボ 1 段分の計算結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitResult {
+    /// 技の入力
+    pub input: String,
+    /// 基礎ダメージ
+    pub base: u32,
+    /// 適用した補正
+    pub proration: f64,
+    /// 適用した根性補正
+    pub guts: f64,
+    /// カウンターヒットか否か
+    pub counter: bool,
+    /// 実ダメージ
+    pub damage: u32,
+    /// 累計ダメージ
+    pub total: u32,
+}
+
+/// コンボの計算結果
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComboResult {
+    /// 1 段毎の内訳
+    pub hits: Vec<HitResult>,
+    /// 合計ダメージ
+    pub total: u32,
+    /// 獲得テンション
+    pub tension: f64,
+    /// ダメージ不明のため計算から除外した技の入力
+    pub skipped: Vec<String>,
+}
+
+/// コンボダメージ計算関数
+///
+/// # 概要
+/// 1 技を 1 ヒットとして扱い、以下の順に適用する。
+/// - 補正：始動技の `scaling` は始動補正としてコンボ全体へ、2 段目以降の `scaling` は強制補正として以降の段へ掛ける。
+///   更に [`HIT_SCALING_START`] 段目から 1 段毎に [`HIT_SCALING_RATE`] を掛ける（下限 [`MIN_PRORATION`]）。
+/// - 根性補正：各段のヒット直前の体力から求める。
+/// - 防御係数・カウンター補正を掛け、端数を切り捨てる（最低 1）。
+///
+/// # 引数
+/// * `steps` - コンボの各段（入力順）
+/// * `defender` - 被コンボ側の情報
+///
+/// # 戻り値
+/// 計算結果
+pub fn calculate(steps: &[ComboStep], defender: &Defender) -> ComboResult {
+    let mut result = ComboResult::default();
+    let mut forced = 1.0;
+    let mut initial = 1.0;
+    let mut health = defender.health;
+
+    for (i, step) in steps.iter().enumerate() {
+        let info = &step.move_info;
+        result.tension += info.input_tension.unwrap_or(0.0);
+        let Some(base) = info.damage.and_then(|damage| u32::try_from(damage).ok()) else {
+            result.skipped.push(info.input.clone());
+            continue;
+        };
+        let hit = result.hits.len() + 1;
+
+        let hit_scaling = HIT_SCALING_RATE.powi(hit.saturating_sub(HIT_SCALING_START - 1) as i32);
+        let proration = (initial * forced * hit_scaling).max(MIN_PRORATION);
+        let guts = guts_rate(defender.guts, health);
+        let counter = if step.counter {
+            COUNTER_DAMAGE_RATE
+        } else {
+            1.0
+        };
+        let damage = ((f64::from(base) * proration * guts * defender.defense * counter).floor()
+            as u32)
+            .max(1);
+
+        result.total += damage;
+        health = health.saturating_sub(damage);
+        result.hits.push(HitResult {
+            input: info.input.clone(),
+            base,
+            proration,
+            guts,
+            counter: step.counter,
+            damage,
+            total: result.total,
+        });
+
+        // 補正は次の段から適用（始動補正のみ始動技を含めず以降全体）
+        if let Some(scaling) = info.scaling.filter(|s| *s > 0.0) {
+            if i == 0 {
+                initial = scaling;
+            } else {
+                forced *= scaling;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+
+    fn step(damage: Option<i32>, scaling: Option<f64>, counter: bool) -> ComboStep {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.damage = damage;
+        move_info.scaling = scaling;
+        move_info.input_tension = Some(50.0);
+        ComboStep { move_info, counter }
+    }
+
+    #[test]
+    fn test_guts_rate() {
+        assert_eq!(guts_rate(0, MAX_HEALTH), 1.0);
+        assert_eq!(guts_rate(0, MAX_HEALTH / 2), 1.0);
+        assert_eq!(guts_rate(2, MAX_HEALTH / 2), 0.94);
+        assert_eq!(guts_rate(2, health_from_percent(15)), 0.76);
+        assert_eq!(guts_rate(9, 0), GUTS_TABLE[5][4]);
+    }
+
+    #[test]
+    fn test_calculate_scaling() {
+        let defender = Defender::default();
+        let result = calculate(
+            &[
+                step(Some(100), Some(0.8), false),
+                step(Some(100), None, false),
+                step(Some(100), None, false),
+            ],
+            &defender,
+        );
+        let damages: Vec<u32> = result.hits.iter().map(|h| h.damage).collect();
+        // 2 段目は始動補正 0.8、3 段目は更にヒット数補正 0.9
+        assert_eq!(damages, vec![100, 80, 72]);
+        assert_eq!(result.total, 252);
+        assert_eq!(result.tension, 150.0);
+    }
+
+    #[test]
+    fn test_calculate_defender_and_counter() {
+        let defender = Defender {
+            defense: 0.9,
+            guts: 0,
+            health: MAX_HEALTH,
+        };
+        let result = calculate(
+            &[step(Some(100), None, true), step(None, None, false)],
+            &defender,
+        );
+        assert_eq!(result.hits[0].damage, 99);
+        assert!(result.hits[0].counter);
+        assert_eq!(result.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_defender_from_char_info() {
+        let info = crate::test_utils::_create_test_char_info();
+        let defender = Defender::from_char_info(&info, 50);
+        assert_eq!(defender.defense, 0.9);
+        assert_eq!(defender.guts, 2);
+        assert_eq!(defender.health, MAX_HEALTH / 2);
+    }
+}
@@ -0,0 +1,217 @@
+//! `counter.rs`
+//!
+//! 攻撃レベルとカウンターヒット種別から硬直・ヒットストップを求める機能を提供する。
+//! 通常ヒット時の硬直差にカウンターヒットの追加硬直・追加ヒットストップを加え、カウンターヒット時の硬直差と繋がる技を判定する。
+
+use crate::timeline::parse_advantage;
+use crate::MoveInfo;
+
+/// 攻撃レベル毎の硬直・ヒットストップ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelData {
+    /// ヒットストップ
+    pub hitstop: u32,
+    /// 立ちヒット硬直
+    pub hitstun: u32,
+    /// しゃがみヒット硬直
+    pub crouch_hitstun: u32,
+    /// ガード硬直
+    pub blockstun: u32,
+}
+
+/// 攻撃レベル表（添字がレベル 0〜4）
+pub const LEVEL_TABLE: [LevelData; 5] = [
+    LevelData {
+        hitstop: 11,
+        hitstun: 12,
+        crouch_hitstun: 13,
+        blockstun: 9,
+    },
+    LevelData {
+        hitstop: 12,
+        hitstun: 14,
+        crouch_hitstun: 15,
+        blockstun: 11,
+    },
+    LevelData {
+        hitstop: 13,
+        hitstun: 16,
+        crouch_hitstun: 17,
+        blockstun: 13,
+    },
+    LevelData {
+        hitstop: 14,
+        hitstun: 19,
+        crouch_hitstun: 20,
+        blockstun: 16,
+    },
+    LevelData {
+        hitstop: 15,
+        hitstun: 21,
+        crouch_hitstun: 22,
+        blockstun: 18,
+    },
+];
+
+/// 攻撃レベル解析関数
+/// "3 [4]"・"2,2,3" 等は先頭のレベルを返す（"-" 等は None）。
+pub fn parse_level(text: &str) -> Option<usize> {
+    text.trim()
+        .chars()
+        .next()
+        .and_then(|c| c.to_digit(10))
+        .map(|level| level as usize)
+        .filter(|level| *level < LEVEL_TABLE.len())
+}
+
+/// 攻撃レベル表の値を返す関数
+pub fn level_data(text: &str) -> Option<LevelData> {
+    parse_level(text).map(|level| LEVEL_TABLE[level])
+}
+
+/// カウンターヒット種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterType {
+    /// 極小
+    VerySmall,
+    /// 小
+    Small,
+    /// 中
+    Mid,
+    /// 大
+    Large,
+}
+
+/// カウンターヒット時の追加分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterBonus {
+    /// 被弾側のみに加わる追加ヒットストップ
+    pub hitstop: u32,
+    /// 追加ヒット硬直
+    pub hitstun: u32,
+}
+
+impl CounterBonus {
+    /// 硬直差への加算値を返す。
+    pub fn advantage(self) -> i32 {
+        (self.hitstop + self.hitstun) as i32
+    }
+}
+
+impl CounterType {
+    /// カウンター欄の表記から種別を返す。
+    /// "Mid, Very Small"・"Large×2" 等は先頭の種別を返す（"-" 等は None）。
+    pub fn parse(text: &str) -> Option<Self> {
+        let first = text
+            .split([',', '×'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        match first.as_str() {
+            "very small" => Some(Self::VerySmall),
+            "small" => Some(Self::Small),
+            "mid" => Some(Self::Mid),
+            "large" => Some(Self::Large),
+            _ => None,
+        }
+    }
+
+    /// 追加ヒットストップ・追加ヒット硬直を返す。
+    pub fn bonus(self) -> CounterBonus {
+        let (hitstop, hitstun) = match self {
+            Self::VerySmall => (2, 0),
+            Self::Small => (4, 2),
+            Self::Mid => (8, 4),
+            Self::Large => (12, 6),
+        };
+        CounterBonus { hitstop, hitstun }
+    }
+}
+
+/// カウンターヒット時の硬直差
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterAdvantage {
+    /// 通常ヒット時の硬直差
+    pub on_hit: i32,
+    /// カウンターヒットによる加算値
+    pub bonus: i32,
+}
+
+impl CounterAdvantage {
+    /// カウンターヒット時の硬直差を返す。
+    pub fn on_counter(self) -> i32 {
+        self.on_hit + self.bonus
+    }
+}
+
+/// カウンターヒット時の硬直差計算関数
+/// ヒット時の硬直差が数値でない（ダウン等）またはカウンター種別が不明な場合は None を返す。
+pub fn counter_advantage(info: &MoveInfo) -> Option<CounterAdvantage> {
+    let on_hit = parse_advantage(&info.on_hit)?;
+    let counter = CounterType::parse(&info.counter)?;
+    Some(CounterAdvantage {
+        on_hit,
+        bonus: counter.bonus().advantage(),
+    })
+}
+
+/// 硬直差から次の技が目押しで繋がるか否かを返す関数（発生が不明な場合は None）
+/// 連係の隙間（`発生 - 1 - 硬直差`）と同じく、発生の 1 フレーム前までに硬直が解ければ繋がる。
+pub fn links_into(advantage: i32, follow_up: &MoveInfo) -> Option<bool> {
+    follow_up.startup.map(|startup| startup - 1 <= advantage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MoveInfoBuilder;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("3 [4]"), Some(3));
+        assert_eq!(parse_level("2,2,3"), Some(2));
+        assert_eq!(parse_level("-"), None);
+        assert_eq!(parse_level("5"), None);
+        assert_eq!(level_data("0").unwrap().hitstun, 12);
+    }
+
+    #[test]
+    fn test_counter_type_parse() {
+        assert_eq!(
+            CounterType::parse("Mid, Very Small"),
+            Some(CounterType::Mid)
+        );
+        assert_eq!(CounterType::parse("Large×2"), Some(CounterType::Large));
+        assert_eq!(
+            CounterType::parse("Very Small"),
+            Some(CounterType::VerySmall)
+        );
+        assert_eq!(CounterType::parse("-"), None);
+    }
+
+    #[test]
+    fn test_counter_advantage_and_links() {
+        let mut info = MoveInfoBuilder::new("5P")
+            .on_hit("+2")
+            .counter("Mid")
+            .build();
+        let advantage = counter_advantage(&info).unwrap();
+        assert_eq!(advantage.on_counter(), 2 + 12);
+
+        let mut follow_up = info.clone();
+        follow_up.startup = Some(15);
+        assert_eq!(links_into(advantage.on_counter(), &follow_up), Some(true));
+        follow_up.startup = Some(16);
+        assert_eq!(links_into(advantage.on_counter(), &follow_up), Some(false));
+        // +4 から発生 5F の技は繋がる（隙間 0F）
+        follow_up.startup = Some(5);
+        assert_eq!(links_into(4, &follow_up), Some(true));
+        assert_eq!(links_into(3, &follow_up), Some(false));
+        follow_up.startup = None;
+        assert_eq!(links_into(advantage.on_counter(), &follow_up), None);
+
+        info.on_hit = "ダウン+36".to_string();
+        assert!(counter_advantage(&info).is_none());
+    }
+}
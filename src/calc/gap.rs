@@ -0,0 +1,202 @@
+//! `gap.rs`
+//!
+//! 2 つの技の間のガード時の隙間を判定する機能を提供する。
+//! 連続ガード・暴れ潰し（フレームトラップ）・切り返し可能な隙間を分類し、隙間に割り込める共通行動を列挙する。
+
+use crate::error::{AppError, Result};
+use crate::timeline::{Sequence, Transition};
+use crate::MoveInfo;
+
+/// フレームトラップとみなす最大隙間フレーム（これを超えると大抵の通常技で割り込める）
+pub const FRAME_TRAP_LIMIT: u32 = 4;
+
+/// 全キャラクター共通の割り込み行動
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniversalOption {
+    /// 行動名
+    pub name: &'static str,
+    /// 割り込みに必要な隙間フレーム数
+    pub frames: u32,
+    /// 補足
+    pub note: &'static str,
+}
+
+/// 共通の割り込み行動一覧（必要フレームの昇順）
+pub const UNIVERSAL_OPTIONS: [UniversalOption; 4] = [
+    UniversalOption {
+        name: "Invincible reversal",
+        frames: 1,
+        note: "invincible from frame 1",
+    },
+    UniversalOption {
+        name: "Throw",
+        frames: 2,
+        note: "2F startup, needs to be in range",
+    },
+    UniversalOption {
+        name: "Jump",
+        frames: 5,
+        note: "airborne after 4F prejump",
+    },
+    UniversalOption {
+        name: "5F normal",
+        frames: 5,
+        note: "fastest normals of most characters",
+    },
+];
+
+/// 隙間の判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapVerdict {
+    /// 連続ガード（割り込み不可）
+    TrueBlockstring,
+    /// フレームトラップ（隙間フレーム数）
+    FrameTrap(u32),
+    /// 切り返し可能な隙間（隙間フレーム数）
+    ReversalWindow(u32),
+}
+
+impl GapVerdict {
+    /// 隙間フレームから判定結果を返す。
+    pub fn classify(gap: i32) -> Self {
+        match u32::try_from(gap) {
+            Ok(0) | Err(_) => Self::TrueBlockstring,
+            Ok(frames) if frames <= FRAME_TRAP_LIMIT => Self::FrameTrap(frames),
+            Ok(frames) => Self::ReversalWindow(frames),
+        }
+    }
+
+    /// 表示用の説明文を返す。
+    pub fn label(self) -> String {
+        match self {
+            Self::TrueBlockstring => "True blockstring".to_string(),
+            Self::FrameTrap(frames) => format!("Frame trap ({frames}F gap)"),
+            Self::ReversalWindow(frames) => format!("Reversal window ({frames}F gap)"),
+        }
+    }
+}
+
+/// 隙間の解析結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GapReport {
+    /// 繋ぎ方
+    pub transition: Transition,
+    /// 隙間フレーム（正の値は相手が行動可能なフレーム数、0 以下は連続ガード）
+    pub gap: i32,
+    /// 判定結果
+    pub verdict: GapVerdict,
+    /// 隙間に割り込める共通行動
+    pub beaten_by: Vec<UniversalOption>,
+}
+
+/// 隙間に割り込める共通行動を返す関数
+/// 行動の必要フレームが隙間以下であれば次の技の持続より先に動ける。
+pub fn beating_options(gap: i32) -> Vec<UniversalOption> {
+    UNIVERSAL_OPTIONS
+        .iter()
+        .filter(|option| i64::from(option.frames) <= i64::from(gap))
+        .copied()
+        .collect()
+}
+
+/// 技間の隙間解析関数
+///
+/// # 概要
+/// 先の技のガード硬直差と次の技の発生から隙間を計算する（キャンセル可能な場合は先の技の硬直を差し引く）。
+///
+/// # 引数
+/// * `first` - 先に出す技
+/// * `second` - 次に出す技
+///
+/// # 戻り値
+/// 解析結果（ガード硬直差・発生が不明な場合はエラー）
+pub fn analyze(first: &MoveInfo, second: &MoveInfo) -> Result<GapReport> {
+    let sequence = Sequence::from_moves(&[first.clone(), second.clone()])?;
+    let step = sequence.steps[0];
+    let gap = step.gap.ok_or_else(|| {
+        AppError::DataProcessing(format!(
+            "`{}` の硬直差 ({}) または `{}` の発生が不明です",
+            first.input, first.on_block, second.input
+        ))
+    })?;
+    Ok(GapReport {
+        transition: step.transition,
+        gap,
+        verdict: GapVerdict::classify(gap),
+        beaten_by: beating_options(gap),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MoveInfoBuilder;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(GapVerdict::classify(-3), GapVerdict::TrueBlockstring);
+        assert_eq!(GapVerdict::classify(0), GapVerdict::TrueBlockstring);
+        assert_eq!(GapVerdict::classify(2), GapVerdict::FrameTrap(2));
+        assert_eq!(
+            GapVerdict::classify(FRAME_TRAP_LIMIT as i32 + 1),
+            GapVerdict::ReversalWindow(FRAME_TRAP_LIMIT + 1)
+        );
+    }
+
+    #[test]
+    fn test_beating_options() {
+        assert!(beating_options(0).is_empty());
+        let names: Vec<&str> = beating_options(2).iter().map(|o| o.name).collect();
+        assert_eq!(names, vec!["Invincible reversal", "Throw"]);
+        assert_eq!(beating_options(10).len(), UNIVERSAL_OPTIONS.len());
+    }
+
+    #[test]
+    fn test_analyze_link_and_cancel() {
+        // 目押し：発生 7 - 1 - 硬直差 (+2) = 4F の隙間
+        let first = MoveInfoBuilder::new("236K")
+            .move_type("special")
+            .frames(10, "3", 10)
+            .on_block("+2")
+            .cancel("-")
+            .build();
+        let second = MoveInfoBuilder::new("5K")
+            .move_type("normal")
+            .frames(7, "3", 10)
+            .on_block("-5")
+            .cancel("-")
+            .build();
+        let report = analyze(&first, &second).unwrap();
+        assert_eq!(report.transition, Transition::Link);
+        assert_eq!(report.gap, 4);
+        assert_eq!(report.verdict, GapVerdict::FrameTrap(4));
+
+        // キャンセル：更に硬直 10F を差し引く
+        let first = MoveInfoBuilder::new("5K")
+            .move_type("normal")
+            .frames(5, "3", 10)
+            .on_block("-8")
+            .cancel("-")
+            .build();
+        let second = MoveInfoBuilder::new("2D")
+            .move_type("normal")
+            .frames(3, "3", 10)
+            .on_block("-12")
+            .cancel("-")
+            .build();
+        let report = analyze(&first, &second).unwrap();
+        assert_eq!(report.transition, Transition::Cancel);
+        assert_eq!(report.gap, 3 - 1 + 8 - 10);
+        assert_eq!(report.verdict, GapVerdict::TrueBlockstring);
+        assert!(report.beaten_by.is_empty());
+
+        // 硬直差不明
+        let first = MoveInfoBuilder::new("5K")
+            .move_type("normal")
+            .frames(5, "3", 10)
+            .on_block("-")
+            .cancel("-")
+            .build();
+        assert!(analyze(&first, &second).is_err());
+    }
+}
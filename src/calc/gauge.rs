@@ -0,0 +1,151 @@
+//! `gauge.rs`
+//!
+//! R.I.S.C. ゲージと壁耐久の蓄積を計算する機能を提供する。
+//! ガードさせた連係で溜まる R.I.S.C. レベルと、コンボで与える壁ダメージ・壁破壊の有無を求める。
+
+use crate::MoveInfo;
+
+/// R.I.S.C. ゲージの最大値（ゲージ満タンで次のヒットがカウンターヒット）
+pub const RISC_MAX: f64 = 12800.0;
+
+/// 壁耐久の最大値（壁ダメージの累計がこれに達すると壁破壊）
+pub const WALL_MAX: f64 = 3000.0;
+
+/// ゲージ 1 段分の計算結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaugeStep {
+    /// 技の入力
+    pub input: String,
+    /// 増加量
+    pub amount: f64,
+    /// 増加後のゲージ（%）
+    pub percent: f64,
+}
+
+/// ゲージ蓄積の計算結果
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GaugeResult {
+    /// 1 段毎の内訳
+    pub steps: Vec<GaugeStep>,
+    /// 開始時のゲージ（%）
+    pub start: f64,
+    /// 終了時のゲージ（%、上限 100）
+    pub end: f64,
+    /// ゲージが満タンになった段（`steps` の添字、満タンにならない場合は None）
+    pub full_at: Option<usize>,
+    /// 値が不明のため計算から除外した技の入力
+    pub skipped: Vec<String>,
+}
+
+impl GaugeResult {
+    /// 増加量の合計を返す。
+    pub fn total(&self) -> f64 {
+        self.steps.iter().map(|step| step.amount).sum()
+    }
+}
+
+/// ゲージ蓄積計算関数
+///
+/// # 引数
+/// * `moves` - 技情報一覧（入力順）
+/// * `start_percent` - 開始時のゲージ（%）
+/// * `max` - ゲージの最大値
+/// * `amount_of` - 技 1 つ分の増加量（不明な場合は None）
+fn accumulate(
+    moves: &[MoveInfo],
+    start_percent: f64,
+    max: f64,
+    amount_of: impl Fn(&MoveInfo) -> Option<f64>,
+) -> GaugeResult {
+    let start = start_percent.clamp(0.0, 100.0);
+    let mut result = GaugeResult {
+        start,
+        end: start,
+        ..GaugeResult::default()
+    };
+    let mut gauge = max * start / 100.0;
+    for info in moves {
+        let Some(amount) = amount_of(info) else {
+            result.skipped.push(info.input.clone());
+            continue;
+        };
+        gauge = (gauge + amount).min(max);
+        let percent = gauge / max * 100.0;
+        if result.full_at.is_none() && gauge >= max {
+            result.full_at = Some(result.steps.len());
+        }
+        result.steps.push(GaugeStep {
+            input: info.input.clone(),
+            amount,
+            percent,
+        });
+        result.end = percent;
+    }
+    result
+}
+
+/// ガード連係の R.I.S.C. 蓄積計算関数
+/// 各技の `risc_gain` をガードさせる毎に加算する。
+pub fn risc_buildup(moves: &[MoveInfo], start_percent: f64) -> GaugeResult {
+    accumulate(moves, start_percent, RISC_MAX, |info| info.risc_gain)
+}
+
+/// コンボの壁ダメージ蓄積計算関数
+/// 各技の `wall_damage` をヒットさせる毎に加算する。
+pub fn wall_buildup(moves: &[MoveInfo], start_percent: f64) -> GaugeResult {
+    accumulate(moves, start_percent, WALL_MAX, |info| {
+        info.wall_damage.map(f64::from)
+    })
+}
+
+/// コンボで相手の R.I.S.C. ゲージが減る量（%）を返す関数
+/// 各技の `risc_loss` はヒット時に相手の R.I.S.C. ゲージを減らす量。
+pub fn risc_loss_percent(moves: &[MoveInfo]) -> f64 {
+    moves.iter().filter_map(|info| info.risc_loss).sum::<f64>() / RISC_MAX * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MoveInfoBuilder;
+
+    #[test]
+    fn test_risc_buildup() {
+        let risc = |gain| {
+            MoveInfoBuilder::new("5P")
+                .risc(gain, Some(RISC_MAX / 10.0))
+                .build()
+        };
+        let moves = vec![
+            risc(Some(RISC_MAX / 4.0)),
+            risc(None),
+            risc(Some(RISC_MAX / 4.0)),
+        ];
+        let result = risc_buildup(&moves, 25.0);
+        assert_eq!(result.steps.len(), 2);
+        assert_eq!(result.end, 75.0);
+        assert_eq!(result.total(), RISC_MAX / 2.0);
+        assert_eq!(result.full_at, None);
+        assert_eq!(result.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_wall_buildup_breaks() {
+        let moves = vec![MoveInfoBuilder::new("5P").wall_damage(Some(1000)).build(); 4];
+        let result = wall_buildup(&moves, 10.0);
+        assert_eq!(result.full_at, Some(2));
+        assert_eq!(result.end, 100.0);
+        assert!(wall_buildup(&moves[..1], 0.0).full_at.is_none());
+    }
+
+    #[test]
+    fn test_risc_loss_percent() {
+        let moves = vec![
+            MoveInfoBuilder::new("5P")
+                .risc(None, Some(RISC_MAX / 10.0))
+                .build();
+            3
+        ];
+        assert!((risc_loss_percent(&moves) - 30.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,10 @@
+//! `calc/mod.rs`
+//!
+//! フレームデータを用いた計算機能群を提供する。
+//! 技間の隙間判定、カウンターヒット時の硬直差、コンボダメージ、R.I.S.C.・壁耐久の蓄積、起き攻めの重ねなど、技情報から攻防の結果を導出する処理を含む。
+
+pub mod combo; // コンボダメージ計算
+pub mod counter; // カウンターヒット時の硬直差計算
+pub mod gap; // 技間の隙間判定
+pub mod gauge; // R.I.S.C.・壁耐久の蓄積計算
+pub mod oki; // 起き攻めの重ね計算
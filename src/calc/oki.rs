@@ -0,0 +1,259 @@
+//! `oki.rs`
+//!
+//! ダウン後の起き攻め（重ね）を計算する機能を提供する。
+//! ダウン技のダウン時間から移動・空振り等の行動時間を差し引き、相手の起き上がりに持続が重なる技を求める。
+
+use crate::timeline::{FrameKind, Timeline};
+use crate::{CharInfo, MoveInfo};
+
+/// 前ダッシュ 1 回分の汎用フレーム数（キャラクター情報に所要フレーム数が無いため全キャラクター共通）
+const DEFAULT_DASH_FRAMES: u32 = 10;
+
+/// ジャンプ移行の既定フレーム数（キャラクター情報が無い場合）
+const DEFAULT_PREJUMP: u32 = 4;
+
+/// ジャンプ滞空の既定フレーム数（キャラクター情報が無い場合）
+const DEFAULT_JUMP_DURATION: u32 = 45;
+
+/// バックステップの既定フレーム数（キャラクター情報が無い場合）
+const DEFAULT_BACKDASH_DURATION: u32 = 20;
+
+/// ダウン情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Knockdown {
+    /// 強制ダウン（受け身不可）か否か
+    pub hard: bool,
+    /// ダウン時の硬直差（表記が無い場合は None）
+    pub advantage: Option<i32>,
+}
+
+/// ヒット時の表記からダウン情報を解析する関数
+/// "ダウン+36"・"強制ダウン+64"・"HKD +50"・"KD" 等に対応し、ダウンしない技は None を返す。
+pub fn parse_knockdown(on_hit: &str) -> Option<Knockdown> {
+    let hard = on_hit.contains("強制ダウン") || on_hit.contains("HKD");
+    if !hard && !on_hit.contains("ダウン") && !on_hit.contains("KD") {
+        return None;
+    }
+    let advantage = on_hit.find('+').and_then(|index| {
+        on_hit[index + 1..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .ok()
+    });
+    Some(Knockdown { hard, advantage })
+}
+
+/// キャラクター毎の移動行動フレーム数（キャラクター情報に無い項目は None）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Movement {
+    /// ジャンプ（移行から着地まで）
+    pub jump: Option<u32>,
+    /// バックステップ
+    pub backdash: Option<u32>,
+}
+
+impl Movement {
+    /// キャラクター情報から生成する。
+    pub fn from_char_info(info: &CharInfo) -> Self {
+        let frames = |value: Option<i32>| value.and_then(|v| u32::try_from(v).ok());
+        Self {
+            jump: frames(info.prejump)
+                .zip(frames(info.jump_duration))
+                .map(|(prejump, duration)| prejump + duration),
+            backdash: frames(info.backdash_duration),
+        }
+    }
+}
+
+/// 起き攻めまでの行動
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OkiAction {
+    /// 表示名
+    pub label: String,
+    /// 所要フレーム数
+    pub frames: u32,
+    /// キャラクター情報ではなく汎用値を用いたか否か
+    pub estimated: bool,
+}
+
+impl OkiAction {
+    /// 行動指定を解析する。
+    /// "dash"・"jump"・"backdash"・数値（待機フレーム）に対応し、それ以外は None（技の空振りとして扱う）。
+    /// ダッシュは常に汎用値を、ジャンプ・バックステップはキャラクター情報に無い場合に既定値を用いる。
+    pub fn parse(text: &str, movement: &Movement) -> Option<Self> {
+        let lower = text.trim().to_lowercase();
+        let (label, frames) = match lower.as_str() {
+            "dash" => ("Dash", Err(DEFAULT_DASH_FRAMES)),
+            "jump" => (
+                "Jump",
+                movement.jump.ok_or(DEFAULT_PREJUMP + DEFAULT_JUMP_DURATION),
+            ),
+            "backdash" => (
+                "Backdash",
+                movement.backdash.ok_or(DEFAULT_BACKDASH_DURATION),
+            ),
+            _ => {
+                let frames = lower
+                    .strip_prefix("wait")
+                    .unwrap_or(&lower)
+                    .trim()
+                    .parse()
+                    .ok()?;
+                ("Wait", Ok(frames))
+            }
+        };
+        Some(Self {
+            label: label.to_string(),
+            frames: frames.unwrap_or_else(|default| default),
+            estimated: frames.is_err(),
+        })
+    }
+
+    /// 技の空振り（全体フレーム）として生成する（フレーム表記を解析できない場合は None）。
+    pub fn whiff(info: &MoveInfo) -> Option<Self> {
+        let timeline = Timeline::from_move(info).ok()?;
+        (!timeline.startup.is_empty()).then(|| Self {
+            label: format!("{} whiff", info.input),
+            frames: timeline.total_frames(),
+            estimated: false,
+        })
+    }
+}
+
+/// 重なる技の情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meaty {
+    /// 技の入力
+    pub input: String,
+    /// 発生
+    pub startup: u32,
+    /// 起き上がり時点で経過済みの持続フレーム数
+    pub meaty_by: u32,
+    /// 起き上がり時点から残る持続フレーム数（起き上がりフレームを含む）
+    pub active_left: u32,
+}
+
+/// 重なる技の一覧を求める関数
+///
+/// # 概要
+/// 行動後の残りダウン時間 `remaining` の次フレームを相手の起き上がりとし、
+/// 行動直後に出した場合に起き上がりフレームが持続と重なる技を返す。
+///
+/// # 引数
+/// * `remaining` - 行動後の残りダウン時間
+/// * `moves` - キャラクターの技一覧
+///
+/// # 戻り値
+/// 重なる技の一覧（発生の昇順）
+pub fn meaty_moves(remaining: i32, moves: &[MoveInfo]) -> Vec<Meaty> {
+    let Ok(wakeup) = usize::try_from(remaining + 1) else {
+        return Vec::new();
+    };
+    if wakeup == 0 {
+        return Vec::new();
+    }
+    let mut meaties: Vec<Meaty> = moves
+        .iter()
+        .filter_map(|info| {
+            let timeline = Timeline::from_move(info).ok()?;
+            if timeline.startup.is_empty() || timeline.active.is_empty() {
+                return None;
+            }
+            let kinds = timeline.frame_kinds();
+            if kinds.get(wakeup - 1) != Some(&FrameKind::Active) {
+                return None;
+            }
+            let startup = timeline.startup_frames() + 1;
+            let active_left = kinds[wakeup - 1..]
+                .iter()
+                .take_while(|kind| **kind != FrameKind::Recovery)
+                .filter(|kind| **kind == FrameKind::Active)
+                .count() as u32;
+            Some(Meaty {
+                input: info.input.clone(),
+                startup,
+                meaty_by: wakeup as u32 - startup,
+                active_left,
+            })
+        })
+        .collect();
+    meaties.sort_by_key(|meaty| meaty.startup);
+    meaties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MoveInfoBuilder;
+
+    #[test]
+    fn test_parse_knockdown() {
+        assert_eq!(
+            parse_knockdown("強制ダウン+49"),
+            Some(Knockdown {
+                hard: true,
+                advantage: Some(49)
+            })
+        );
+        assert_eq!(
+            parse_knockdown("ダウン+36"),
+            Some(Knockdown {
+                hard: false,
+                advantage: Some(36)
+            })
+        );
+        assert_eq!(
+            parse_knockdown("KD"),
+            Some(Knockdown {
+                hard: false,
+                advantage: None
+            })
+        );
+        assert_eq!(parse_knockdown("+2"), None);
+    }
+
+    #[test]
+    fn test_oki_action_parse() {
+        let movement = Movement::default();
+        let dash = OkiAction::parse("Dash", &movement).unwrap();
+        assert_eq!((dash.frames, dash.estimated), (10, true));
+        assert_eq!(OkiAction::parse("wait 7", &movement).unwrap().frames, 7);
+        let wait = OkiAction::parse("12", &movement).unwrap();
+        assert_eq!((wait.frames, wait.estimated), (12, false));
+        assert!(OkiAction::parse("5K", &movement).is_none());
+
+        let info = crate::test_utils::_create_test_char_info();
+        let movement = Movement::from_char_info(&info);
+        assert_eq!(movement.jump, Some(4 + 45));
+        let jump = OkiAction::parse("jump", &movement).unwrap();
+        assert_eq!((jump.frames, jump.estimated), (4 + 45, false));
+        let backdash = OkiAction::parse("backdash", &movement).unwrap();
+        assert_eq!((backdash.frames, backdash.estimated), (20, false));
+        // ダッシュの所要フレーム数はキャラクター情報に無いため常に汎用値
+        let dash = OkiAction::parse("dash", &movement).unwrap();
+        assert_eq!((dash.frames, dash.estimated), (10, true));
+
+        // 発生 5・持続 3・硬直 10 → 全体 17
+        let whiff =
+            OkiAction::whiff(&MoveInfoBuilder::new("5K").frames(5, "3", 10).build()).unwrap();
+        assert_eq!(whiff.frames, 17);
+    }
+
+    #[test]
+    fn test_meaty_moves() {
+        let moves = vec![
+            MoveInfoBuilder::new("5K").frames(5, "3", 10).build(),
+            MoveInfoBuilder::new("c.S").frames(7, "6", 10).build(),
+            MoveInfoBuilder::new("2D").frames(10, "3", 18).build(),
+        ];
+        // 起き上がりは 7 フレーム目
+        let meaties = meaty_moves(6, &moves);
+        let inputs: Vec<&str> = meaties.iter().map(|m| m.input.as_str()).collect();
+        assert_eq!(inputs, vec!["5K", "c.S"]);
+        assert_eq!((meaties[0].meaty_by, meaties[0].active_left), (2, 1));
+        assert_eq!((meaties[1].meaty_by, meaties[1].active_left), (0, 6));
+        assert!(meaty_moves(-1, &moves).is_empty());
+    }
+}
@@ -3,146 +3,267 @@
 //! 各種チェック機能提供モジュールである。  
 //! ディレクトリ、ファイル、JSON の存在および正当性を確認する関数群を含む。
 
+use crate::commands::update; // 自動修復用データ再取得処理
 use crate::error::{AppError, Result};
-use crate::find::Nicknames;
-use crate::{Context, CHARS}; // CHARS 定数：キャラクター名定数（クレートルートに定義されている前提）
+use crate::find::{self, Nicknames};
+use crate::models::{MoveAliases, MoveInfo};
+use crate::timeline;
+use crate::{Context, ImageLinks, CHARS}; // CHARS 定数：キャラクター名定数（クレートルートに定義されている前提）
 use bitflags::bitflags;
 use colored::Colorize; // ターミナル出力の色付けに利用するクレートである
-use std::{fs, path::Path}; // ファイル操作およびパス操作用
-
-/// データフォルダ "data" 存在チェック関数である。  
-/// 引数：`init_check` - 初期チェックか否かの真偽値  
-/// 戻り値：チェック成功時 None / エラー発生時エラーメッセージ (Some(String)) を返す。
-pub async fn data_folder_exists(init_check: bool) -> Option<String> {
-    // "data" フォルダ存在確認
-    if Path::new("data").exists() {
-        None
-    } else {
-        // "data" フォルダ未存在エラー用メッセージ
-        let error_msg = "Error: The 'data' folder does not exist.\nDownload and import the 'data' folder from:\nhttps://github.com/yakiimoninja/baiken.".to_string();
+use std::{collections::HashMap, fs, path::Path}; // ファイル操作およびパス操作用
+
+/// データ取得元案内文
+const DATA_SOURCE_HINT: &str =
+    "Download and import the `data` folder from:\nhttps://github.com/yakiimoninja/baiken.";
+
+/// 診断結果の重大度である。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// データ欠損・解析不能（コマンド実行不可）
+    Error,
+    /// 参照不整合・値域外（コマンド実行は可能）
+    Warning,
+}
 
-        if init_check {
-            // 初期チェック時、エラーメッセージをコンソール出力しパニック
-            println!();
-            panic!("{}", error_msg.red());
-        } else {
-            // Discord などで出力するためエラーメッセージを返す
-            Some(error_msg)
+/// 単一チェック項目の診断結果である。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 診断を生成したチェック種別
+    pub check: CheckOptions,
+    /// 重大度
+    pub severity: Severity,
+    /// 対象キャラクター（キャラクター単位の診断時のみ）
+    pub character: Option<String>,
+    /// 欠損・不正内容
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// エラー診断生成関数である。
+    fn error(check: CheckOptions, character: Option<&str>, message: String) -> Self {
+        Self {
+            check,
+            severity: Severity::Error,
+            character: character.map(str::to_string),
+            message,
         }
     }
+
+    /// 警告診断生成関数である。
+    fn warning(check: CheckOptions, character: Option<&str>, message: String) -> Self {
+        Self {
+            check,
+            severity: Severity::Warning,
+            character: character.map(str::to_string),
+            message,
+        }
+    }
+
+    /// 自動修復（Dustloop からの再取得）対象か否かを返す。
+    pub fn is_repairable(&self) -> bool {
+        self.character.is_some()
+            && self.check.intersects(
+                CheckOptions::CHARACTER_FOLDERS
+                    | CheckOptions::CHARACTER_JSONS
+                    | CheckOptions::CHARACTER_IMAGES,
+            )
+    }
 }
 
-/// nicknames.json 存在および正当性チェック関数である。  
-/// 引数：`init_check` - 初期チェックか否かの真偽値  
-/// 戻り値：正常時 None / エラー発生時エラーメッセージ (Some(String)) を返す。
-pub async fn nicknames_json_exists(init_check: bool) -> Option<String> {
-    // nicknames.json ファイル読み込み
-    let Ok(data_from_file) = fs::read_to_string("data/nicknames.json") else {
-        let error_msg = "Error: Failed to read 'nicknames.json' file.\nDownload and import the `data` folder from:\nhttps://github.com/yakiimoninja/baiken.".to_string();
-
-        if init_check {
-            println!();
-            panic!("{}", error_msg.red());
+/// チェック結果集約構造体である。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckReport {
+    /// 検出された診断結果群
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CheckReport {
+    /// 欠損・不正が一件も無いか否かを返す。
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// 指定重大度の診断件数を返す。
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+
+    /// キャラクター別（キャラクター非依存分は "global"）のエラー・警告件数を返す。
+    pub fn counts_by_character(&self) -> Vec<(String, usize, usize)> {
+        let mut counts: Vec<(String, usize, usize)> = Vec::new();
+        for diagnostic in &self.diagnostics {
+            let label = diagnostic.character.as_deref().unwrap_or("global");
+            let index = match counts.iter().position(|(name, _, _)| name == label) {
+                Some(index) => index,
+                None => {
+                    counts.push((label.to_string(), 0, 0));
+                    counts.len() - 1
+                }
+            };
+            match diagnostic.severity {
+                Severity::Error => counts[index].1 += 1,
+                Severity::Warning => counts[index].2 += 1,
+            }
+        }
+        counts
+    }
+
+    /// 全診断の詳細一覧（1行1件）を生成する。
+    pub fn to_details(&self) -> String {
+        self.diagnostics
+            .iter()
+            .map(|d| {
+                let severity = match d.severity {
+                    Severity::Error => "ERROR",
+                    Severity::Warning => "WARN ",
+                };
+                format!(
+                    "[{severity}] [{}] {}\n",
+                    d.character.as_deref().unwrap_or("global"),
+                    d.message
+                )
+            })
+            .collect()
+    }
+
+    /// 全診断が自動修復対象か否かを返す。
+    pub fn is_repairable(&self) -> bool {
+        !self.is_ok() && self.diagnostics.iter().all(Diagnostic::is_repairable)
+    }
+
+    /// 自動修復対象キャラクター一覧（重複除去済み）を返す。
+    pub fn repairable_characters(&self) -> Vec<String> {
+        let mut characters: Vec<String> = Vec::new();
+        for diagnostic in self.diagnostics.iter().filter(|d| d.is_repairable()) {
+            if let Some(character) = &diagnostic.character {
+                if !characters.contains(character) {
+                    characters.push(character.clone());
+                }
+            }
+        }
+        characters
+    }
+
+    /// Discord 返信用の要約文を生成する。
+    /// メッセージ長上限を考慮し、診断件数が多い場合は末尾を省略する。
+    pub fn to_message(&self) -> String {
+        let mut message = format!("Error: {} data problem(s) found.\n", self.diagnostics.len());
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            let line = format!("- {}\n", diagnostic.message.replace('\'', "`"));
+            if message.len() + line.len() > 1500 {
+                message += &format!("- ...and {} more.\n", self.diagnostics.len() - i);
+                break;
+            }
+            message += &line;
+        }
+        if self.diagnostics.iter().any(Diagnostic::is_repairable) {
+            message += "Please execute the `/update` command.";
         } else {
-            return Some(error_msg);
+            message += DATA_SOURCE_HINT;
         }
-    };
+        message
+    }
+}
 
-    // JSON デシリアライズ試行
-    if serde_json::from_str::<Vec<Nicknames>>(&data_from_file).is_ok() {
-        println!("{}", "Successfully read 'nicknames.json' file.".green());
+/// データフォルダ存在チェック関数である。  
+/// 引数：`data_dir` - データディレクトリのパス  
+/// 戻り値：チェック成功時 None / 未存在時診断結果 (Some(Diagnostic)) を返す。
+pub fn data_folder_exists(data_dir: &str) -> Option<Diagnostic> {
+    if Path::new(data_dir).is_dir() {
         None
     } else {
-        // nicknames.json 正当性エラー用メッセージ
-        let error_msg = "Error: Failed to deserialize 'nicknames.json' file.\nDownload and import the `data` folder from:\nhttps://github.com/yakiimoninja/baiken.".to_string();
+        Some(Diagnostic::error(
+            CheckOptions::DATA_FOLDER,
+            None,
+            format!("The '{data_dir}' folder does not exist."),
+        ))
+    }
+}
 
-        if init_check {
-            println!();
-            panic!("{}", error_msg.red());
-        } else {
-            Some(error_msg)
+/// nicknames.json 存在および正当性チェック関数である。  
+/// 引数：`data_dir` - データディレクトリのパス  
+/// 戻り値：正常時 None / エラー発生時診断結果 (Some(Diagnostic)) を返す。
+pub fn nicknames_json_exists(data_dir: &str) -> Option<Diagnostic> {
+    let nicknames_path = Path::new(data_dir).join("nicknames.json");
+    let message = match fs::read_to_string(&nicknames_path) {
+        Err(_) => "Failed to read 'nicknames.json' file.",
+        // JSON デシリアライズ試行
+        Ok(data) if serde_json::from_str::<Vec<Nicknames>>(&data).is_err() => {
+            "Failed to deserialize 'nicknames.json' file."
         }
-    }
+        Ok(_) => return None,
+    };
+    Some(Diagnostic::error(
+        CheckOptions::NICKNAMES_JSON,
+        None,
+        message.to_string(),
+    ))
 }
 
-/// キャラクターフォルダ存在チェック関数である。  
-/// CHARS 定数に基づき、各キャラクター用フォルダが "data" 内に存在するか確認する。  
-/// 引数：`init_check` - 初期チェックか否かの真偽値  
-/// 戻り値：正常時 None / エラー発生時エラーメッセージ (Some(String)) を返す。
-pub async fn character_folders_exist(init_check: bool) -> Option<String> {
-    // CHARS 内の各キャラクターについてフォルダ存在確認
-    for char in CHARS {
-        let character_path = &("data/".to_owned() + char);
-        if !Path::new(&character_path).exists() {
-            // キャラクターフォルダ未存在エラー用メッセージ
-            let error_msg = "Error: Missing '".to_owned() + &character_path + "' folder.\nDownload and import the `data` folder from:\nhttps://github.com/yakiimoninja/baiken.";
-            if init_check {
-                println!();
-                panic!("{}", error_msg.red());
+/// キャラクター単位ファイル存在チェック共通処理である。  
+/// CHARS 定数の各キャラクターについて `relative(char)` が存在するか確認し、欠損分の診断結果を返す。
+fn missing_character_paths(
+    data_dir: &str,
+    check: CheckOptions,
+    kind: &str,
+    relative: impl Fn(&str) -> String,
+) -> Vec<Diagnostic> {
+    CHARS
+        .iter()
+        .filter_map(|char| {
+            let path = relative(char);
+            if Path::new(data_dir).join(&path).exists() {
+                None
             } else {
-                return Some(error_msg);
+                Some(Diagnostic::error(
+                    check,
+                    Some(char),
+                    format!("Missing '{data_dir}/{path}' {kind}."),
+                ))
             }
-        }
-    }
-    None
+        })
+        .collect()
+}
+
+/// キャラクターフォルダ存在チェック関数である。  
+/// CHARS 定数に基づき、各キャラクター用フォルダがデータディレクトリ内に存在するか確認する。  
+/// 戻り値：欠損フォルダごとの診断結果を返す。
+pub fn character_folders_exist(data_dir: &str) -> Vec<Diagnostic> {
+    missing_character_paths(
+        data_dir,
+        CheckOptions::CHARACTER_FOLDERS,
+        "folder",
+        |char| char.to_string(),
+    )
 }
 
 /// キャラクター JSON 存在チェック関数である。  
 /// 各キャラクター用フォルダ内に、キャラクター JSON ファイルが存在するか確認する。  
-/// 引数：`init_check` - 初期チェックか否かの真偽値  
-/// 戻り値：正常時 None / エラー発生時エラーメッセージ (Some(String)) を返す。
-pub async fn character_jsons_exist(init_check: bool) -> Option<String> {
-    // CHARS 内各キャラクターについて JSON ファイル存在確認
-    for char in CHARS {
-        let character_json = &("data/".to_owned() + char + "/" + char + ".json");
-        if !Path::new(&character_json).exists() {
-            // キャラクター JSON 未存在エラー用メッセージ
-            let error_msg = "Error: Missing '".to_owned()
-                + &character_json
-                + "' file.\nPlease execute the '/update' command.";
-            if init_check {
-                println!();
-                panic!("{}", error_msg.red());
-            } else {
-                return Some(error_msg);
-            }
-        }
-    }
-    println!(
-        "{}",
-        ("Successfully read ".to_owned() + &CHARS.len().to_string() + " character.json files.")
-            .green()
-    );
-    None
+/// 戻り値：欠損ファイルごとの診断結果を返す。
+pub fn character_jsons_exist(data_dir: &str) -> Vec<Diagnostic> {
+    missing_character_paths(data_dir, CheckOptions::CHARACTER_JSONS, "file", |char| {
+        format!("{char}/{char}.json")
+    })
 }
 
 /// キャラクター画像 JSON 存在チェック関数である。  
 /// 各キャラクター用フォルダ内に、画像 JSON ファイルが存在するか確認する。  
-/// 引数：`init_check` - 初期チェックか否かの真偽値  
-/// 戻り値：正常時 None / エラー発生時エラーメッセージ (Some(String)) を返す。
-pub async fn character_images_exist(init_check: bool) -> Option<String> {
-    // CHARS 内各キャラクターについて画像 JSON ファイル存在確認
-    for char in CHARS {
-        let images_json = &("data/".to_owned() + char + "/images.json");
-        if !Path::new(&images_json).exists() {
-            // 画像 JSON 未存在エラー用メッセージ
-            let error_msg = "Error: Missing '".to_owned() + &images_json + "' file.\nDownload and import the `data` folder from:\nhttps://github.com/yakiimoninja/baiken.";
-            if init_check {
-                println!();
-                panic!("{}", error_msg.red());
-            } else {
-                return Some(error_msg);
-            }
-        }
-    }
-    None
+/// 戻り値：欠損ファイルごとの診断結果を返す。
+pub fn character_images_exist(data_dir: &str) -> Vec<Diagnostic> {
+    missing_character_paths(data_dir, CheckOptions::CHARACTER_IMAGES, "file", |char| {
+        format!("{char}/images.json")
+    })
 }
 
 bitflags! {
     /// チェックオプションフラグ
     ///
     /// 各種チェック機能の有効・無効を指定するためのビットフラグ
-    #[derive(Default)]
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
     pub struct CheckOptions: u8 {
         /// データフォルダの存在チェック
         const DATA_FOLDER = 0b00001;
@@ -154,75 +275,107 @@ bitflags! {
         const CHARACTER_JSONS = 0b01000;
         /// キャラクター画像の存在チェック
         const CHARACTER_IMAGES = 0b10000;
+        /// データ内容の整合性チェック（全JSONを解析するため低速）
+        const DATA_INTEGRITY = 0b100000;
         /// すべてのチェックを実行
         const ALL = Self::DATA_FOLDER.bits() | Self::NICKNAMES_JSON.bits() |
                    Self::CHARACTER_FOLDERS.bits() | Self::CHARACTER_JSONS.bits() |
-                   Self::CHARACTER_IMAGES.bits();
+                   Self::CHARACTER_IMAGES.bits() | Self::DATA_INTEGRITY.bits();
     }
 }
 
-/// アダプティブチェック関数
+/// チェック一括実行関数
 ///
-/// 指定されたオプションに基づいて、必要なチェックのみを実行する
+/// 指定されたオプションのチェックを全て実行し、診断結果を集約して返す。
+/// データフォルダ自体が存在しない場合、以降のチェックは省略する。
 ///
 /// # 引数
-/// * `ctx` - Discordのコンテキスト
+/// * `data_dir` - データディレクトリのパス
 /// * `options` - 実行するチェックを指定するオプションフラグ
-///
-/// 戻り値：全チェック成功時 Ok(()) / 失敗時 Err("Failed `adaptive_check`")
-pub async fn adaptive_check(ctx: Context<'_>, options: CheckOptions) -> Result<()> {
+pub fn run_checks(data_dir: &str, options: CheckOptions) -> CheckReport {
+    let mut report = CheckReport::default();
+
     if options.contains(CheckOptions::DATA_FOLDER) {
-        // Checking if data folder exists
-        if let Some(error_msg) = data_folder_exists(false).await {
-            if let Err(e) = ctx.say(&error_msg.replace('\'', "`")).await {
-                println!("Failed to send message: {e}");
-            }
-            println!();
-            panic!("{}", error_msg.replace('\n', " ").red());
+        if let Some(diagnostic) = data_folder_exists(data_dir) {
+            report.diagnostics.push(diagnostic);
+            return report;
         }
     }
     if options.contains(CheckOptions::NICKNAMES_JSON) {
-        // Checking if nicknames.json exists
-        if let Some(error_msg) = nicknames_json_exists(false).await {
-            if let Err(e) = ctx.say(&error_msg.replace('\'', "`")).await {
-                println!("Failed to send message: {e}");
-            }
-            println!();
-            panic!("{}", error_msg.replace('\n', " ").red());
-        }
+        report.diagnostics.extend(nicknames_json_exists(data_dir));
     }
     if options.contains(CheckOptions::CHARACTER_FOLDERS) {
-        // Checking if character folders exist
-        if let Some(error_msg) = character_folders_exist(false).await {
-            if let Err(e) = ctx.say(&error_msg.replace('\'', "`")).await {
-                println!("Failed to send message: {e}");
-            }
-            println!();
-            panic!("{}", error_msg.replace('\n', " ").red());
-        }
+        report.diagnostics.extend(character_folders_exist(data_dir));
     }
     if options.contains(CheckOptions::CHARACTER_JSONS) {
-        // Checking if character jsons exist
-        if let Some(error_msg) = character_jsons_exist(false).await {
-            if let Err(e) = ctx.say(&error_msg.replace('\'', "`")).await {
-                println!("Failed to send message: {e}");
-            }
-            println!();
-            panic!("{}", error_msg.replace('\n', " ").red());
-        }
+        report.diagnostics.extend(character_jsons_exist(data_dir));
     }
     if options.contains(CheckOptions::CHARACTER_IMAGES) {
-        // Checking if character images exist
-        if let Some(error_msg) = character_images_exist(false).await {
-            if let Err(e) = ctx.say(&error_msg.replace('\'', "`")).await {
-                println!("Failed to send message: {e}");
-            }
-            println!();
-            panic!("{}", error_msg.replace('\n', " ").red());
+        report.diagnostics.extend(character_images_exist(data_dir));
+    }
+    if options.contains(CheckOptions::DATA_INTEGRITY) {
+        report.diagnostics.extend(data_integrity_issues(data_dir));
+    }
+
+    report
+}
+
+/// アダプティブチェック関数
+///
+/// 指定されたオプションに基づいて、必要なチェックのみを実行する。
+/// 欠損検出時は内容をユーザーへ返信し、パニックせずエラーを返す。
+/// 自動修復有効時は、修復可能な欠損（キャラクターデータ）を再取得した上で再チェックする。
+///
+/// # 引数
+/// * `ctx` - Discordのコンテキスト
+/// * `options` - 実行するチェックを指定するオプションフラグ
+///
+/// 戻り値：全チェック成功時 Ok(()) / 失敗時 Err(AppError::DataProcessing)
+pub async fn adaptive_check(ctx: Context<'_>, options: CheckOptions) -> Result<()> {
+    let data_dir = ctx.data().data_dir.clone();
+    let mut report = run_checks(&data_dir, options);
+    if report.is_ok() {
+        return Ok(());
+    }
+
+    if ctx.data().auto_repair && report.is_repairable() {
+        let characters = report.repairable_characters();
+        println!(
+            "{}",
+            format!("Attempting automatic repair for: {}", characters.join(", ")).yellow()
+        );
+        if let Err(e) = ctx
+            .say(format!(
+                "Missing data detected for {}. Attempting automatic repair...",
+                characters.join(", ")
+            ))
+            .await
+        {
+            println!("Failed to send message: {e}");
+        }
+
+        if let Err(e) = update::repair_characters(&data_dir, &characters).await {
+            println!("{}", format!("Automatic repair failed: {e}").red());
+        }
+
+        report = run_checks(&data_dir, options);
+        if report.is_ok() {
+            println!("{}", "Automatic repair succeeded.".green());
+            return Ok(());
         }
     }
 
-    Ok(())
+    let message = report.to_message();
+    println!();
+    println!("{}", message.replace('\n', " ").red());
+    if let Err(e) = ctx.say(&message).await {
+        println!("Failed to send message: {e}");
+    }
+
+    Err(AppError::DataProcessing(format!(
+        "{} data problem(s) found",
+        report.diagnostics.len()
+    )))
 }
 
 /// データディレクトリの検証を行う
@@ -295,7 +448,7 @@ pub fn check_character_folders(data_dir: &str) -> Result<()> {
     }
 
     // CHARS配列からキャラクターフォルダを確認
-    for char_name in &crate::CHARS {
+    for char_name in &CHARS {
         let char_dir = data_path.join(char_name);
         if !char_dir.exists() || !char_dir.is_dir() {
             println!(
@@ -315,94 +468,238 @@ pub fn check_character_folders(data_dir: &str) -> Result<()> {
     Ok(())
 }
 
-/// データ整合性チェック
-///
-/// 全キャラクターデータの整合性を検証する
-///
-/// # 引数
-/// * `data_dir` - データディレクトリへのパス
-#[allow(dead_code)]
-pub fn check_data_integrity(data_dir: &str) {
-    println!("{}", "データ整合性の検証を開始します...".cyan());
+/// 技データ数値項目の許容範囲（項目名, 下限, 上限）
+const NUMERIC_RANGES: [(&str, f64, f64); 8] = [
+    ("damage", 0.0, 1000.0),
+    ("startup", 0.0, 999.0),
+    ("recovery", 0.0, 999.0),
+    ("risc_gain", 0.0, 10000.0),
+    ("risc_loss", 0.0, 10000.0),
+    ("wall_damage", 0.0, 10000.0),
+    ("input_tension", 0.0, 1000.0),
+    ("chip_ratio", 0.0, 100.0),
+];
+
+/// 技データの数値項目を（項目名, 値）の組で列挙する。
+fn numeric_fields(move_info: &MoveInfo) -> [(&'static str, Option<f64>); 8] {
+    [
+        ("damage", move_info.damage.map(f64::from)),
+        ("startup", move_info.startup.map(f64::from)),
+        ("recovery", move_info.recovery.map(f64::from)),
+        ("risc_gain", move_info.risc_gain),
+        ("risc_loss", move_info.risc_loss),
+        ("wall_damage", move_info.wall_damage.map(f64::from)),
+        ("input_tension", move_info.input_tension),
+        ("chip_ratio", move_info.chip_ratio),
+    ]
+}
 
-    let data_path = Path::new(data_dir);
+/// 画像URL形式チェック関数である。  
+/// http(s) スキームかつ空白を含まず、画像拡張子付きのファイル名で終わるか判定する。
+pub fn is_valid_image_url(url: &str) -> bool {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    if url.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let Some((host, path)) = rest.split_once('/') else {
+        return false;
+    };
+    let file_name = path.rsplit('/').next().unwrap_or("");
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    !host.is_empty() && ["png", "jpg", "jpeg", "gif", "webp"].contains(&extension.as_str())
+}
 
-    // 各キャラクターのJSONファイルをサンプリングチェック
-    for char_name in &["Sol_Badguy", "Ky_Kiske", "May"] {
-        let char_dir = data_path.join(char_name);
-        if !char_dir.exists() || !char_dir.is_dir() {
-            continue; // このキャラクターはスキップ
+/// JSON ファイル読み込み・解析共通処理である。  
+/// ファイル未存在時は None（存在チェック側で報告済み）、解析失敗時は診断結果を追加し None を返す。
+fn parse_json_file<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    character: Option<&str>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<T> {
+    let content = fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<T>(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            diagnostics.push(Diagnostic::error(
+                CheckOptions::DATA_INTEGRITY,
+                character,
+                format!("Failed to parse '{}': {e}", path.display()),
+            ));
+            None
         }
+    }
+}
 
-        // character.json の整合性チェック
-        let char_data_path = char_dir.join("character.json");
-        if char_data_path.exists() && char_data_path.is_file() {
-            match fs::read_to_string(&char_data_path) {
-                Ok(content) => {
-                    if let Err(e) = serde_json::from_str::<crate::models::CharInfo>(&content) {
-                        println!(
-                            "{}",
+/// キャラクター単位の整合性チェック関数である。  
+/// 技・画像・エイリアスの各 JSON を解析し、相互参照と値域を検証する。
+fn character_integrity_issues(data_dir: &str, character: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let char_dir = Path::new(data_dir).join(character);
+    let check = CheckOptions::DATA_INTEGRITY;
+    let who = Some(character);
+
+    let moves: Option<Vec<MoveInfo>> = parse_json_file(
+        &char_dir.join(format!("{character}.json")),
+        who,
+        &mut diagnostics,
+    );
+    let images: Option<Vec<ImageLinks>> =
+        parse_json_file(&char_dir.join("images.json"), who, &mut diagnostics);
+    let aliases: Option<Vec<MoveAliases>> =
+        parse_json_file(&char_dir.join("aliases.json"), who, &mut diagnostics);
+
+    if let Some(moves) = &moves {
+        for move_info in moves {
+            // 値域チェック
+            for ((field, value), (_, min, max)) in
+                numeric_fields(move_info).iter().zip(NUMERIC_RANGES.iter())
+            {
+                if let Some(value) = value {
+                    if !(*min..=*max).contains(value) {
+                        diagnostics.push(Diagnostic::warning(
+                            check,
+                            who,
                             format!(
-                                "警告: キャラクターデータの解析に失敗しました: {} - {e}",
-                                char_data_path.display()
-                            )
-                            .yellow()
-                        );
+                                "Move '{}' has out-of-range {field}: {value} (expected {min}..={max}).",
+                                move_info.input
+                            ),
+                        ));
                     }
                 }
-                Err(e) => {
-                    println!(
-                        "{}",
-                        format!(
-                            "警告: キャラクターデータの読み込みに失敗しました: {} - {e}",
-                            char_data_path.display()
-                        )
-                        .yellow()
-                    );
+            }
+
+            // フレーム表記チェック
+            if let Err(err) = timeline::Timeline::from_move(move_info) {
+                diagnostics.push(Diagnostic::warning(
+                    check,
+                    who,
+                    format!("Move '{}': {err}", move_info.input),
+                ));
+            }
+
+            // 画像エントリ存在チェック
+            if let Some(images) = &images {
+                if find::find_image_index(&move_info.input, images).is_none() {
+                    diagnostics.push(Diagnostic::warning(
+                        check,
+                        who,
+                        format!("Move '{}' has no image entry.", move_info.input),
+                    ));
                 }
             }
         }
 
-        // moves.json の整合性チェック
-        let moves_data_path = char_dir.join("moves.json");
-        if moves_data_path.exists() && moves_data_path.is_file() {
-            match fs::read_to_string(&moves_data_path) {
-                Ok(content) => {
-                    if let Err(e) = serde_json::from_str::<Vec<crate::models::MoveInfo>>(&content) {
-                        println!(
-                            "{}",
-                            format!(
-                                "警告: 技データの解析に失敗しました: {} - {e}",
-                                moves_data_path.display()
-                            )
-                            .yellow()
-                        );
-                    }
-                }
-                Err(e) => {
-                    println!(
-                        "{}",
+        // エイリアス参照先チェック
+        for alias in aliases.iter().flatten() {
+            let exists = moves.iter().any(|m| {
+                find::move_input_matches(&m.input, &alias.input)
+                    || m.name.eq_ignore_ascii_case(alias.input.trim())
+            });
+            if !exists {
+                diagnostics.push(Diagnostic::warning(
+                    check,
+                    who,
+                    format!(
+                        "Alias entry '{}' does not match any move input.",
+                        alias.input
+                    ),
+                ));
+            }
+        }
+    }
+
+    // 画像URL形式チェック
+    for image in images.iter().flatten() {
+        let urls = std::iter::once(&image.move_img).chain(image.hitbox_img.iter());
+        for url in urls.filter(|url| !url.is_empty()) {
+            if !is_valid_image_url(url) {
+                diagnostics.push(Diagnostic::warning(
+                    check,
+                    who,
+                    format!("Image URL for '{}' is malformed: {url}", image.input),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// ニックネーム整合性チェック関数である。  
+/// 未知のキャラクター名、および複数キャラクター間で重複するニックネームを検出する。
+fn nickname_integrity_issues(data_dir: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let check = CheckOptions::DATA_INTEGRITY;
+    let Some(nicknames) = parse_json_file::<Vec<Nicknames>>(
+        &Path::new(data_dir).join("nicknames.json"),
+        None,
+        &mut diagnostics,
+    ) else {
+        return diagnostics;
+    };
+
+    // ニックネーム（小文字化）→ 最初に登録されたキャラクター
+    let mut owners: HashMap<String, &str> = HashMap::new();
+    for entry in &nicknames {
+        if !CHARS.contains(&entry.character.as_str()) {
+            diagnostics.push(Diagnostic::error(
+                check,
+                None,
+                format!(
+                    "'nicknames.json' references unknown character '{}'.",
+                    entry.character
+                ),
+            ));
+        }
+        for nickname in entry.nicknames.iter().filter(|n| !n.trim().is_empty()) {
+            let key = nickname.trim().to_lowercase();
+            match owners.get(&key) {
+                Some(owner) if *owner != entry.character => {
+                    diagnostics.push(Diagnostic::warning(
+                        check,
+                        None,
                         format!(
-                            "警告: 技データの読み込みに失敗しました: {} - {e}",
-                            moves_data_path.display()
-                        )
-                        .yellow()
-                    );
+                            "Nickname '{nickname}' is shared by '{owner}' and '{}'.",
+                            entry.character
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    owners.insert(key, &entry.character);
                 }
             }
         }
     }
 
-    println!("{}", "データ整合性の検証が完了しました".green());
+    diagnostics
+}
+
+/// データ整合性チェック関数である。  
+/// 全キャラクターの技・画像・エイリアス JSON およびニックネームを解析し、
+/// 参照不整合・値域外・URL 形式不正を診断結果として返す。
+pub fn data_integrity_issues(data_dir: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = nickname_integrity_issues(data_dir);
+    for character in CHARS {
+        diagnostics.extend(character_integrity_issues(data_dir, character));
+    }
+    diagnostics
 }
 
 #[cfg(test)]
 mod tests {
     use super::*; // 親モジュールの全ての要素をインポート
     use crate::test_utils::{create_test_dir_structure, create_test_json_file};
-    use std::path::PathBuf;
 
-    fn setup_test_data(temp_dir: &PathBuf) -> Result<()> {
+    fn setup_test_data(temp_dir: &Path) -> Result<()> {
         // Sol_Badguy のデータを作成
         let sol_dir = temp_dir.join("Sol_Badguy");
         let sol_char_json = r#"{"defense":0.9,"guts":2.0,"guard_balance":1.5,"prejump":4,"umo":"","forward_dash":7.5,"backdash":6.0,"backdash_duration":20,"backdash_invincibility":7,"backdash_airborne":true,"backdash_distance":4.2,"jump_duration":45,"jump_height":3.5,"high_jump_duration":55,"high_jump_height":4.7,"earliest_iad":"","ad_duration":"","ad_distance":"","abd_duration":"","abd_distance":"","movement_tension":0.1,"jump_tension":0.2,"airdash_tension":0.15,"walk_speed":2.2,"back_walk_speed":1.8,"dash_initial_speed":5.5,"dash_acceleration":0.3,"dash_friction":0.05,"jump_gravity":0.25,"high_jump_gravity":0.2}"#;
@@ -474,4 +771,126 @@ mod tests {
         // 検証
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_run_checks_missing_data_folder() {
+        let report = run_checks("/tmp/non_existent_dir_123456789", CheckOptions::ALL);
+
+        // データフォルダ欠損のみが報告され、以降のチェックは省略される
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].check, CheckOptions::DATA_FOLDER);
+        assert!(!report.is_repairable());
+    }
+
+    #[test]
+    fn test_run_checks_collects_missing_character_files() {
+        let (_temp_dir, temp_path) = create_test_dir_structure();
+        create_test_json_file(
+            temp_path.join("nicknames.json"),
+            r#"[{"character":"Sol_Badguy","nicknames":["sol"]}]"#,
+        )
+        .unwrap();
+        for char in CHARS {
+            fs::create_dir_all(temp_path.join(char)).unwrap();
+            if char != "May" {
+                create_test_json_file(temp_path.join(char).join(format!("{char}.json")), "[]")
+                    .unwrap();
+            }
+        }
+
+        let report = run_checks(temp_path.to_str().unwrap(), CheckOptions::ALL);
+
+        // 全キャラクターの images.json 欠損と May の JSON 欠損が全て報告される
+        assert_eq!(report.diagnostics.len(), CHARS.len() + 1);
+        assert!(report.is_repairable());
+        assert_eq!(report.repairable_characters().len(), CHARS.len());
+        assert!(report
+            .diagnostics
+            .iter()
+            .any(|d| d.check == CheckOptions::CHARACTER_JSONS
+                && d.character.as_deref() == Some("May")));
+
+        // 返信文は件数を含み、長さ上限内に収まる
+        let message = report.to_message();
+        assert!(message.contains(&format!("{} data problem(s)", CHARS.len() + 1)));
+        assert!(message.len() < 2000);
+    }
+
+    #[test]
+    fn test_run_checks_invalid_nicknames_json() {
+        let (_temp_dir, temp_path) = create_test_dir_structure();
+        create_test_json_file(temp_path.join("nicknames.json"), "{invalid").unwrap();
+
+        let report = run_checks(
+            temp_path.to_str().unwrap(),
+            CheckOptions::DATA_FOLDER | CheckOptions::NICKNAMES_JSON,
+        );
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert!(report.diagnostics[0].message.contains("deserialize"));
+        assert!(!report.is_repairable());
+    }
+
+    #[test]
+    fn test_is_valid_image_url() {
+        assert!(is_valid_image_url(
+            "https://www.dustloop.com/wiki/images/0/0a/GGST_Sol_Badguy_2D.png"
+        ));
+        assert!(!is_valid_image_url(
+            "https://www.dustloop.com/wiki/images/d/d4/"
+        ));
+        assert!(!is_valid_image_url("ftp://example.com/a.png"));
+        assert!(!is_valid_image_url("https://example.com/a b.png"));
+    }
+
+    #[test]
+    fn test_data_integrity_issues() {
+        let (_temp_dir, temp_path) = create_test_dir_structure();
+        create_test_json_file(
+            temp_path.join("nicknames.json"),
+            r#"[{"character":"Sol_Badguy","nicknames":["sol"]},{"character":"Ky_Kiske","nicknames":["SOL"]}]"#,
+        )
+        .unwrap();
+        let sol_dir = temp_path.join("Sol_Badguy");
+        create_test_json_file(
+            sol_dir.join("Sol_Badguy.json"),
+            r#"[{"input":"足払い(2D)","name":"Foot Sweep","damage":-5,"guard":"Low","startup":10,"active":"3","recovery":20,"on_hit":"KD","on_block":"-12","level":"2","counter":"Mid","move_type":"normal","risc_gain":null,"risc_loss":null,"wall_damage":null,"input_tension":null,"chip_ratio":null,"scaling":null,"invincibility":"-","cancel":"SR","caption":"","notes":""},
+                {"input":"5P","name":"Punch","damage":26,"guard":"All","startup":4,"active":"3","recovery":9,"on_hit":"+2","on_block":"-1","level":"0","counter":"Small","move_type":"normal","risc_gain":null,"risc_loss":null,"wall_damage":null,"input_tension":null,"chip_ratio":null,"scaling":null,"invincibility":"-","cancel":"SR","caption":"","notes":""}]"#,
+        )
+        .unwrap();
+        create_test_json_file(
+            sol_dir.join("images.json"),
+            r#"[{"input":"2D","move_img":"https://www.dustloop.com/wiki/images/d/d4/","hitbox_img":[]}]"#,
+        )
+        .unwrap();
+        create_test_json_file(
+            sol_dir.join("aliases.json"),
+            r#"[{"input":"2D","aliases":["足払い"]},{"input":"6HS","aliases":["ぶっきら"]}]"#,
+        )
+        .unwrap();
+        create_test_json_file(temp_path.join("May").join("May.json"), "{broken").unwrap();
+
+        let report = CheckReport {
+            diagnostics: data_integrity_issues(temp_path.to_str().unwrap()),
+        };
+        let details = report.to_details();
+
+        // 解析失敗はエラー、参照不整合・値域外は警告
+        assert_eq!(report.count(Severity::Error), 1);
+        assert!(details.contains("[ERROR] [May] Failed to parse"));
+        assert!(details.contains("Nickname 'SOL' is shared by 'Sol_Badguy' and 'Ky_Kiske'"));
+        assert!(details.contains("out-of-range damage: -5"));
+        assert!(details.contains("Move '5P' has no image entry."));
+        assert!(details.contains("Alias entry '6HS' does not match any move input."));
+        assert!(details.contains("Image URL for '2D' is malformed"));
+        assert!(!details.contains("Alias entry '2D'"));
+        assert_eq!(report.count(Severity::Warning), 5);
+    }
+
+    #[test]
+    fn test_data_integrity_shipped_data() {
+        // 同梱データに解析不能・欠損ファイルが無いこと
+        let report = run_checks("data", CheckOptions::ALL);
+        assert_eq!(report.count(Severity::Error), 0, "{}", report.to_details());
+    }
 }
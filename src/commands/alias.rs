@@ -0,0 +1,408 @@
+//! # alias.rs
+//!
+//! エイリアス提案コマンドモジュール  
+//! Discordコマンド /alias 実装モジュール  
+//! 技エイリアスの追加提案を受け付け、開発者が承認／却下ボタンで審査して `aliases.json` へ反映する処理を提供
+
+use crate::aliases::{self, AliasSuggestion};
+use crate::commands::feedback::notify_submitter;
+use crate::feedback_store::{
+    FeedbackCategory, FeedbackRecord, FeedbackStatus, FeedbackStore, NewFeedback,
+};
+use crate::guild::GuildConfig;
+use crate::{check, error::AppError, find, utils, Context};
+use colored::Colorize;
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, UserId,
+};
+use std::collections::HashSet;
+
+/// 審査ボタンの custom_id 接頭辞
+const REVIEW_PREFIX: &str = "alias_review";
+
+/// `/alias pending` で表示する最大件数
+const MAX_PENDING_SHOWN: usize = 10;
+
+/// 審査ボタンの custom_id 解析関数
+///
+/// # 戻り値
+/// 承認なら `(true, 番号)`、却下なら `(false, 番号)`、審査ボタン以外は None
+fn parse_review_id(custom_id: &str) -> Option<(bool, u64)> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != REVIEW_PREFIX {
+        return None;
+    }
+    let accept = match parts.next()? {
+        "accept" => true,
+        "reject" => false,
+        _ => return None,
+    };
+    let id = parts.next()?.parse().ok()?;
+    parts.next().is_none().then_some((accept, id))
+}
+
+/// 審査メッセージ本文生成関数
+fn review_text(record: &FeedbackRecord, suggestion: &AliasSuggestion) -> String {
+    format!(
+        "__**Alias suggestion #{}**__\n**{}** `{}` ← `{}`\nSuggested by {} (<@{}>)",
+        record.id,
+        suggestion.character.replace('_', " "),
+        suggestion.input,
+        suggestion.alias,
+        record.user_name,
+        record.user_id
+    )
+}
+
+/// 審査ボタン生成関数
+fn review_buttons(id: u64) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{REVIEW_PREFIX}:accept:{id}"))
+            .label("Accept")
+            .style(ButtonStyle::Success),
+        CreateButton::new(format!("{REVIEW_PREFIX}:reject:{id}"))
+            .label("Reject")
+            .style(ButtonStyle::Danger),
+    ])]
+}
+
+/// 審査メッセージ生成関数
+fn review_message(record: &FeedbackRecord, suggestion: &AliasSuggestion) -> CreateMessage {
+    CreateMessage::new()
+        .content(review_text(record, suggestion))
+        .components(review_buttons(record.id))
+}
+
+/// エイリアス関連コマンド
+///
+/// # 概要
+/// 以下のサブコマンドを提供する：
+/// - suggest: エイリアス追加を提案
+/// - pending: 未審査の提案を審査ボタン付きで表示（開発者のみ）
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands("suggest", "pending"),
+    subcommand_required
+)]
+pub async fn alias(_: Context<'_>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// エイリアス追加を提案するコマンド処理
+///
+/// # 概要
+/// 対象技の存在と、エイリアスが既存のエイリアス・技名・技入力や未審査の提案と重複しないことを確認し、
+/// フィードバックとして記録した上で開発者へ審査メッセージを DM で送信する。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `character_move` - 対象技の名前・入力またはエイリアス
+/// * `alias` - 追加するエイリアス
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn suggest(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[min_length = 2]
+    #[rename = "move"]
+    #[description = "Move name, input or alias."]
+    character_move: String,
+    #[description = "New alias for the move."] alias: String,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned() + &character + ", " + &character_move + ", " + &alias + "'")
+            .purple()
+    );
+
+    if (check::adaptive_check(
+        ctx,
+        check::CheckOptions::DATA_FOLDER
+            | check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
+    )
+    .await)
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    let alias = alias.trim().to_string();
+    if let Err(msg) = aliases::validate_alias(&alias) {
+        ctx.say(msg).await?;
+        return Ok(());
+    }
+
+    // キャラクター検索
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        &character,
+        GuildConfig::for_context(&ctx).as_ref(),
+    )
+    .await
+    {
+        Ok(character_arg_altered) => character_arg_altered,
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+            return Ok(());
+        }
+    };
+
+    // 技データ読み込み
+    let data_dir = &ctx.data().data_dir;
+    let moves_info = utils::load_move_info(data_dir, &character_arg_altered)?;
+
+    // 対象技検索
+    let index = match find::find_move_index(
+        &ctx.data().data_dir,
+        &character_arg_altered,
+        character_move,
+        &moves_info,
+        GuildConfig::for_context(&ctx).as_ref(),
+    )
+    .await
+    {
+        Ok(index) => index,
+        Err(err) => {
+            ctx.say(err.to_string() + "\nView the moves of a character by executing `/moves`.")
+                .await?;
+            return Ok(());
+        }
+    };
+    let target = &moves_info[index];
+
+    // 重複確認
+    let move_aliases = utils::load_move_aliases(data_dir, &character_arg_altered)?;
+    if let Some(conflict) = aliases::alias_conflict(&alias, &move_aliases, &moves_info) {
+        ctx.say(conflict + ".").await?;
+        return Ok(());
+    }
+
+    let suggestion = AliasSuggestion {
+        character: character_arg_altered.clone(),
+        input: target.input.clone(),
+        alias: alias.clone(),
+    };
+    let feedback = NewFeedback {
+        user_id: ctx.author().id.get(),
+        user_name: ctx.author().name.clone(),
+        guild_id: ctx.guild_id().map(|id| id.get()),
+        channel_id: ctx.channel_id().get(),
+        category: FeedbackCategory::Alias,
+        text: format!(
+            "Alias `{alias}` for {} `{}`",
+            character_arg_altered, target.input
+        ),
+        alias: Some(suggestion.clone()),
+    };
+    let queued = FeedbackStore::update(data_dir, |store| {
+        if let Some(id) = store.pending_alias(&character_arg_altered, &alias) {
+            return Ok(Err(id));
+        }
+        let id = store.add(feedback);
+        Ok(Ok(store.get_mut(id).cloned()))
+    })?;
+    let record = match queued {
+        Ok(Some(record)) => record,
+        Ok(None) => return Ok(()),
+        Err(id) => {
+            ctx.say(format!(
+                "`{alias}` is already awaiting review (suggestion `#{id}`)."
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // 開発者へ審査依頼を送信
+    let owners = ctx.framework().options().owners.clone();
+    for owner in &owners {
+        if let Err(e) = owner
+            .direct_message(ctx, review_message(&record, &suggestion))
+            .await
+        {
+            eprintln!(
+                "{}",
+                format!("エイリアス提案 #{} の送信に失敗: {e}", record.id).red()
+            );
+        }
+    }
+
+    ctx.say(format!(
+        "Suggested `{alias}` for `{}` (suggestion `#{}`). You will be notified once it is reviewed.",
+        target.input, record.id
+    ))
+    .await?;
+    Ok(())
+}
+
+/// 未審査のエイリアス提案を審査ボタン付きで表示するコマンド処理
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn pending(ctx: Context<'_>) -> Result<(), AppError> {
+    let store = FeedbackStore::load(&ctx.data().data_dir)?;
+    let records: Vec<&FeedbackRecord> = store
+        .with_status(FeedbackStatus::Open)
+        .into_iter()
+        .filter(|record| record.alias.is_some())
+        .collect();
+
+    if records.is_empty() {
+        ctx.say("No alias suggestions awaiting review.").await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "{} alias suggestion(s) awaiting review.",
+        records.len()
+    ))
+    .await?;
+    for record in records.into_iter().take(MAX_PENDING_SHOWN) {
+        if let Some(suggestion) = &record.alias {
+            ctx.channel_id()
+                .send_message(ctx, review_message(record, suggestion))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 審査ボタン押下処理
+///
+/// # 概要
+/// 承認時は `aliases.json` へ書き込んで対応済みに、却下時は却下にし、提案者へ通知する。
+/// 審査ボタン以外の操作は無視する。
+///
+/// # 引数
+/// * `ctx` - Serenity コンテキスト
+/// * `owners` - Bot 所有者の ID
+/// * `data_dir` - データディレクトリ
+/// * `component` - ボタン操作
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+pub async fn handle_review(
+    ctx: &serenity::Context,
+    owners: &HashSet<UserId>,
+    data_dir: &str,
+    component: &ComponentInteraction,
+) -> Result<(), AppError> {
+    let Some((accept, id)) = parse_review_id(&component.data.custom_id) else {
+        return Ok(());
+    };
+
+    if !owners.contains(&component.user.id) {
+        component
+            .create_response(
+                ctx,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content("Only the bot owner can review alias suggestions.")
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    // 対応状況の確認と更新（承認時は書き込みに成功した場合のみ対応済み）
+    let outcome = FeedbackStore::update(data_dir, |store| {
+        let Some(record) = store.get_mut(id) else {
+            return Ok(Err(format!("Suggestion `#{id}` not found.")));
+        };
+        let Some(suggestion) = record.alias.clone() else {
+            return Ok(Err(format!("Feedback `#{id}` is not an alias suggestion.")));
+        };
+        if record.status != FeedbackStatus::Open {
+            return Ok(Err(format!(
+                "Suggestion `#{id}` was already {}.",
+                poise::ChoiceParameter::name(&record.status)
+            )));
+        }
+        if !accept {
+            record.set_status(FeedbackStatus::Rejected);
+            return Ok(Ok((record.clone(), suggestion, None)));
+        }
+        match aliases::apply_suggestion(data_dir, &suggestion)? {
+            Some(conflict) => {
+                record.set_status(FeedbackStatus::Rejected);
+                Ok(Ok((record.clone(), suggestion, Some(conflict))))
+            }
+            None => {
+                record.set_status(FeedbackStatus::Resolved);
+                Ok(Ok((record.clone(), suggestion, None)))
+            }
+        }
+    })?;
+
+    let (content, notice) = match outcome {
+        Err(msg) => (msg, None),
+        Ok((record, suggestion, conflict)) => {
+            let base = review_text(&record, &suggestion);
+            let (result, message) = match (record.status, conflict) {
+                (FeedbackStatus::Resolved, _) => (
+                    format!("Accepted by <@{}>.", component.user.id),
+                    format!(
+                        "`{}` can now be used for {} `{}`.",
+                        suggestion.alias,
+                        suggestion.character.replace('_', " "),
+                        suggestion.input
+                    ),
+                ),
+                (_, Some(conflict)) => (
+                    format!("Rejected automatically: {conflict}."),
+                    format!("The alias was taken in the meantime: {conflict}."),
+                ),
+                _ => (
+                    format!("Rejected by <@{}>.", component.user.id),
+                    format!("`{}` was not added.", suggestion.alias),
+                ),
+            };
+            println!(
+                "{}",
+                format!("エイリアス提案 #{}: {result}", record.id).yellow()
+            );
+            (format!("{base}\n**{result}**"), Some((record, message)))
+        }
+    };
+
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .components(Vec::new()),
+            ),
+        )
+        .await?;
+
+    if let Some((record, message)) = notice {
+        notify_submitter(ctx, &record, Some(&message)).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_review_id() {
+        assert_eq!(parse_review_id("alias_review:accept:12"), Some((true, 12)));
+        assert_eq!(parse_review_id("alias_review:reject:3"), Some((false, 3)));
+        assert_eq!(parse_review_id("alias_review:accept:x"), None);
+        assert_eq!(parse_review_id("alias_review:maybe:3"), None);
+        assert_eq!(parse_review_id("alias_review:accept:3:4"), None);
+        assert_eq!(parse_review_id("next"), None);
+    }
+}
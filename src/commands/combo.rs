@@ -0,0 +1,326 @@
+//! # combo.rs
+//!
+//! コンボダメージ計算コマンドモジュール  
+//! Discordコマンド /combo 実装モジュール  
+//! ">" 区切りのコンボレシピから補正・根性値・防御係数を適用した合計ダメージと 1 段毎の内訳を表示する処理を提供
+
+use crate::calc::combo::{self, ComboResult, ComboStep, Defender};
+use crate::common::resolve;
+use crate::guild::GuildConfig;
+use crate::{check, error::AppError, find, utils, Context, EMBED_COLOR};
+use colored::Colorize;
+use poise::serenity_prelude::CreateEmbed;
+
+/// 被コンボ側キャラクター指定の区切り（例："5K > 近S vs Potemkin"）
+const DEFENDER_SEPARATOR: &str = " vs ";
+
+/// カウンターヒット指定の接頭辞（小文字比較）
+const COUNTER_PREFIXES: [&str; 2] = ["ch ", "ch."];
+
+/// コンボの最大段数
+const MAX_COMBO_STEPS: usize = 20;
+
+/// 内訳表の技入力の最大表示文字数
+const INPUT_WIDTH: usize = 12;
+
+/// カウンターヒット指定の解析関数（"CH 6H" → (true, "6H")）
+fn parse_counter(part: &str) -> (bool, &str) {
+    let lower = part.to_lowercase();
+    COUNTER_PREFIXES
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map_or((false, part), |prefix| (true, part[prefix.len()..].trim()))
+}
+
+/// コンボレシピ分割関数（"5K > 近S vs Potemkin" → (["5K", "近S"], Some("Potemkin"))）
+fn split_recipe(recipe: &str) -> (Vec<&str>, Option<&str>) {
+    let recipe = recipe.trim();
+    // ASCII のみ小文字化してバイト位置を保つ
+    let lower = recipe.to_ascii_lowercase();
+    let (moves, defender) = match lower.find(DEFENDER_SEPARATOR) {
+        Some(index) => (
+            &recipe[..index],
+            Some(recipe[index + DEFENDER_SEPARATOR.len()..].trim()),
+        ),
+        None => match lower.strip_suffix(DEFENDER_SEPARATOR.trim_end()) {
+            Some(rest) => (&recipe[..rest.len()], None),
+            None => (recipe, None),
+        },
+    };
+    (
+        utils::split_sequence(moves),
+        defender.filter(|d| !d.is_empty()),
+    )
+}
+
+/// 内訳表生成関数（コードブロック）
+fn breakdown_table(result: &ComboResult) -> String {
+    let mut table = format!(
+        "```\n{:>2} {:<INPUT_WIDTH$} {:>4} {:>5} {:>4} {:>5}\n",
+        "#", "Move", "Base", "Rate", "Dmg", "Total"
+    );
+    for (i, hit) in result.hits.iter().enumerate() {
+        let input: String = hit.input.chars().take(INPUT_WIDTH).collect();
+        let input = if hit.counter {
+            format!("{input}*")
+        } else {
+            input
+        };
+        table += &format!(
+            "{:>2} {:<INPUT_WIDTH$} {:>4} {:>4}% {:>4} {:>5}\n",
+            i + 1,
+            input,
+            hit.base,
+            (hit.proration * 100.0).round(),
+            hit.damage,
+            hit.total
+        );
+    }
+    table + "```"
+}
+
+/// コンボ計算結果の埋め込みメッセージ作成関数
+///
+/// # 引数
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `defender_name` - 被コンボ側のキャラクター名（未指定時は None）
+/// * `defender` - 被コンボ側の情報
+/// * `stats_known` - 被コンボ側のキャラクター情報を読み込めたか否か
+/// * `result` - 計算結果
+///
+/// # 戻り値
+/// 埋め込みメッセージ
+fn create_combo_embed(
+    character_arg_altered: &str,
+    defender_name: Option<&str>,
+    defender: &Defender,
+    stats_known: bool,
+    result: &ComboResult,
+) -> CreateEmbed {
+    let inputs: Vec<&str> = result.hits.iter().map(|hit| hit.input.as_str()).collect();
+    let mut description = format!("**{} damage**　Tension: {}\n", result.total, result.tension);
+    description += &defender_line(defender_name, defender, stats_known);
+    if result.hits.iter().any(|hit| hit.counter) {
+        description += "`*` Counter hit\n";
+    }
+    if !result.skipped.is_empty() {
+        description += &format!("No damage data: {}\n", result.skipped.join(", "));
+    }
+    description += &breakdown_table(result);
+
+    CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .title(format!(
+            "__**{}：{}**__",
+            character_arg_altered,
+            utils::truncate_string(&inputs.join(" > "), 200)
+        ))
+        .url("https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview")
+        .description(utils::truncate_string(&description, 4096))
+}
+
+/// 被コンボ側の説明行生成関数
+///
+/// # 概要
+/// キャラクター指定時にキャラクター情報が無い場合は、標準値で計算したことを明記する。
+fn defender_line(defender_name: Option<&str>, defender: &Defender, stats_known: bool) -> String {
+    let stats = format!(
+        "defense ×{}, guts rank {}, health {}/{}",
+        defender.defense,
+        defender.guts,
+        defender.health,
+        combo::MAX_HEALTH
+    );
+    match defender_name {
+        Some(name) if stats_known => format!("vs {name}: {stats}\n"),
+        Some(name) => format!(
+            "vs {name}: stats unknown, using standard values ({stats})\nExecute `/update` to fetch the character stats.\n"
+        ),
+        None => format!("vs Standard: {stats}\n"),
+    }
+}
+
+/// コンボダメージを計算するコマンド処理
+///
+/// # 概要
+/// ">" 区切りの技一覧（"CH " 接頭辞でカウンターヒット）から合計ダメージと獲得テンションを計算する。
+/// 末尾に "vs キャラクター名" を付けると、そのキャラクターの防御係数・根性値で計算する（情報が無い場合は標準値で計算した旨を表示）。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `recipe` - コンボレシピ
+/// * `health` - 被コンボ側のコンボ開始時の体力（%）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn combo(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[min_length = 2]
+    #[description = "Moves separated by '>', 'CH' prefix for counter hit, optional 'vs <character>'."]
+    recipe: String,
+    #[min = 1]
+    #[max = 100]
+    #[description = "Defender health (%) at the start of the combo. Defaults to 100."]
+    health: Option<u32>,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned() + &character + ", " + &recipe + "'").purple()
+    );
+
+    if (check::adaptive_check(
+        ctx,
+        check::CheckOptions::DATA_FOLDER
+            | check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
+    )
+    .await)
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    // キャラクター検索
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        &character,
+        GuildConfig::for_context(&ctx).as_ref(),
+    )
+    .await
+    {
+        Ok(character_arg_altered) => character_arg_altered,
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+            return Ok(());
+        }
+    };
+
+    let (parts, defender_arg) = split_recipe(&recipe);
+    if parts.is_empty() || parts.len() > MAX_COMBO_STEPS {
+        ctx.say(format!(
+            "A combo needs between 1 and {MAX_COMBO_STEPS} moves separated by `>`."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    // 被コンボ側の情報（キャラクター情報が無い場合は標準値）
+    let health = health.unwrap_or(100);
+    let mut defender = Defender {
+        health: combo::health_from_percent(health),
+        ..Defender::default()
+    };
+    let mut defender_name = None;
+    let mut stats_known = true;
+    if let Some(defender_arg) = defender_arg {
+        let name = match find::find_character(
+            &ctx.data().data_dir,
+            defender_arg,
+            GuildConfig::for_context(&ctx).as_ref(),
+        )
+        .await
+        {
+            Ok(name) => name,
+            Err(err) => {
+                ctx.say(err.to_string()).await?;
+                return Ok(());
+            }
+        };
+        match utils::load_character_info(&ctx.data().data_dir, &name) {
+            Ok(info) => defender = Defender::from_char_info(&info, health),
+            Err(err) => {
+                println!("{}", format!("Defender data unavailable: {err}").yellow());
+                stats_known = false;
+            }
+        }
+        defender_name = Some(name);
+    }
+
+    // 技検索
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, &character_arg_altered)?;
+    let (counters, queries): (Vec<bool>, Vec<&str>) = parts.into_iter().map(parse_counter).unzip();
+    let Some(moves) =
+        resolve::resolve_moves(&ctx, &character_arg_altered, &queries, &moves_info).await?
+    else {
+        return Ok(());
+    };
+    let steps: Vec<ComboStep> = moves
+        .into_iter()
+        .zip(counters)
+        .map(|(move_info, counter)| ComboStep { move_info, counter })
+        .collect();
+
+    let result = combo::calculate(&steps, &defender);
+    let embed = create_combo_embed(
+        &character_arg_altered,
+        defender_name.as_deref(),
+        &defender,
+        stats_known,
+        &result,
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_counter() {
+        assert_eq!(parse_counter("CH 6H"), (true, "6H"));
+        assert_eq!(parse_counter("ch.6H"), (true, "6H"));
+        assert_eq!(parse_counter("6H"), (false, "6H"));
+        assert_eq!(parse_counter("Charge"), (false, "Charge"));
+    }
+
+    #[test]
+    fn test_split_recipe() {
+        assert_eq!(
+            split_recipe("5K > c.S > 2H VS Potemkin"),
+            (vec!["5K", "c.S", "2H"], Some("Potemkin"))
+        );
+        assert_eq!(split_recipe("5K>2D"), (vec!["5K", "2D"], None));
+        assert_eq!(split_recipe("5K > 2D vs "), (vec!["5K", "2D"], None));
+    }
+
+    #[test]
+    fn test_defender_line() {
+        let defender = Defender::default();
+        assert!(defender_line(None, &defender, true).starts_with("vs Standard: defense ×1,"));
+        assert!(
+            defender_line(Some("Potemkin"), &defender, true).starts_with("vs Potemkin: defense")
+        );
+        assert!(defender_line(Some("Potemkin"), &defender, false)
+            .starts_with("vs Potemkin: stats unknown, using standard values (defense"));
+    }
+
+    #[test]
+    fn test_breakdown_table() {
+        let mut move_info = crate::test_utils::create_test_move_info().remove(0);
+        move_info.input = "5K".to_string();
+        move_info.damage = Some(30);
+        let result = combo::calculate(
+            &[ComboStep {
+                move_info,
+                counter: true,
+            }],
+            &Defender::default(),
+        );
+        let table = breakdown_table(&result);
+        assert!(table.contains("5K*"));
+        assert!(table
+            .lines()
+            .nth(2)
+            .unwrap()
+            .trim_end()
+            .ends_with("33    33"));
+    }
+}
@@ -0,0 +1,464 @@
+//! # config.rs
+//!
+//! サーバー設定コマンドモジュール  
+//! Discordコマンド /config 実装モジュール  
+//! フレームデータ表示の項目・並び順・空項目の非表示、サーバー独自の技エイリアス・愛称をサーバー毎に設定する処理を提供（サーバー管理権限が必要）
+
+use crate::common::embed::{self, EmbedView, MoveField};
+use crate::guild::GuildConfig;
+use crate::{check, error::AppError, find, nickname_store, utils, Context};
+use colored::Colorize;
+use poise::serenity_prelude::GuildId;
+
+/// 設定表示の最大文字数（Discord のメッセージ上限未満）
+const MAX_CONFIG_TEXT_LEN: usize = 1900;
+
+/// 項目一覧の表示文字列生成関数（例："`startup`, `active`"）
+fn fields_text(fields: &[MoveField]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("`{}`", field.key()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// サーバー設定の表示文字列生成関数
+fn config_text(config: &GuildConfig) -> String {
+    let mut text = "__**Server config**__\n".to_string();
+    for view in [EmbedView::Simple, EmbedView::Advanced] {
+        let selection = config.selection(view);
+        text += &format!(
+            "**{}**: {}\n",
+            poise::ChoiceParameter::name(&view),
+            fields_text(&selection.fields)
+        );
+    }
+    text += &format!("**hide_empty**: {}", config.hide_empty);
+
+    for entry in &config.nicknames {
+        text += &format!(
+            "\n**nicknames** {}: {}",
+            entry.character,
+            entry
+                .nicknames
+                .iter()
+                .map(|n| format!("`{n}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    for (character, entries) in &config.aliases {
+        for entry in entries {
+            text += &format!(
+                "\n**aliases** {character} `{}`: {}",
+                entry.input,
+                entry
+                    .aliases
+                    .iter()
+                    .map(|a| format!("`{a}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+    utils::truncate_string(&text, MAX_CONFIG_TEXT_LEN)
+}
+
+/// キャラクター検索処理（見つからない場合は案内を送信して None）
+async fn resolve_character(
+    ctx: Context<'_>,
+    config: &GuildConfig,
+    character: &str,
+) -> Result<Option<String>, AppError> {
+    match find::find_character(&ctx.data().data_dir, character, Some(config)).await {
+        Ok(character_arg_altered) if !character_arg_altered.is_empty() => {
+            Ok(Some(character_arg_altered))
+        }
+        Ok(_) => {
+            ctx.say("Pick a single character.").await?;
+            Ok(None)
+        }
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// 変更したサーバー設定の保存と結果送信処理
+async fn save_and_reply(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    config: &GuildConfig,
+    message: String,
+) -> Result<(), AppError> {
+    config.save(&ctx.data().data_dir, guild_id.get())?;
+    println!("{}", format!("Guild {guild_id}: {message}").green());
+    ctx.say(message).await?;
+    Ok(())
+}
+
+/// サーバー設定コマンド
+///
+/// # 概要
+/// 以下のサブコマンドを提供する：
+/// - show: 現在の設定を表示
+/// - fields: simple / advanced 表示の項目と並び順を設定
+/// - hide_empty: 空項目（"-"）の非表示を設定
+/// - alias add / remove: サーバー独自の技エイリアスを追加・削除
+/// - nickname add / remove: サーバー独自の愛称を追加・削除
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands("show", "fields", "hide_empty", "alias_group", "nickname_group"),
+    subcommand_required
+)]
+pub async fn config(_: Context<'_>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// 現在のサーバー設定を表示するコマンド処理
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn show(ctx: Context<'_>) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let config = GuildConfig::load(&ctx.data().data_dir, guild_id.get())?;
+    ctx.say(config_text(&config)).await?;
+    Ok(())
+}
+
+/// 表示項目と並び順を設定するコマンド処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `view` - 表示形式
+/// * `fields` - 表示する項目（"," 区切り、省略時は既定値へ戻す）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn fields(
+    ctx: Context<'_>,
+    #[description = "Display to configure."] view: EmbedView,
+    #[description = "Fields in display order, separated by ','. Leave empty to reset."]
+    fields: Option<String>,
+) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let data_dir = &ctx.data().data_dir;
+    let mut config = GuildConfig::load(data_dir, guild_id.get())?;
+
+    *config.fields_mut(view) = match fields.as_deref().map(embed::parse_fields) {
+        Some(Ok(fields)) => Some(fields),
+        Some(Err(err)) => {
+            ctx.say(err.to_string()).await?;
+            return Ok(());
+        }
+        None => None,
+    };
+    config.save(data_dir, guild_id.get())?;
+    println!(
+        "{}",
+        format!("Guild {guild_id} config updated: {config:?}").green()
+    );
+    ctx.say(config_text(&config)).await?;
+    Ok(())
+}
+
+/// 空項目の非表示を設定するコマンド処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `hide` - 空項目（"-"）を表示しないか否か
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn hide_empty(
+    ctx: Context<'_>,
+    #[description = "Hide fields whose value is '-'."] hide: bool,
+) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let data_dir = &ctx.data().data_dir;
+    let mut config = GuildConfig::load(data_dir, guild_id.get())?;
+    config.hide_empty = hide;
+    config.save(data_dir, guild_id.get())?;
+    ctx.say(config_text(&config)).await?;
+    Ok(())
+}
+
+/// サーバー独自の技エイリアス設定コマンド
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "alias",
+    subcommands("alias_add", "alias_remove"),
+    subcommand_required
+)]
+pub async fn alias_group(_: Context<'_>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// サーバー独自の技エイリアスを追加するコマンド処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `character_move` - 対象技の名前・入力またはエイリアス
+/// * `alias` - 追加するエイリアス
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "add"
+)]
+pub async fn alias_add(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname."] character: String,
+    #[rename = "move"]
+    #[description = "Move name, input or alias."]
+    character_move: String,
+    #[description = "New alias for the move."] alias: String,
+) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    if check::adaptive_check(
+        ctx,
+        check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
+    )
+    .await
+    .is_err()
+    {
+        return Ok(());
+    }
+
+    let data_dir = &ctx.data().data_dir;
+    let mut config = GuildConfig::load(data_dir, guild_id.get())?;
+    let Some(character_arg_altered) = resolve_character(ctx, &config, &character).await? else {
+        return Ok(());
+    };
+    let moves_info = utils::load_move_info(data_dir, &character_arg_altered)?;
+    let index = match find::find_move_index(
+        &ctx.data().data_dir,
+        &character_arg_altered,
+        character_move,
+        &moves_info,
+        Some(&config),
+    )
+    .await
+    {
+        Ok(index) => index,
+        Err(err) => {
+            ctx.say(err.to_string() + "\nView the moves of a character by executing `/moves`.")
+                .await?;
+            return Ok(());
+        }
+    };
+    let input = moves_info[index].input.clone();
+
+    let global = utils::load_move_aliases(data_dir, &character_arg_altered)?;
+    if let Err(msg) = config.add_alias(&character_arg_altered, &input, &alias, &global, &moves_info)
+    {
+        ctx.say(msg).await?;
+        return Ok(());
+    }
+    let message = format!(
+        "Added server alias `{}` for {character_arg_altered} `{input}`.",
+        alias.trim()
+    );
+    save_and_reply(ctx, guild_id, &config, message).await
+}
+
+/// サーバー独自の技エイリアスを削除するコマンド処理
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "remove"
+)]
+pub async fn alias_remove(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname."] character: String,
+    #[description = "Server alias to remove."] alias: String,
+) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let mut config = GuildConfig::load(&ctx.data().data_dir, guild_id.get())?;
+    let Some(character_arg_altered) = resolve_character(ctx, &config, &character).await? else {
+        return Ok(());
+    };
+    match config.remove_alias(&character_arg_altered, &alias) {
+        Ok(input) => {
+            let message = format!(
+                "Removed server alias `{}` from {character_arg_altered} `{input}`.",
+                alias.trim()
+            );
+            save_and_reply(ctx, guild_id, &config, message).await
+        }
+        Err(msg) => {
+            ctx.say(msg).await?;
+            Ok(())
+        }
+    }
+}
+
+/// サーバー独自の愛称設定コマンド
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "nickname",
+    subcommands("nickname_add", "nickname_remove"),
+    subcommand_required
+)]
+pub async fn nickname_group(_: Context<'_>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// サーバー独自の愛称を追加するコマンド処理
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "add"
+)]
+pub async fn nickname_add(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname."] character: String,
+    #[rest]
+    #[description = "Nickname to add."]
+    nickname: String,
+) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    if check::adaptive_check(ctx, check::CheckOptions::NICKNAMES_JSON)
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    let data_dir = &ctx.data().data_dir;
+    let mut config = GuildConfig::load(data_dir, guild_id.get())?;
+    let Some(character_arg_altered) = resolve_character(ctx, &config, &character).await? else {
+        return Ok(());
+    };
+    let global = nickname_store::load(data_dir)?;
+    if let Err(msg) = config.add_nickname(&character_arg_altered, &nickname, &global) {
+        ctx.say(msg).await?;
+        return Ok(());
+    }
+    let message = format!(
+        "Added server nickname `{}` for {character_arg_altered}.",
+        nickname.trim()
+    );
+    save_and_reply(ctx, guild_id, &config, message).await
+}
+
+/// サーバー独自の愛称を削除するコマンド処理
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "remove"
+)]
+pub async fn nickname_remove(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname."] character: String,
+    #[rest]
+    #[description = "Server nickname to remove."]
+    nickname: String,
+) -> Result<(), AppError> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let mut config = GuildConfig::load(&ctx.data().data_dir, guild_id.get())?;
+    let Some(character_arg_altered) = resolve_character(ctx, &config, &character).await? else {
+        return Ok(());
+    };
+    match config.remove_nickname(&character_arg_altered, &nickname) {
+        Ok(removed) => {
+            let message =
+                format!("Removed server nickname `{removed}` from {character_arg_altered}.");
+            save_and_reply(ctx, guild_id, &config, message).await
+        }
+        Err(msg) => {
+            ctx.say(msg).await?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_text() {
+        let config = GuildConfig {
+            simple_fields: Some(vec![MoveField::Startup, MoveField::OnBlock]),
+            advanced_fields: None,
+            hide_empty: true,
+            ..Default::default()
+        };
+        let text = config_text(&config);
+        assert!(text.contains("**simple**: `startup`, `on_block`"));
+        assert!(text.contains("**advanced**: `damage`"));
+        assert!(text.ends_with("**hide_empty**: true"));
+    }
+
+    #[test]
+    fn test_config_text_overlays() {
+        let mut config = GuildConfig::default();
+        config.nicknames.push(crate::Nicknames {
+            character: "Ky_Kiske".to_string(),
+            nicknames: vec!["団長".to_string()],
+        });
+        config.aliases.insert(
+            "Sol_Badguy".to_string(),
+            vec![crate::MoveAliases {
+                input: "236P".to_string(),
+                aliases: vec!["GF".to_string(), "銃".to_string()],
+            }],
+        );
+        let text = config_text(&config);
+        assert!(text.contains("\n**nicknames** Ky_Kiske: `団長`"));
+        assert!(text.contains("\n**aliases** Sol_Badguy `236P`: `GF`, `銃`"));
+    }
+}
@@ -0,0 +1,57 @@
+//! # doctor.rs
+//!
+//! データ診断モジュール  
+//! Discordコマンド /doctor 実装モジュール  
+//! データファイルの存在確認と整合性検証を実行し、キャラクター別の診断結果を送信する処理を提供  
+//! このコマンドは、Botの所有者のみ実行可能
+
+use crate::check::{self, CheckOptions, Severity};
+use crate::{error::Result, Context, EMBED_COLOR};
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+
+/// データ診断処理  
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn doctor(ctx: Context<'_>) -> Result<()> {
+    // 全JSONの解析を伴うため応答保留
+    ctx.defer().await?;
+
+    let data_dir = ctx.data().data_dir.clone();
+    let report =
+        tokio::task::spawn_blocking(move || check::run_checks(&data_dir, CheckOptions::ALL))
+            .await
+            .map_err(|e| crate::AppError::DataProcessing(e.to_string()))?;
+
+    if report.is_ok() {
+        ctx.say("No data problems found.").await?;
+        return Ok(());
+    }
+
+    // キャラクター別件数一覧
+    let mut description = format!(
+        "Errors: **{}** / Warnings: **{}**\n```diff",
+        report.count(Severity::Error),
+        report.count(Severity::Warning)
+    );
+    for (name, errors, warnings) in report.counts_by_character() {
+        let mark = if errors > 0 { '-' } else { '!' };
+        description += &format!("\n{mark} {name}: {errors} errors, {warnings} warnings");
+    }
+    description += "\n```";
+
+    let embed = CreateEmbed::new()
+        .title("Data Doctor")
+        .description(description)
+        .color(EMBED_COLOR);
+
+    // 詳細はテキストファイルとして添付
+    let attachment = CreateAttachment::bytes(report.to_details().into_bytes(), "doctor_report.txt");
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(attachment),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! # export.rs
+//!
+//! 技データ出力コマンドモジュール  
+//! Discordコマンド /export 実装モジュール  
+//! キャラクターの全技データをエイリアス・画像 URL 付きで CSV・Markdown・JSON ファイルとして添付する処理を提供
+
+use crate::guild::GuildConfig;
+use crate::move_export::{self as export, ExportFormat};
+use crate::{check, error::AppError, find, utils, Context};
+use colored::Colorize;
+use poise::serenity_prelude::CreateAttachment;
+
+/// 技データ出力コマンド
+///
+/// # 概要
+/// 指定キャラクターの全技データ（サーバー独自のエイリアスを含む）を指定形式のファイルとして添付する。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `format` - 出力形式（省略時は CSV）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[description = "File format (default: csv)."] format: Option<ExportFormat>,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned() + &character + "'").purple()
+    );
+
+    if (check::adaptive_check(
+        ctx,
+        check::CheckOptions::DATA_FOLDER
+            | check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS
+            | check::CheckOptions::CHARACTER_IMAGES,
+    )
+    .await)
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    // キャラクター検索
+    let guild = GuildConfig::for_context(&ctx);
+    let character_arg_altered =
+        match find::find_character(&ctx.data().data_dir, &character, guild.as_ref()).await {
+            Ok(character_arg_altered) => character_arg_altered,
+            Err(err) => {
+                ctx.say(err.to_string()).await?;
+                println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+                return Ok(());
+            }
+        };
+
+    // 技・エイリアス・画像データ読み込み
+    let data_dir = &ctx.data().data_dir;
+    let moves_info = utils::load_move_info(data_dir, &character_arg_altered)?;
+    let mut aliases_data = utils::load_move_aliases(data_dir, &character_arg_altered)?;
+    if let Some(guild_aliases) = guild
+        .as_ref()
+        .and_then(|g| g.aliases.get(&character_arg_altered))
+    {
+        aliases_data.extend(guild_aliases.iter().cloned());
+    }
+    let image_links = utils::load_image_links(data_dir, &character_arg_altered)?;
+
+    // 出力ファイル生成
+    let format = format.unwrap_or(ExportFormat::Csv);
+    let rows = export::build_export(&moves_info, &aliases_data, &image_links);
+    let content = export::render(format, &character_arg_altered, &rows)?;
+    let attachment = CreateAttachment::bytes(
+        content.into_bytes(),
+        export::file_name(&character_arg_altered, format),
+    );
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!(
+                "{} moves of `{}` as {}.",
+                rows.len(),
+                character_arg_altered,
+                poise::ChoiceParameter::name(&format)
+            ))
+            .attachment(attachment),
+    )
+    .await?;
+    Ok(())
+}
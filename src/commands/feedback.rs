@@ -2,52 +2,336 @@
 //!
 //! フィードバック送信モジュール  
 //! Discordコマンド /feedback 実装モジュール  
-//! ユーザーから受け取ったフィードバックメッセージをファイルに保存し、送信完了を通知する処理を提供
+//! ユーザーから受け取ったフィードバックを構造化して保存し、開発者による一覧・対応・返信と送信者への通知を行う処理を提供
 
-// crate 内の共通型（Context, AppError）をインポート
-use crate::{error::AppError, Context};
-// colored クレートを利用して、コンソール出力文字列に色を付けるための拡張メソッドを使用
-use colored::Colorize;
-// ファイル操作および書き込み操作に必要な標準ライブラリのモジュールをインポート
-use std::{
-    fs::OpenOptions, // ファイルオープンのためのオプション指定をサポート
-    io::Write,       // ファイル書き込み操作のためのトレイトをインポート
+use crate::common::paginate::{paginate, Page};
+use crate::feedback_store::{
+    FeedbackCategory, FeedbackRecord, FeedbackStatus, FeedbackStore, NewFeedback,
 };
+use crate::{error::AppError, Context, EMBED_COLOR};
+use colored::Colorize;
+use poise::serenity_prelude as serenity;
+use serenity::{CacheHttp, ChannelId, CreateEmbed, CreateMessage, UserId};
+
+/// 一覧 1 ページあたりの表示件数
+const RECORDS_PER_PAGE: usize = 5;
+
+/// 一覧表示時の本文最大文字数
+const PREVIEW_LEN: usize = 200;
+
+/// フィードバック 1 件の一覧表示文字列生成関数
+fn record_summary(record: &FeedbackRecord) -> (String, String) {
+    let name = format!(
+        "#{} [{}] {}",
+        record.id,
+        poise::ChoiceParameter::name(&record.category),
+        record.user_name
+    );
+    let mut value = format!(
+        "{}\n<t:{}:R> in <#{}>",
+        crate::utils::truncate_string(&record.text, PREVIEW_LEN),
+        record.created_at,
+        record.channel_id
+    );
+    if !record.replies.is_empty() {
+        value += &format!(" · {} repl(ies)", record.replies.len());
+    }
+    (name, value)
+}
+
+/// 一覧ページ生成関数
+fn list_pages(records: &[&FeedbackRecord], status: FeedbackStatus) -> Vec<Page> {
+    let status_name = poise::ChoiceParameter::name(&status);
+    if records.is_empty() {
+        return vec![CreateEmbed::new()
+            .color(EMBED_COLOR)
+            .title(format!("Feedback ({status_name})"))
+            .description("No feedback found.")
+            .into()];
+    }
+    records
+        .chunks(RECORDS_PER_PAGE)
+        .map(|chunk| {
+            let fields = chunk.iter().map(|record| {
+                let (name, value) = record_summary(record);
+                (name, value, false)
+            });
+            CreateEmbed::new()
+                .color(EMBED_COLOR)
+                .title(format!("Feedback ({status_name}, {} total)", records.len()))
+                .fields(fields)
+                .into()
+        })
+        .collect()
+}
+
+/// 送信者への通知文生成関数
+fn notification_text(record: &FeedbackRecord, message: Option<&str>) -> String {
+    let mut text = match record.status {
+        FeedbackStatus::Open => format!("The dev replied to your feedback #{}:", record.id),
+        FeedbackStatus::Resolved => format!("Your feedback #{} has been resolved.", record.id),
+        FeedbackStatus::Rejected => format!("Your feedback #{} has been closed.", record.id),
+    };
+    text += &format!(
+        "\n> {}",
+        crate::utils::truncate_string(&record.text, PREVIEW_LEN)
+    );
+    if let Some(message) = message {
+        text += &format!("\n\n{message}");
+    }
+    text
+}
+
+/// 送信者通知関数
+///
+/// DM を送信し、失敗した場合は送信元チャンネルでメンションして通知する。
+///
+/// # 戻り値
+/// 通知できた場合は `true`
+pub(crate) async fn notify_submitter(
+    http: impl CacheHttp + Copy,
+    record: &FeedbackRecord,
+    message: Option<&str>,
+) -> bool {
+    let text = notification_text(record, message);
+    let dm = UserId::new(record.user_id)
+        .direct_message(http, CreateMessage::new().content(&text))
+        .await;
+    if dm.is_ok() {
+        return true;
+    }
+
+    let mention = format!("<@{}> {text}", record.user_id);
+    match ChannelId::new(record.channel_id).say(http, mention).await {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("フィードバック #{} の通知に失敗: {e}", record.id).red()
+            );
+            false
+        }
+    }
+}
+
+/// フィードバック記録処理
+async fn submit_feedback(
+    ctx: Context<'_>,
+    text: String,
+    category: FeedbackCategory,
+) -> Result<(), AppError> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        ctx.say("Feedback cannot be empty.").await?;
+        return Ok(());
+    }
+
+    let feedback = NewFeedback {
+        user_id: ctx.author().id.get(),
+        user_name: ctx.author().name.clone(),
+        guild_id: ctx.guild_id().map(|id| id.get()),
+        channel_id: ctx.channel_id().get(),
+        category,
+        text,
+        alias: None,
+    };
+    let id = FeedbackStore::update(&ctx.data().data_dir, |store| Ok(store.add(feedback)))?;
+
+    println!(
+        "{}",
+        format!("フィードバック #{id} を記録しました").yellow()
+    );
+    ctx.say(format!(
+        "Submitted successfully! Your feedback id is `#{id}`. You will be notified when it is resolved."
+    ))
+    .await?;
+    Ok(())
+}
+
+/// 対応状況更新処理（更新後の記録を返す、存在しない場合は None）
+fn set_status(
+    data_dir: &str,
+    id: u64,
+    status: FeedbackStatus,
+    reply: Option<&str>,
+) -> Result<Option<FeedbackRecord>, AppError> {
+    FeedbackStore::update(data_dir, |store| {
+        Ok(store.get_mut(id).map(|record| {
+            record.set_status(status);
+            if let Some(reply) = reply {
+                record.replies.push(reply.to_string());
+            }
+            record.clone()
+        }))
+    })
+}
 
 /// フィードバック送信処理
 /// 開発者宛フィードバック登録
 ///
-/// ユーザーから受け取ったテキストを 'request.txt' ファイルに追記し、
-/// 送信完了メッセージを返す処理。
-#[poise::command(prefix_command, slash_command, aliases("r"))]
+/// # 概要
+/// 以下のサブコマンドを提供する：
+/// - submit: フィードバックを送信
+/// - list: フィードバック一覧を表示（開発者のみ）
+/// - resolve: フィードバックを対応済み／却下にして送信者へ通知（開発者のみ）
+/// - reply: 送信者へ返信（開発者のみ）
+///
+/// プレフィックスコマンドでは `!feedback <text>` のままでも送信できる。
+#[poise::command(
+    prefix_command,
+    slash_command,
+    aliases("r"),
+    subcommands("submit", "list", "resolve", "reply")
+)]
 pub async fn feedback(
-    ctx: Context<'_>, // Discord コマンド実行時のコンテキスト（ユーザー情報、チャンネル情報等）を保持
-    #[description = "Message for the dev."] text: String, // 開発者へ送るフィードバックのメッセージ
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "Message for the dev."]
+    text: Option<String>,
 ) -> Result<(), AppError> {
-    // 正常終了時は Ok(())、エラー時は Error を返す非同期関数
-    // ファイルオープン処理
-    // 'request.txt' を新規作成（存在しなければ）し、既存の場合は末尾に追記するモードでオープンする
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("./request.txt")
-        .expect("\nFailed to open 'request.txt' file."); // オープン失敗時はエラーメッセージを出力してパニック
-
-    // テキスト整形処理
-    // ユーザーから送られたフィードバックテキストに改行を追加し、各メッセージ間に空行を挿入する
-    let new_text = text.clone() + "\n\n";
-
-    // ファイル書き込み処理
-    // 整形済みのテキストを 'request.txt' に書き込む
-    write!(file, "{new_text}").expect("\nFailed to write to 'request.txt'");
-
-    // ログ出力処理
-    // コンソールに書き込み完了のログを出力し、黄色で強調表示する
-    println!("{}", "Done writting to 'request.txt'".yellow());
-
-    // 送信メッセージ出力処理
-    // Discord のチャネルに成功メッセージを送信する
-    ctx.say("Submitted successfully!").await?;
-
-    Ok(()) // 正常終了
+    match text {
+        Some(text) => submit_feedback(ctx, text, FeedbackCategory::Other).await,
+        None => {
+            ctx.say("Usage: `/feedback submit <text> [category]`.")
+                .await?;
+            Ok(())
+        }
+    }
+}
+
+/// フィードバックを送信するコマンド処理
+#[poise::command(prefix_command, slash_command)]
+pub async fn submit(
+    ctx: Context<'_>,
+    #[description = "Message for the dev."] text: String,
+    #[description = "Kind of feedback."] category: Option<FeedbackCategory>,
+) -> Result<(), AppError> {
+    submit_feedback(ctx, text, category.unwrap_or(FeedbackCategory::Other)).await
+}
+
+/// フィードバック一覧を表示するコマンド処理
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Status to list (default: open)."] status: Option<FeedbackStatus>,
+) -> Result<(), AppError> {
+    let status = status.unwrap_or(FeedbackStatus::Open);
+    let store = FeedbackStore::load(&ctx.data().data_dir)?;
+    let records = store.with_status(status);
+    paginate(ctx, list_pages(&records, status)).await
+}
+
+/// フィードバックの対応状況を更新するコマンド処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `id` - フィードバック番号
+/// * `message` - 送信者への一言（任意）
+/// * `rejected` - 却下として閉じる場合は true
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn resolve(
+    ctx: Context<'_>,
+    #[description = "Feedback id."] id: u64,
+    #[description = "Message for the submitter."] message: Option<String>,
+    #[description = "Close as rejected instead of resolved."] rejected: Option<bool>,
+) -> Result<(), AppError> {
+    let status = if rejected.unwrap_or(false) {
+        FeedbackStatus::Rejected
+    } else {
+        FeedbackStatus::Resolved
+    };
+    let Some(record) = set_status(&ctx.data().data_dir, id, status, message.as_deref())? else {
+        ctx.say(format!("Feedback `#{id}` not found.")).await?;
+        return Ok(());
+    };
+
+    let notified = notify_submitter(ctx, &record, message.as_deref()).await;
+    ctx.say(format!(
+        "Feedback `#{id}` marked as {}.{}",
+        poise::ChoiceParameter::name(&status),
+        if notified {
+            ""
+        } else {
+            " (submitter could not be notified)"
+        }
+    ))
+    .await?;
+    Ok(())
+}
+
+/// 送信者へ返信するコマンド処理（対応状況は変更しない）
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn reply(
+    ctx: Context<'_>,
+    #[description = "Feedback id."] id: u64,
+    #[rest]
+    #[description = "Reply for the submitter."]
+    text: String,
+) -> Result<(), AppError> {
+    let record = FeedbackStore::update(&ctx.data().data_dir, |store| {
+        Ok(store.get_mut(id).map(|record| {
+            record.replies.push(text.clone());
+            record.clone()
+        }))
+    })?;
+    let Some(record) = record else {
+        ctx.say(format!("Feedback `#{id}` not found.")).await?;
+        return Ok(());
+    };
+
+    // 返信時は未対応扱いの文面で通知する
+    let mut shown = record.clone();
+    shown.status = FeedbackStatus::Open;
+    let notified = notify_submitter(ctx, &shown, Some(&text)).await;
+    ctx.say(if notified {
+        format!("Reply sent for feedback `#{id}`.")
+    } else {
+        format!("Reply saved for feedback `#{id}`, but the submitter could not be notified.")
+    })
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status: FeedbackStatus) -> FeedbackRecord {
+        FeedbackRecord {
+            id: 7,
+            user_id: 1,
+            user_name: "tester".to_string(),
+            guild_id: None,
+            channel_id: 2,
+            created_at: 0,
+            category: FeedbackCategory::Bug,
+            text: "5K startup is wrong".to_string(),
+            status,
+            replies: Vec::new(),
+            closed_at: None,
+            alias: None,
+        }
+    }
+
+    #[test]
+    fn test_notification_text() {
+        let text = notification_text(&record(FeedbackStatus::Resolved), Some("Fixed."));
+        assert!(text.starts_with("Your feedback #7 has been resolved."));
+        assert!(text.contains("> 5K startup is wrong"));
+        assert!(text.ends_with("\n\nFixed."));
+
+        let text = notification_text(&record(FeedbackStatus::Open), None);
+        assert!(text.starts_with("The dev replied to your feedback #7:"));
+    }
+
+    #[test]
+    fn test_list_pages() {
+        let records: Vec<FeedbackRecord> = (0..7).map(|_| record(FeedbackStatus::Open)).collect();
+        let refs: Vec<&FeedbackRecord> = records.iter().collect();
+        assert_eq!(list_pages(&refs, FeedbackStatus::Open).len(), 2);
+        assert_eq!(list_pages(&[], FeedbackStatus::Open).len(), 1);
+    }
 }
@@ -4,10 +4,16 @@
 //! 指定されたキャラクター名（または愛称）と技名（入力またはエイリアス）をもとに、
 //! JSONファイルから該当データを取得し、画像リンクや各種技パラメータを整形して表示する。
 
-use crate::{check, error::AppError, find, Context, ImageLinks, MoveInfo, EMBED_COLOR};
+use crate::calc::counter;
+use crate::common::embed::{self, EmbedView, FieldSelection};
+use crate::common::resolve;
+use crate::guild::GuildConfig;
+use crate::{
+    check, error::AppError, find, guild, image_cache, utils, Context, MoveInfo, EMBED_COLOR,
+};
 use colored::Colorize;
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
-use std::{fs, string::String};
+use poise::serenity_prelude::CreateEmbed;
+use std::string::String;
 
 /// デフォルト画像URL
 const IMAGE_DEFAULT: &str = "https://www.dustloop.com/wiki/images/5/54/GGST_Logo_Sparkly.png";
@@ -22,7 +28,13 @@ const IMAGE_DEFAULT: &str = "https://www.dustloop.com/wiki/images/5/54/GGST_Logo
 /// 成功時は正式なキャラクター名、失敗時はエラー
 async fn load_character_data(character: &str, ctx: &Context<'_>) -> Result<String, AppError> {
     // キャラクター名の正規化　入力に基づく正式名称の取得
-    let character_arg_altered = match find::find_character(&character.to_string()).await {
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
         Ok(character_arg_altered) => character_arg_altered, // 正式名称取得
         Err(err) => {
             ctx.say(err.to_string()).await?; // エラーメッセージ送信
@@ -34,244 +46,6 @@ async fn load_character_data(character: &str, ctx: &Context<'_>) -> Result<Strin
     Ok(character_arg_altered)
 }
 
-/// キャラクターの技情報を読み込む関数
-///
-/// # 引数
-/// * `character_arg_altered` - 正式なキャラクター名
-///
-/// # 戻り値
-/// 成功時は技情報のベクター
-fn load_moves_info(character_arg_altered: &str) -> Vec<MoveInfo> {
-    // JSONファイルパスの組み立て　キャラクター情報ファイルのパス生成
-    let char_file_path =
-        "data/".to_owned() + character_arg_altered + "/" + character_arg_altered + ".json";
-    // JSONファイルの読み込み　キャラクター情報の取得
-    let char_file_data = fs::read_to_string(char_file_path)
-        .expect(&("\nFailed to read '".to_owned() + character_arg_altered + ".json" + "' file."));
-
-    // JSON文字列のデシリアライズ　技情報の構造体へ変換
-    let moves_info = serde_json::from_str::<Vec<MoveInfo>>(&char_file_data).unwrap();
-
-    // JSON読み込み成功の表示　確認メッセージ出力
-    println!(
-        "{}",
-        ("Successfully read '".to_owned() + character_arg_altered + ".json' file.").green()
-    );
-
-    moves_info
-}
-
-/// 画像リンク情報を読み込む関数
-///
-/// # 引数
-/// * `character_arg_altered` - 正式なキャラクター名
-///
-/// # 戻り値
-/// 画像リンク情報のベクター
-fn load_image_links(character_arg_altered: &str) -> Vec<ImageLinks> {
-    // 画像リンク用JSONファイルのパス組み立て　画像情報ファイルの指定
-    let images_json_path = "data/".to_owned() + character_arg_altered + "/images.json";
-    println!("Loading images JSON from: {images_json_path}");
-    let image_links = fs::read_to_string(&images_json_path).expect(
-        &("\nFailed to read 'data/".to_owned() + character_arg_altered + "/images.json' file."),
-    );
-
-    // JSONファイルを行単位で分割
-    println!("Debug: Parsing image_links JSON for {character_arg_altered}");
-
-    // デバッグログ処理（本番では省略可能）
-    debug_image_links_json(&image_links);
-
-    // 画像リンクJSONの安全なデシリアライズ
-    parse_image_links(&image_links)
-}
-
-/// 画像リンクJSONのデバッグ情報を出力する関数
-///
-/// # 引数
-/// * `image_links` - 画像リンクJSONの文字列
-fn debug_image_links_json(image_links: &str) {
-    println!("==================== MANUAL DEBUGGING ====================");
-    // まず、JSON全体を解析できるかチェック
-    let parse_result = serde_json::from_str::<serde_json::Value>(image_links);
-    match parse_result {
-        Ok(json_value) => {
-            println!("Entire JSON successfully parsed as generic Value");
-
-            // JSONが配列であることを確認
-            if let serde_json::Value::Array(items) = json_value {
-                println!("JSON is an array with {} items", items.len());
-
-                // 各項目を個別にテスト
-                for (i, item) in items.iter().enumerate() {
-                    println!("Testing item #{i}");
-
-                    // テスト用のJSONオブジェクトを作成
-                    let test_json = serde_json::to_string(item).unwrap();
-
-                    // デシリアライズを試みる
-                    match serde_json::from_str::<ImageLinks>(&test_json) {
-                        Ok(_) => println!("Item #{i} deserialized successfully"),
-                        Err(e) => {
-                            println!("ERROR with item #{i}: {e}");
-                            println!("Problem item: {test_json}");
-
-                            // 問題のあるアイテムの詳細を出力
-                            if let Some(input) = item.get("input") {
-                                println!(
-                                    "input field: {input:?} (type: {})",
-                                    if input.is_string() {
-                                        "string"
-                                    } else if input.is_number() {
-                                        "number"
-                                    } else {
-                                        "other"
-                                    }
-                                );
-                            }
-
-                            if let Some(move_img) = item.get("move_img") {
-                                println!(
-                                    "move_img field: {move_img:?} (type: {})",
-                                    if move_img.is_string() {
-                                        "string"
-                                    } else if move_img.is_number() {
-                                        "number"
-                                    } else {
-                                        "other"
-                                    }
-                                );
-                            }
-
-                            if let Some(hitbox_img) = item.get("hitbox_img") {
-                                println!(
-                                    "hitbox_img field: {hitbox_img:?} (type: {})",
-                                    if hitbox_img.is_array() {
-                                        "array"
-                                    } else if hitbox_img.is_number() {
-                                        "number"
-                                    } else {
-                                        "other"
-                                    }
-                                );
-                            }
-                        }
-                    }
-                }
-            } else {
-                println!("JSON is not an array!");
-            }
-        }
-        Err(e) => {
-            println!("Failed to parse the entire JSON: {e}");
-        }
-    }
-    println!("================== END MANUAL DEBUGGING ==================");
-}
-
-/// 画像リンクJSONをパースする関数
-///
-/// # 引数
-/// * `image_links` - 画像リンクJSONの文字列
-///
-/// # 戻り値
-/// 画像リンク情報のベクター
-fn parse_image_links(image_links: &str) -> Vec<ImageLinks> {
-    println!("Safely deserializing image links...");
-    let mut image_links_vec = Vec::new();
-
-    // JSONが配列であることを確認
-    if let Ok(serde_json::Value::Array(items)) =
-        serde_json::from_str::<serde_json::Value>(image_links)
-    {
-        // 各要素を個別に処理
-        for item in items {
-            let item_json = serde_json::to_string(&item).unwrap_or_default();
-            match serde_json::from_str::<ImageLinks>(&item_json) {
-                Ok(link) => image_links_vec.push(link),
-                Err(e) => {
-                    // エラーを出力するが、処理は継続
-                    println!("Warning: Failed to deserialize item: {e}");
-                    println!("Skipping problematic item: {item_json}");
-                }
-            }
-        }
-    } else {
-        println!("Error: images.json is not a valid JSON array");
-    }
-
-    println!(
-        "Successfully deserialized {count} image links",
-        count = image_links_vec.len()
-    );
-
-    image_links_vec
-}
-
-/// 技に対応する画像URLを取得する
-///
-/// # 引数
-/// * `move_data` - 技情報
-/// * `image_links` - 画像リンク情報の配列
-///
-/// # 戻り値
-/// 画像のURL（見つからない場合はデフォルト画像）
-fn get_move_image_url(move_data: &MoveInfo, image_links: &[ImageLinks]) -> String {
-    let mut embed_image = IMAGE_DEFAULT.to_string();
-
-    // 括弧を除去した技名を作成（例：「2d(2d)」→「2d」）
-    let cleaned_input = if move_data.input.contains('(') {
-        move_data
-            .input
-            .split('(')
-            .next()
-            .unwrap_or("")
-            .trim()
-            .to_string()
-    } else {
-        move_data.input.to_string()
-    };
-
-    // 括弧内のコマンドを取得（例：「2d(2d)」→「2d」）
-    let bracket_content = if move_data.input.contains('(') && move_data.input.contains(')') {
-        let start = move_data.input.find('(').unwrap_or(0) + 1;
-        let end = move_data.input.find(')').unwrap_or(move_data.input.len());
-        if start < end {
-            move_data.input[start..end].to_string()
-        } else {
-            String::new()
-        }
-    } else {
-        String::new()
-    };
-
-    // 画像リンクの探索　対象技の画像リンクを検索
-    for img_links in image_links {
-        // 完全一致、括弧を除去した技名との一致、または括弧内のコマンドとの一致
-        if (move_data.input.to_lowercase() == img_links.input.to_lowercase()
-            || (!cleaned_input.is_empty()
-                && cleaned_input.to_lowercase() == img_links.input.to_lowercase())
-            || (!bracket_content.is_empty()
-                && bracket_content.to_lowercase() == img_links.input.to_lowercase()))
-            && !img_links.move_img.is_empty()
-        {
-            embed_image = img_links.move_img.to_string(); // 画像リンク更新
-            break; // 探索終了
-        }
-        // 部分一致（バックアップ）
-        else if img_links
-            .input
-            .to_lowercase()
-            .contains(&move_data.input.to_lowercase())
-            && !img_links.move_img.is_empty()
-        {
-            embed_image = img_links.move_img.to_string(); // 画像リンク更新
-        }
-    }
-
-    embed_image
-}
-
 /// 技情報と画像データを読み込む関数
 ///
 /// # 引数
@@ -287,16 +61,18 @@ async fn find_move_and_images(
     ctx: &Context<'_>,
 ) -> Result<(MoveInfo, String), AppError> {
     // 技情報の読み込み
-    let moves_info = load_moves_info(character_arg_altered);
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, character_arg_altered)?;
 
     // 画像リンク情報の読み込み
-    let image_links = load_image_links(character_arg_altered);
+    let image_links = utils::load_image_links(&ctx.data().data_dir, character_arg_altered)?;
 
     // 技インデックス検索
     let move_index = match find::find_move_index(
+        &ctx.data().data_dir,
         &character_arg_altered.to_string(),
         character_move.to_string(),
         &moves_info,
+        GuildConfig::for_context(ctx).as_ref(),
     )
     .await
     {
@@ -324,17 +100,54 @@ async fn find_move_and_images(
     );
 
     // 技画像URLの取得
-    let embed_image = get_move_image_url(&move_data, &image_links);
+    let embed_image = find::find_move_image(&move_data.input, &image_links)
+        .unwrap_or(IMAGE_DEFAULT)
+        .to_string();
 
     Ok((move_data, embed_image))
 }
 
+/// カウンターヒットからの繋ぎ判定文字列生成関数
+///
+/// # 引数
+/// * `move_info` - 始動技の技情報
+/// * `follow_ups` - 判定する後続技
+///
+/// # 戻り値
+/// 後続技毎の判定結果（1 行 1 技）
+fn counter_follow_up_text(move_info: &MoveInfo, follow_ups: &[MoveInfo]) -> String {
+    let Some(advantage) = counter::counter_advantage(move_info) else {
+        return format!(
+            "`{}` のカウンターヒット時の硬直差が不明です。",
+            move_info.input
+        );
+    };
+    let mut text = format!(
+        "__**カウンターヒット時 {:+}F からの繋ぎ**__",
+        advantage.on_counter()
+    );
+    for follow_up in follow_ups {
+        let startup = follow_up
+            .startup
+            .map_or("-".to_string(), |startup| startup.to_string());
+        let mark = match counter::links_into(advantage.on_counter(), follow_up) {
+            Some(true) => "✅",
+            Some(false) => "❌",
+            None => "❔",
+        };
+        text += &format!("\n{mark} `{}` (発生 {startup}F)", follow_up.input);
+    }
+    text
+}
+
 /// 技情報の詳細な埋め込みメッセージを作成する関数
 ///
 /// # 引数
 /// * `move_info` - 技情報
 /// * `embed_image` - 埋め込む画像のURL
 /// * `character_arg_altered` - 正式なキャラクター名
+/// * `follow_ups` - カウンターヒットから繋がるか判定する後続技（空の場合は判定しない）
+/// * `selection` - 表示項目の指定
 ///
 /// # 戻り値
 /// 埋め込みメッセージのベクター
@@ -342,91 +155,13 @@ fn create_advanced_embeds(
     move_info: &MoveInfo,
     embed_image: &str,
     character_arg_altered: &str,
+    follow_ups: &[MoveInfo],
+    selection: &FieldSelection,
 ) -> Vec<CreateEmbed> {
     // 埋め込みメッセージ群生成用ベクターの初期化
     let mut vec_embeds = Vec::new();
-    // 埋め込みURLの作成　Dustloop Wiki のキャラクター概要ページURL生成
-    let embed_url = "https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview";
-    // 埋め込みフッターの作成　技に関するキャプションを利用
-    let embed_footer = CreateEmbedFooter::new(&move_info.caption);
-
-    // 埋め込みメッセージの生成　技データの各パラメータをフィールドとして追加
-    let embed = CreateEmbed::new()
-        .color(EMBED_COLOR) // 埋め込みカラー設定
-        .title(format!(
-            "{}：{input}",
-            character_arg_altered,
-            input = move_info.input
-        ))
-        .url(embed_url) // URL設定
-        .image(embed_image) // 画像リンク設定
-        .fields(vec![
-            (
-                "ダメージ",
-                &move_info.damage.map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            ("ガード", &move_info.guard, true),
-            ("無敵", &move_info.invincibility, true),
-            (
-                "始動",
-                &move_info.startup.map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            ("持続", &move_info.active, true),
-            (
-                "硬直",
-                &move_info
-                    .recovery
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            ("ヒット時", &move_info.on_hit, true),
-            ("ガード時", &move_info.on_block, true),
-            ("カウンター", &move_info.counter, true),
-            ("技レベル", &move_info.level, true),
-            (
-                "リスク増加",
-                &move_info
-                    .risc_gain
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            (
-                "リスク減少",
-                &move_info
-                    .risc_loss
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            (
-                "壁ダメージ",
-                &move_info
-                    .wall_damage
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            (
-                "入力緊張度",
-                &move_info
-                    .input_tension
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            (
-                "チップ比率",
-                &move_info
-                    .chip_ratio
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            (
-                "スケーリング",
-                &move_info.scaling.map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-        ])
-        .footer(embed_footer); // フッター設定
+    // 埋め込みメッセージの生成　指定された項目をフィールドとして追加
+    let embed = embed::create_move_embed(character_arg_altered, move_info, embed_image, selection);
 
     // 生成した埋め込みメッセージをベクターに追加
     vec_embeds.push(embed);
@@ -439,15 +174,47 @@ fn create_advanced_embeds(
         vec_embeds.push(embed2); // ベクターに追加
     }
 
+    // 後続技が指定された場合、カウンターヒットからの繋ぎ判定を追加
+    if !follow_ups.is_empty() {
+        let embed3 = CreateEmbed::new()
+            .color(EMBED_COLOR)
+            .description(counter_follow_up_text(move_info, follow_ups));
+        vec_embeds.push(embed3);
+    }
+
     vec_embeds
 }
 
+/// 後続技検索関数（見つからない場合は案内を送信）
+///
+/// # 引数
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `follow_ups` - "," 区切りの後続技指定
+/// * `ctx` - コマンドコンテキスト
+///
+/// # 戻り値
+/// 後続技の技情報一覧（入力順、見つからない技がある場合は None）
+async fn find_follow_ups(
+    character_arg_altered: &str,
+    follow_ups: &str,
+    ctx: &Context<'_>,
+) -> Result<Option<Vec<MoveInfo>>, AppError> {
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, character_arg_altered)?;
+    let queries: Vec<&str> = follow_ups
+        .split(',')
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .collect();
+    resolve::resolve_moves(ctx, character_arg_altered, &queries, &moves_info).await
+}
+
 /// 技の詳細情報を埋め込みメッセージで表示するコマンド
 ///
 /// # 引数
 /// * `ctx` - コマンドコンテキスト
 /// * `character` - キャラクター名または愛称
 /// * `character_move` - 技名、入力、またはエイリアス
+/// * `follow_ups` - カウンターヒットから繋がるか判定する後続技（"," 区切り）
 ///
 /// # 戻り値
 /// 処理結果 `Result<(), AppError>`
@@ -461,6 +228,13 @@ pub async fn advanced(
     #[rename = "move"]
     #[description = "技名、入力、またはエイリアス"]
     character_move: String,
+    #[rename = "counter_followups"]
+    #[description = "カウンターヒットから繋がるか判定する技（\",\" 区切り）"]
+    follow_ups: Option<String>,
+    #[description = "表示する項目（\",\" 区切り、例：startup,active,on_block）"] fields: Option<
+        String,
+    >,
+    #[description = "空の項目（-）を表示しない"] hide_empty: Option<bool>,
 ) -> Result<(), AppError> {
     // コマンド引数の表示　引数確認用
     println!(
@@ -483,6 +257,21 @@ pub async fn advanced(
         return Ok(());
     }
 
+    // 表示項目決定（コマンド引数 → サーバー設定 → 既定値）
+    let selection = match guild::resolve_selection(
+        &ctx.data().data_dir,
+        ctx.guild_id().map(|id| id.get()),
+        EmbedView::Advanced,
+        fields.as_deref(),
+        hide_empty,
+    ) {
+        Ok(selection) => selection,
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            return Ok(());
+        }
+    };
+
     // キャラクターデータ読み込み
     let Ok(character_arg_altered) = load_character_data(&character, &ctx).await else {
         return Ok(());
@@ -495,15 +284,37 @@ pub async fn advanced(
         return Ok(());
     };
 
+    // カウンターヒット後続技読み込み
+    let follow_ups = match follow_ups {
+        Some(follow_ups) => {
+            let Some(moves) = find_follow_ups(&character_arg_altered, &follow_ups, &ctx).await?
+            else {
+                return Ok(());
+            };
+            moves
+        }
+        None => Vec::new(),
+    };
+
+    // リモート利用不可画像はローカル複製へ置換
+    let mut local_images = image_cache::LocalImages::load(&ctx.data().data_dir);
+    let embed_image = local_images.url(&embed_image);
+
     // 埋め込みメッセージ作成
-    let vec_embeds = create_advanced_embeds(&move_info, &embed_image, &character_arg_altered);
+    let vec_embeds = create_advanced_embeds(
+        &move_info,
+        &embed_image,
+        &character_arg_altered,
+        &follow_ups,
+        &selection,
+    );
 
     // 返信メッセージ用オブジェクト生成　送信用オブジェクトの初期化
     let mut reply = poise::CreateReply::default();
     // 生成した埋め込みメッセージ群を返信オブジェクトに追加
     reply.embeds.extend(vec_embeds);
     // 返信メッセージの送信　Discordへ送信
-    ctx.send(reply).await?;
+    ctx.send(local_images.attach(reply).await).await?;
 
     // 正常終了の返却　処理完了
     Ok(())
@@ -513,72 +324,7 @@ pub async fn advanced(
 mod tests {
     use super::*;
     use crate::models::MoveInfo;
-    use crate::test_utils::{create_test_json_file, create_test_move_info};
-    use crate::ImageLinks;
-    use std::env;
-    use std::fs;
-    use std::path::PathBuf;
-    use tempfile::TempDir;
-
-    // テスト環境のセットアップ用ヘルパー関数
-    fn setup_test_environment() -> (TempDir, PathBuf) {
-        let temp_dir = TempDir::new().expect("一時ディレクトリの作成に失敗");
-        let temp_path = temp_dir.path().to_path_buf();
-
-        // テスト用ディレクトリ構造を作成
-        fs::create_dir_all(temp_path.join("data/Sol_Badguy"))
-            .expect("テスト用ディレクトリの作成に失敗");
-
-        // キャラクターJSONファイルの作成
-        let moves_info = create_test_move_info();
-        let json_content = serde_json::to_string(&moves_info).expect("JSONシリアライズに失敗");
-        create_test_json_file(
-            &temp_path.join("data/Sol_Badguy/Sol_Badguy.json"),
-            &json_content,
-        )
-        .expect("テストJSONファイルの作成に失敗");
-
-        // 画像JSONファイルの作成
-        let image_links = vec![
-            ImageLinks {
-                input: "5P".to_string(),
-                move_img: "http://example.com/5p.png".to_string(),
-                hitbox_img: vec!["http://example.com/5p_hitbox.png".to_string()],
-            },
-            ImageLinks {
-                input: "236K".to_string(),
-                move_img: "http://example.com/236k.png".to_string(),
-                hitbox_img: vec!["http://example.com/236k_hitbox.png".to_string()],
-            },
-        ];
-        let json_content = serde_json::to_string(&image_links).expect("JSONシリアライズに失敗");
-        create_test_json_file(
-            &temp_path.join("data/Sol_Badguy/images.json"),
-            &json_content,
-        )
-        .expect("画像JSONファイルの作成に失敗");
-
-        (temp_dir, temp_path)
-    }
-
-    // 一時的に作業ディレクトリを変更するためのヘルパー構造体
-    struct TempWorkingDir {
-        original_dir: PathBuf,
-    }
-
-    impl TempWorkingDir {
-        fn _new(path: &PathBuf) -> Self {
-            let original_dir = env::current_dir().expect("現在のディレクトリの取得に失敗");
-            env::set_current_dir(path).expect("ディレクトリの変更に失敗");
-            Self { original_dir }
-        }
-    }
-
-    impl Drop for TempWorkingDir {
-        fn drop(&mut self) {
-            env::set_current_dir(&self.original_dir).expect("元のディレクトリに戻れませんでした");
-        }
-    }
+    use crate::test_utils::create_test_move_info;
 
     #[test]
     fn test_create_advanced_embeds() {
@@ -612,11 +358,40 @@ mod tests {
         let character_name = "Sol_Badguy";
 
         // 関数を実行
-        let embeds = create_advanced_embeds(&move_info, embed_image, character_name);
+        let embeds = create_advanced_embeds(
+            &move_info,
+            embed_image,
+            character_name,
+            &[],
+            &FieldSelection::for_view(EmbedView::Advanced),
+        );
 
         // 結果の検証
         assert!(!embeds.is_empty());
         // 最低限、埋め込みが1つ以上生成されていることを確認
-        assert!(embeds.len() >= 1);
+        assert!(!embeds.is_empty());
+    }
+
+    #[test]
+    fn test_counter_follow_up_text() {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.input = "6H".to_string();
+        move_info.on_hit = "-4".to_string();
+        move_info.counter = "Large".to_string();
+        assert_eq!(embed::MoveField::CounterHit.value(&move_info), "+14 (+18)");
+
+        let mut fast = move_info.clone();
+        fast.input = "5K".to_string();
+        fast.startup = Some(5);
+        let mut slow = fast.clone();
+        slow.input = "2D".to_string();
+        slow.startup = Some(20);
+        let text = counter_follow_up_text(&move_info, &[fast, slow]);
+        assert!(text.contains("✅ `5K` (発生 5F)"));
+        assert!(text.contains("❌ `2D` (発生 20F)"));
+
+        move_info.on_hit = "KD".to_string();
+        assert_eq!(embed::MoveField::CounterHit.value(&move_info), "-");
+        assert!(counter_follow_up_text(&move_info, &[]).contains("不明"));
     }
 }
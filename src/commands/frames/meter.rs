@@ -4,227 +4,196 @@
 //! 開始・アクティブ・リカバリーフレーム情報処理
 //! コマンド実行機能
 
-use crate::{check, error::AppError, find, Context, ImageLinks, MoveInfo, EMBED_COLOR}; // 依存モジュール群
+use crate::common::embed; // 技情報埋め込み共通処理
+use crate::common::resolve; // 技指定の検索
+use crate::guild::GuildConfig;
+use crate::invuln::InvulnKind; // 無敵種別
+use crate::render::{self, meter::FrameCell}; // フレームメーター画像描画
+use crate::timeline::{Segment, SegmentKind, Sequence, Timeline, Transition}; // フレーム表記解析
+use crate::{check, error::AppError, find, utils, Context, MoveInfo, EMBED_COLOR}; // 依存モジュール群
 use colored::Colorize; // 文字色変換ライブラリ
-use poise::serenity_prelude::CreateEmbed; // 埋め込み作成ライブラリ
-use std::{fs, string::String}; // ファイル操作・文字列操作
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed}; // 埋め込み作成ライブラリ
 
 const GREEN_CIRCLE: &str = "🟢\u{200b}"; // 緑丸定数
 const RED_SQUARE: &str = "🟥\u{200b}"; // 赤四角定数
 const BLUE_DIAMOND: &str = "🔷\u{200b}"; // 青菱形定数
 
+/// フレームメーター画像の添付ファイル名
+const METER_FILE: &str = "meter.png";
+
+/// フレームメーター画像の凡例
+const METER_LEGEND: &str = "🟩 Startup　🟥 Active　⬛ No hitbox　🟦 Recovery";
+
+/// 連係のフレームメーター凡例（隙間の表示色）
+const SEQUENCE_LEGEND: &str = "🟤 Gap (opponent can act)";
+
+/// 連係指定の最大技数
+const MAX_SEQUENCE_MOVES: usize = 10;
+
 /// デフォルト画像URL
 const IMAGE_DEFAULT: &str = "https://www.dustloop.com/wiki/images/5/54/GGST_Logo_Sparkly.png";
 
-/// 指定ムーブの開始フレーム情報からシンボル文字列生成
-///
-/// # 引数
-/// * `move_info` - ムーブ情報構造体参照
-///
-/// # 戻り値
-/// 開始フレームシンボル文字列
-async fn startup_frames(move_info: &MoveInfo) -> String {
-    // Option<i32>の場合は文字列に変換
-    let startup_str = move_info.startup.map_or("-".to_string(), |v| v.to_string());
-    let startup_vec = sep_frame_vec(&startup_str).await; // 開始フレーム分割結果取得
-    let mut meter_msg = String::new(); // メーター文字列初期化
-                                       // println!("startup_vec: {:?}", startup_vec); // デバッグ出力用
-
-    // 単一エントリかつ空または "-"、または1フレームのみの場合
-    if (startup_vec.len() == 1 && startup_vec[0] == "-")
-        || (startup_vec.len() == 1 && startup_vec[0].parse::<u16>().unwrap() == 1)
-    {
-        meter_msg += "-"; // 単一フレーム表現
-    }
-    // 複数エントリの場合の処理
-    else {
-        let mut startup_bra = false; // 括弧有無判定フラグ初期化
-
-        // 各エントリ処理ループ
-        for x in 0..startup_vec.len() {
-            // 数字エントリの場合
-            if let Ok(num) = startup_vec[x].parse::<u16>() {
-                // 数値-1回分ループ処理
-                for _ in 0..num - 1 {
-                    if startup_bra {
-                        // 括弧内：前エントリとの差分回数緑丸追加
-                        for _ in 0..((startup_vec[x].parse::<u16>().unwrap())
-                            - (startup_vec[x - 2].parse::<u16>()).unwrap())
-                        {
-                            meter_msg += GREEN_CIRCLE; // 括弧内緑丸追加
-                        }
-                        break; // ループ中断
-                    }
-                    meter_msg += GREEN_CIRCLE; // 括弧前：緑丸追加
-                }
-            }
-            // 数字以外のエントリの場合
-            else {
-                // "+"記号処理（末尾直前の場合）
-                if x == startup_vec.len() - 2 && startup_vec[x] == "+" {
-                    if let Ok(num) = startup_vec[x + 1].parse::<u16>() {
-                        // 数字変換試行
-                        if num == 1 {
-                            meter_msg += GREEN_CIRCLE; // 単一数値：緑丸置換
-                        } else {
-                            meter_msg.push_str(GREEN_CIRCLE);
-                            meter_msg.push_str(&startup_vec[x]);
-                            // 複数数値：緑丸＋"+"追加
-                        }
-                    } else {
-                        meter_msg.push_str(&startup_vec[x]); // 数字変換失敗：記号そのまま追加
-                    }
-                }
-                // その他の記号処理
-                else {
-                    meter_msg.push_str(&startup_vec[x]); // 記号追加
-                }
-
-                // 括弧・チルダ判定更新
-                if startup_vec[x] == "[" || startup_vec[x] == "~" {
-                    startup_bra = true; // 括弧開始
-                } else if startup_vec[x] == "]" {
-                    startup_bra = false; // 括弧終了
-                }
-            }
+/// 区間フレーム数の表記補足生成関数（範囲・上限不明・終了条件）
+fn segment_suffix(segment: &Segment) -> String {
+    let mut suffix = String::new();
+    if !segment.frames.is_fixed() {
+        match segment.frames.max {
+            Some(max) => suffix += &format!("~{max}"),
+            None if segment.condition.is_none() => suffix += "+",
+            None => {}
         }
     }
-    meter_msg // シンボル文字列返却
+    if let Some(condition) = &segment.condition {
+        suffix += &format!("Until {condition}");
+    }
+    suffix
 }
 
-/// 指定ムーブのアクティブフレーム情報からシンボル文字列生成
+/// 開始フレームの絵文字表現生成関数
 ///
 /// # 引数
-/// * `move_info` - ムーブ情報構造体参照
+/// * `timeline` - 技のタイムライン
 ///
 /// # 戻り値
-/// アクティブフレームシンボル文字列
-async fn active_frames(move_info: &MoveInfo) -> String {
-    let active_vec = sep_frame_vec(&move_info.active).await; // アクティブフレーム分割結果取得
-    let mut meter_msg = String::new(); // メーター文字列初期化
-                                       // println!("Active vec: {:?}", active_vec); // デバッグ出力用
-
-    if active_vec.len() == 1 && active_vec[0] == "-" {
-        meter_msg += "-"; // 単一ハイフン表現
-    } else {
-        let mut hit_recovery = false; // 括弧有無判定フラグ初期化
-
-        // 各エントリ処理ループ（参照）
-        for active_vec_string in &active_vec {
-            if let Ok(num) = active_vec_string.parse::<u16>() {
-                // 数値エントリの場合、数値分ループ
-                for _ in 0..num {
-                    if hit_recovery {
-                        meter_msg += BLUE_DIAMOND; // 括弧内：青菱形追加
-                    } else {
-                        meter_msg += RED_SQUARE; // 括弧前：赤四角追加
-                    }
-                }
-            }
-            // 数値以外のエントリの場合
-            else {
-                meter_msg.push_str(active_vec_string); // 記号追加
-                if active_vec_string == "(" {
-                    hit_recovery = true; // 括弧開始
-                } else if active_vec_string == ")" {
-                    hit_recovery = false; // 括弧終了
-                }
-            }
-        }
+/// 開始フレームシンボル文字列（発生前フレームが無い場合は "-"）
+fn startup_frames(timeline: &Timeline) -> String {
+    let frames = timeline.startup_frames();
+    if frames == 0 {
+        return "-".to_string();
     }
-    meter_msg // シンボル文字列返却
+    GREEN_CIRCLE.repeat(frames as usize)
 }
 
-/// 指定ムーブのリカバリーフレーム情報からシンボル文字列生成
+/// アクティブフレームの絵文字表現生成関数
+/// 攻撃判定の無い区間は括弧内に青菱形で表す。
 ///
 /// # 引数
-/// * `move_info` - ムーブ情報構造体参照
+/// * `timeline` - 技のタイムライン
 ///
 /// # 戻り値
-/// リカバリーフレームシンボル文字列
-async fn recovery_frames(move_info: &MoveInfo) -> String {
-    // Option<i32>の場合は文字列に変換
-    let recovery_str = move_info
-        .recovery
-        .map_or("-".to_string(), |v| v.to_string());
-    let recovery_vec = sep_frame_vec(&recovery_str).await; // リカバリーフレーム分割結果取得
-    let mut meter_msg = String::new(); // メーター文字列初期化
-
-    if recovery_vec.len() == 1 && recovery_vec[0] == "-" {
-        meter_msg += "-"; // 単一ハイフン表現
-    } else {
-        let mut recovery_tilde = false; // チルダ有無判定フラグ初期化
-
-        // 各エントリ処理ループ（添字利用）
-        for x in 0..recovery_vec.len() {
-            if let Ok(num) = recovery_vec[x].parse::<u16>() {
-                // 数値エントリの場合、数値分ループ
-                for _ in 0..num {
-                    if recovery_tilde {
-                        // チルダ内：前エントリとの差分回数青菱形追加
-                        for _ in 0..((recovery_vec[x].parse::<u16>().unwrap())
-                            - (recovery_vec[x - 2].parse::<u16>()).unwrap())
-                        {
-                            meter_msg += BLUE_DIAMOND; // チルダ内青菱形追加
-                        }
-                        break; // ループ中断
-                    }
-                    meter_msg += BLUE_DIAMOND; // チルダ前：青菱形追加
-                }
-            }
-            // 数値以外のエントリの場合
-            else {
-                meter_msg.push_str(&recovery_vec[x]); // 記号追加
-                                                      // チルダ・括弧判定更新
-                recovery_tilde = recovery_vec[x] == "~" || recovery_vec[x] == "(";
+/// アクティブフレームシンボル文字列（持続情報が無い場合は "-"）
+fn active_frames(timeline: &Timeline) -> String {
+    if timeline.active.is_empty() {
+        return "-".to_string();
+    }
+    let mut meter_msg = String::new();
+    for segment in &timeline.active.segments {
+        let frames = segment.frames.min as usize;
+        match segment.kind {
+            SegmentKind::Hit => meter_msg += &RED_SQUARE.repeat(frames),
+            SegmentKind::Gap => {
+                meter_msg += "(";
+                meter_msg += &BLUE_DIAMOND.repeat(frames);
+                meter_msg += ")";
             }
         }
+        meter_msg += &segment_suffix(segment);
+    }
+    if let Some(landing) = timeline.active.landing {
+        meter_msg += "+";
+        meter_msg += &RED_SQUARE.repeat(landing as usize);
     }
-    meter_msg // シンボル文字列返却
+    meter_msg
 }
 
-/// 指定文字列を分割しセパレータを保持したベクター返却
+/// リカバリーフレームの絵文字表現生成関数
 ///
 /// # 引数
-/// * `text` - 分割対象文字列
+/// * `timeline` - 技のタイムライン
 ///
 /// # 戻り値
-/// 分割結果ベクター（空文字・"total"除去済）
-async fn sep_frame_vec(text: &str) -> Vec<String> {
-    let mut result = Vec::new(); // 分割結果格納ベクター初期化
-    let mut last = 0; // 前回インデックス保持用
-
-    // セパレータ（英数字以外）で分割処理
-    for (index, matched) in text.match_indices(|c: char| !(c.is_alphanumeric())) {
-        if last != index {
-            result.push(text[last..index].to_string()); // 文字列部分抽出
-        }
-        result.push(matched.to_string()); // セパレータ抽出
-        last = index + matched.len(); // インデックス更新
+/// リカバリーフレームシンボル文字列（硬直情報が無い場合は "-"）
+fn recovery_frames(timeline: &Timeline) -> String {
+    let frames = timeline.recovery.min_frames();
+    if frames == 0 {
+        return "-".to_string();
     }
-    if last < text.len() {
-        result.push(text[last..].to_string()); // 残余部分抽出
+    BLUE_DIAMOND.repeat(frames as usize)
+}
+
+/// 無敵種別の凡例絵文字を返す（メーター画像の帯色に対応）。
+fn invuln_emoji(kind: InvulnKind) -> &'static str {
+    match kind {
+        InvulnKind::Full => "⬜",
+        InvulnKind::Strike => "🟨",
+        InvulnKind::GuardPoint => "🟧",
+        InvulnKind::Armor => "🟫",
+        InvulnKind::Projectile => "🟪",
+        InvulnKind::Throw => "🩷",
+        InvulnKind::UpperBody | InvulnKind::LowProfile | InvulnKind::Airborne => "🩵",
+        InvulnKind::Other => "🩶",
     }
+}
 
-    // 空文字および "total" 削除処理（複数エントリの場合）
-    if result.len() > 1 {
-        'outer: loop {
-            let cur_it_len = result.len(); // 現在長さ保持
-
-            // 各エントリ検査ループ
-            for r in 0..result.len() - 1 {
-                if result[r].to_lowercase() == "total" || result[r].is_empty() || result[r] == " " {
-                    result.remove(r); // 不要項目削除
-                    break; // 削除後ループ再開
-                }
-            }
+/// 無敵範囲の一覧表記生成関数（例："🟨 Strike 1-9"）
+fn invuln_summary(timeline: &Timeline) -> Vec<String> {
+    timeline
+        .invuln
+        .iter()
+        .map(|range| {
+            let end = range
+                .end
+                .map_or_else(|| "…".to_string(), |end| end.to_string());
+            format!(
+                "{} {} {}-{end}",
+                invuln_emoji(range.kind),
+                range.kind.label(),
+                range.start
+            )
+        })
+        .collect()
+}
 
-            if cur_it_len == result.len() {
-                break 'outer; // 安定状態判定
-            }
+/// フレームメーター画像用のフレーム列生成関数
+///
+/// # 概要
+/// 連係（単独技を含む）のフレーム種別列に無敵種別を重ねる。
+///
+/// # 引数
+/// * `sequence` - 連係のタイムライン
+///
+/// # 戻り値
+/// 1 フレーム目から順のマス配列（フレーム情報が無い場合は空）
+fn meter_cells(sequence: &Sequence) -> Vec<FrameCell> {
+    sequence
+        .frames()
+        .into_iter()
+        .map(|(kind, invuln)| FrameCell { kind, invuln })
+        .collect()
+}
+
+/// フレームメーター画像の添付ファイル生成関数
+/// フレーム情報が無い場合・生成失敗時は None を返す（呼び出し側で絵文字表示）。
+fn meter_attachment(cells: &[FrameCell]) -> Option<CreateAttachment> {
+    if cells.is_empty() {
+        return None;
+    }
+    match render::encode_png(&render::meter::render_meter(cells)) {
+        Ok(bytes) => Some(CreateAttachment::bytes(bytes, METER_FILE)),
+        Err(err) => {
+            println!("{}", format!("Meter render failed: {err}").red());
+            None
         }
     }
-    result // 分割結果返却
+}
+
+/// 連係の繋ぎ説明生成関数（例："5K → 近S: Cancel, 2F gap"）
+fn step_summary(
+    from: &MoveInfo,
+    to: &MoveInfo,
+    transition: Transition,
+    gap: Option<i32>,
+) -> String {
+    let transition = match transition {
+        Transition::Cancel => "Cancel",
+        Transition::Link => "Link",
+    };
+    let gap = match gap {
+        Some(gap) if gap > 0 => format!("{gap}F gap"),
+        Some(_) => "no gap".to_string(),
+        None => "gap unknown".to_string(),
+    };
+    format!("`{}` → `{}`: {transition}, {gap}", from.input, to.input)
 }
 
 /// キャラクターデータを読み込む関数
@@ -237,7 +206,13 @@ async fn sep_frame_vec(text: &str) -> Vec<String> {
 /// 成功時は正式なキャラクター名、失敗時はエラー
 async fn load_character_data(character: &str, ctx: &Context<'_>) -> Result<String, AppError> {
     // キャラクター探索処理（エイリアス対応）
-    let character_arg_altered = match find::find_character(&character.to_string()).await {
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
         Ok(character_arg_altered) => character_arg_altered, // キャラクター名称確定
         Err(err) => {
             ctx.say(err.to_string()).await?; // エラーメッセージ送信
@@ -263,32 +238,16 @@ async fn load_move_data(
     character_move: &str,
     ctx: &Context<'_>,
 ) -> Result<(Vec<MoveInfo>, usize), AppError> {
-    // キャラクターJSONパス取得
-    let char_file_path =
-        "data/".to_owned() + character_arg_altered + "/" + character_arg_altered + ".json";
-
-    // ファイル読み込み
-    let char_file_data = fs::read_to_string(char_file_path.clone()).map_err(|_| {
-        AppError::FileNotFound(format!(
-            "Failed to read '{character_arg_altered}.json' file."
-        ))
-    })?;
-
-    // JSONデシリアライズ　技データ抽出
-    let moves_info: Vec<MoveInfo> =
-        serde_json::from_str(&char_file_data).map_err(AppError::Json)?;
-
-    // 読み込み成功表示
-    println!(
-        "{}",
-        ("Successfully read '".to_owned() + character_arg_altered + ".json' file.").green()
-    );
+    // 技データ読み込み
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, character_arg_altered)?;
 
     // 技インデックス検索
     let index = match find::find_move_index(
+        &ctx.data().data_dir,
         &character_arg_altered.to_string(),
         character_move.to_string(),
         &moves_info,
+        GuildConfig::for_context(ctx).as_ref(),
     )
     .await
     {
@@ -307,111 +266,24 @@ async fn load_move_data(
 /// 技情報から適切な画像URLを検索する
 ///
 /// # 引数
+/// * `data_dir` - データディレクトリ
 /// * `character_arg_altered` - 正式なキャラクター名
 /// * `selected_move_info` - 選択された技情報
 ///
 /// # 戻り値
 /// 画像のURL
-async fn find_move_image(
+fn find_move_image(
+    data_dir: &str,
     character_arg_altered: &str,
     selected_move_info: &MoveInfo,
 ) -> Result<String, AppError> {
-    // 画像JSONパス
-    let image_json_path = "data/".to_owned() + character_arg_altered + "/images.json";
-
-    // 画像JSONファイル読み込み
-    let image_links = fs::read_to_string(image_json_path).map_err(|_| {
-        AppError::FileNotFound(format!(
-            "Failed to read '{character_arg_altered}' images.json file."
-        ))
-    })?;
-
-    // 画像情報デシリアライズ
-    let image_links =
-        serde_json::from_str::<Vec<ImageLinks>>(&image_links).map_err(AppError::Json)?;
-
-    let mut embed_image = String::new(); // 埋め込み画像初期化
-
-    // 括弧を除去した技名を作成（例：「2d(2d)」→「2d」）
-    let cleaned_input = if selected_move_info.input.contains('(') {
-        selected_move_info
-            .input
-            .split('(')
-            .next()
-            .unwrap_or("")
-            .trim()
-            .to_string()
-    } else {
-        selected_move_info.input.to_string()
-    };
+    let image_links = utils::load_image_links(data_dir, character_arg_altered)?;
 
-    // 括弧内のコマンドを取得（例：「2d(2d)」→「2d」）
-    let bracket_content =
-        if selected_move_info.input.contains('(') && selected_move_info.input.contains(')') {
-            let start = selected_move_info.input.find('(').unwrap_or(0) + 1;
-            let end = selected_move_info
-                .input
-                .find(')')
-                .unwrap_or(selected_move_info.input.len());
-            if start < end {
-                selected_move_info.input[start..end].to_string()
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
-
-    // ムーブ画像送信処理
-    for img_links in &image_links {
-        // 完全一致、括弧を除去した技名との一致、または括弧内のコマンドとの一致
-        if selected_move_info.input.to_lowercase() == img_links.input.to_lowercase()
-            || (!cleaned_input.is_empty()
-                && cleaned_input.to_lowercase() == img_links.input.to_lowercase())
-            || (!bracket_content.is_empty()
-                && bracket_content.to_lowercase() == img_links.input.to_lowercase())
-        {
-            // ヒット判定
-            println!(
-                "{}",
-                ("Successfully read move '".to_owned()
-                    + &selected_move_info.input
-                    + "' in '"
-                    + character_arg_altered
-                    + ".json' file.")
-                    .green()
-            ); // 成功出力
-
-            embed_image = if img_links.move_img.is_empty() {
-                String::from(IMAGE_DEFAULT) // デフォルト画像設定
-            } else {
-                img_links.move_img.clone() // ムーブ画像設定
-            };
-            break; // 検索終了
-        }
-    }
-
-    // 画像が見つからなかった場合、部分一致で再検索
-    if embed_image.is_empty() {
-        for img_links in &image_links {
-            if img_links
-                .input
-                .to_lowercase()
-                .contains(&selected_move_info.input.to_lowercase())
-                && !img_links.move_img.is_empty()
-            {
-                embed_image = img_links.move_img.clone();
-                break;
-            }
-        }
-    }
-
-    // デフォルト画像がセットされていなかった場合
-    if embed_image.is_empty() {
-        embed_image = String::from(IMAGE_DEFAULT);
-    }
-
-    Ok(embed_image)
+    Ok(
+        find::find_move_image(&selected_move_info.input, &image_links)
+            .unwrap_or(IMAGE_DEFAULT)
+            .to_string(),
+    )
 }
 
 /// 技情報と画像を検索する関数
@@ -436,7 +308,11 @@ async fn find_move_and_images(
     let selected_move_info = moves_info[index].clone();
 
     // 画像の検索
-    let embed_image = find_move_image(character_arg_altered, &selected_move_info).await?;
+    let embed_image = find_move_image(
+        &ctx.data().data_dir,
+        character_arg_altered,
+        &selected_move_info,
+    )?;
 
     Ok((selected_move_info, embed_image))
 }
@@ -447,41 +323,194 @@ async fn find_move_and_images(
 /// * `move_info` - 技情報
 /// * `embed_image` - 埋め込む画像のURL
 /// * `character_arg_altered` - 正式なキャラクター名
+/// * `emoji` - 画像を使わず絵文字でメーターを表示するか否か
 ///
 /// # 戻り値
-/// 埋め込みメッセージのベクター
+/// 埋め込みメッセージのベクターとメーター画像の添付ファイル（絵文字表示時は None）
 async fn create_meter_embeds(
     move_info: &MoveInfo,
     embed_image: &str,
     character_arg_altered: &str,
-) -> Vec<CreateEmbed> {
-    // フレームメーター文字列生成処理
-    let mut meter_msg = String::from("`"); // バッククォート開始
-    meter_msg += &startup_frames(move_info).await; // 開始フレーム処理
-    meter_msg += &active_frames(move_info).await; // アクティブフレーム処理
-    meter_msg += &recovery_frames(move_info).await; // リカバリーフレーム処理
-    meter_msg += "`"; // バッククォート終了
-
-    let embed_title = format!("__**{}：{}**__", character_arg_altered, move_info.input); // 埋め込みタイトル生成
+    emoji: bool,
+) -> (Vec<CreateEmbed>, Option<CreateAttachment>) {
+    // フレーム表記解析（解析不能時は元表記をそのまま表示）
+    let (timeline, fields) = match Timeline::from_move(move_info) {
+        Ok(timeline) => {
+            let fields = [
+                startup_frames(&timeline),
+                active_frames(&timeline),
+                recovery_frames(&timeline),
+            ];
+            (timeline, fields)
+        }
+        Err(err) => {
+            println!("{}", format!("Error: {err}").red());
+            let fields = [
+                move_info.startup.map_or("-".to_string(), |v| v.to_string()),
+                move_info.active.clone(),
+                move_info
+                    .recovery
+                    .map_or("-".to_string(), |v| v.to_string()),
+            ];
+            (Timeline::default(), fields)
+        }
+    };
 
-    let embed_url = "https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview"; // 埋め込みURL生成
+    // フレームメーター文字列生成処理
+    let meter_msg = format!("`{}`", fields.concat());
 
-    let embed = CreateEmbed::new()
-        .color(EMBED_COLOR) // 埋め込み色設定
-        .title(embed_title) // タイトル設定
-        .url(embed_url) // URL設定
+    let [startup_field, active_field, recovery_field] = fields;
+    let embed = embed::move_embed_base(character_arg_altered, move_info) // 共通の色・タイトル・URL
         .fields(vec![
-            ("Startup", &startup_frames(move_info).await, true), // 開始フレームフィールド
-            ("Active", &active_frames(move_info).await, true),   // アクティブフレームフィールド
-            ("Recovery", &recovery_frames(move_info).await, true), // リカバリーフレームフィールド
+            (
+                "Startup",
+                utils::truncate_string(&startup_field, 1024),
+                true,
+            ), // 開始フレームフィールド
+            ("Active", utils::truncate_string(&active_field, 1024), true), // アクティブフレームフィールド
+            (
+                "Recovery",
+                utils::truncate_string(&recovery_field, 1024),
+                true,
+            ), // リカバリーフレームフィールド
         ])
         .image(embed_image); // 画像設定
 
+    // メーター画像生成（フレーム情報が無い・生成失敗時は絵文字表示）
+    let sequence = Sequence {
+        timelines: vec![timeline.clone()],
+        steps: Vec::new(),
+    };
+    if !emoji {
+        if let Some(attachment) = meter_attachment(&meter_cells(&sequence)) {
+            let active = &timeline.active;
+            let total = timeline.total_frames();
+            let mut description = match active.max_frames() {
+                Some(max) if max > active.min_frames() => format!(
+                    "{METER_LEGEND}\nTotal: {total}~{} frames",
+                    total + max - active.min_frames()
+                ),
+                _ => format!("{METER_LEGEND}\nTotal: {total} frames"),
+            };
+            if active.hits() > 1 {
+                description += &format!(" ({} hits)", active.hits());
+            }
+            // 条件付き・別表記のある持続は元表記を併記
+            if !active.conditions().is_empty()
+                || !active.variants.is_empty()
+                || !active.notes.is_empty()
+            {
+                description += &format!("\nActive: {}", move_info.active);
+            }
+            // 無敵範囲（画像上の帯色に対応）
+            let invuln = invuln_summary(&timeline);
+            if !invuln.is_empty() {
+                description += &format!("\nInvuln: {}", invuln.join("　"));
+            }
+            let embed2 = CreateEmbed::new()
+                .color(EMBED_COLOR)
+                .description(description)
+                .image(format!("attachment://{METER_FILE}"));
+            return (vec![embed, embed2], Some(attachment));
+        }
+    }
+
     let embed2 = CreateEmbed::new()
         .color(EMBED_COLOR) // 埋め込み色設定
-        .description(&meter_msg); // 説明文設定
+        .description(utils::truncate_string(&meter_msg, 4096)); // 説明文設定
 
-    vec![embed, embed2] // 埋め込みベクター作成
+    (vec![embed, embed2], None) // 埋め込みベクター作成
+}
+
+/// 連係フレームメーター表示用の埋め込みメッセージを作成する関数
+///
+/// # 概要
+/// 各技のフレームを繋ぎ方（キャンセル・目押し）に従って連結し、技間のガード時の隙間を併記する。
+///
+/// # 引数
+/// * `moves` - 技情報一覧（入力順）
+/// * `embed_image` - 埋め込む画像のURL（先頭の技）
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `emoji` - 画像を使わず絵文字でメーターを表示するか否か
+///
+/// # 戻り値
+/// 埋め込みメッセージのベクターとメーター画像の添付ファイル（絵文字表示時は None）
+fn create_sequence_embeds(
+    moves: &[MoveInfo],
+    embed_image: &str,
+    character_arg_altered: &str,
+    emoji: bool,
+) -> Result<(Vec<CreateEmbed>, Option<CreateAttachment>), AppError> {
+    let sequence = Sequence::from_moves(moves)?;
+
+    let inputs: Vec<&str> = moves.iter().map(|m| m.input.as_str()).collect();
+    let fields: Vec<(String, String, bool)> = moves
+        .iter()
+        .map(|m| {
+            let frames = format!(
+                "{} / {} / {}",
+                m.startup.map_or("-".to_string(), |v| v.to_string()),
+                m.active,
+                m.recovery.map_or("-".to_string(), |v| v.to_string())
+            );
+            (m.input.clone(), frames, true)
+        })
+        .collect();
+    let embed = embed::move_embed_base(character_arg_altered, &moves[0])
+        .title(format!(
+            "__**{}：{}**__",
+            character_arg_altered,
+            inputs.join(" > ")
+        ))
+        .fields(fields)
+        .image(embed_image);
+
+    // 技間の繋ぎと隙間
+    let steps: Vec<String> = moves
+        .windows(2)
+        .zip(&sequence.steps)
+        .map(|(pair, step)| step_summary(&pair[0], &pair[1], step.transition, step.gap))
+        .collect();
+
+    let cells = meter_cells(&sequence);
+    if !emoji {
+        if let Some(attachment) = meter_attachment(&cells) {
+            let description = format!(
+                "{METER_LEGEND}　{SEQUENCE_LEGEND}\nTotal: {} frames\n{}",
+                cells.len(),
+                steps.join("\n")
+            );
+            let embed2 = CreateEmbed::new()
+                .color(EMBED_COLOR)
+                .description(description)
+                .image(format!("attachment://{METER_FILE}"));
+            return Ok((vec![embed, embed2], Some(attachment)));
+        }
+    }
+
+    // 絵文字表示（キャンセルで繋ぐ技は硬直を省略）
+    let meter_msg: Vec<String> = sequence
+        .timelines
+        .iter()
+        .enumerate()
+        .map(|(i, timeline)| {
+            let mut msg = startup_frames(timeline) + &active_frames(timeline);
+            if sequence
+                .steps
+                .get(i)
+                .is_none_or(|step| step.transition == Transition::Link)
+            {
+                msg += &recovery_frames(timeline);
+            }
+            msg
+        })
+        .collect();
+    let description = format!("`{}`\n{}", meter_msg.join(" > "), steps.join("\n"));
+    let embed2 = CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .description(utils::truncate_string(&description, 4096));
+
+    Ok((vec![embed, embed2], None))
 }
 
 /// ムーブのフレームメーターを視覚表示するコマンド処理
@@ -489,7 +518,7 @@ async fn create_meter_embeds(
 /// # 引数
 /// * `ctx` - コマンド実行コンテキスト
 /// * `character` - キャラクター名またはニックネーム
-/// * `character_move` - ムーブ名・入力またはエイリアス
+/// * `character_move` - ムーブ名・入力またはエイリアス（"5K > 近S > 2HS" のように ">" 区切りで連係指定可）
 ///
 /// # 戻り値
 /// 処理結果 `Result<(), AppError>`
@@ -501,8 +530,9 @@ pub async fn meter(
     character: String, // キャラクター指定文字列
     #[min_length = 2]
     #[rename = "move"]
-    #[description = "Move name, input or alias."]
+    #[description = "Move name, input or alias. Chain moves with '>' (e.g. 5K > c.S > 2H)."]
     character_move: String, // ムーブ指定文字列
+    #[description = "Show the emoji meter instead of an image."] emoji: Option<bool>, // 絵文字表示指定
 ) -> Result<(), AppError> {
     println!(
         "{}",
@@ -528,6 +558,44 @@ pub async fn meter(
         return Ok(());
     };
 
+    // 連係指定（"A > B > C"）
+    let parts = utils::split_sequence(&character_move);
+    if parts.len() > 1 {
+        if parts.len() > MAX_SEQUENCE_MOVES {
+            ctx.say(format!(
+                "A sequence can contain up to {MAX_SEQUENCE_MOVES} moves."
+            ))
+            .await?;
+            return Ok(());
+        }
+        let moves_info = utils::load_move_info(&ctx.data().data_dir, &character_arg_altered)?;
+        let Some(moves) =
+            resolve::resolve_moves(&ctx, &character_arg_altered, &parts, &moves_info).await?
+        else {
+            return Ok(());
+        };
+        let embed_image = find_move_image(&ctx.data().data_dir, &character_arg_altered, &moves[0])?;
+        let (vec_embeds, meter_image) = match create_sequence_embeds(
+            &moves,
+            &embed_image,
+            &character_arg_altered,
+            emoji.unwrap_or(false),
+        ) {
+            Ok(result) => result,
+            Err(err) => {
+                ctx.say(err.to_string()).await?;
+                return Ok(());
+            }
+        };
+        let mut reply = poise::CreateReply::default();
+        reply.embeds.extend(vec_embeds);
+        if let Some(attachment) = meter_image {
+            reply = reply.attachment(attachment);
+        }
+        ctx.send(reply).await?;
+        return Ok(());
+    }
+
     // 技情報と画像データ読み込み
     let Ok((selected_move_info, embed_image)) =
         find_move_and_images(&character_arg_altered, &character_move, &ctx, &character).await
@@ -536,13 +604,126 @@ pub async fn meter(
     };
 
     // 埋め込みメッセージ作成
-    let vec_embeds =
-        create_meter_embeds(&selected_move_info, &embed_image, &character_arg_altered).await;
+    let (vec_embeds, meter_image) = create_meter_embeds(
+        &selected_move_info,
+        &embed_image,
+        &character_arg_altered,
+        emoji.unwrap_or(false),
+    )
+    .await;
 
     // 返信作成と送信
     let mut reply = poise::CreateReply::default(); // 返信オブジェクト初期化
     reply.embeds.extend(vec_embeds); // 埋め込み追加
+    if let Some(attachment) = meter_image {
+        reply = reply.attachment(attachment); // メーター画像添付
+    }
     ctx.send(reply).await?; // 返信送信
 
     Ok(()) // 正常終了
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+    use crate::timeline::FrameKind;
+
+    #[test]
+    fn test_sequence_legend_unique() {
+        let gap = SEQUENCE_LEGEND.split(' ').next().unwrap();
+        assert!(!METER_LEGEND.contains(gap));
+        for kind in [
+            InvulnKind::Full,
+            InvulnKind::Strike,
+            InvulnKind::GuardPoint,
+            InvulnKind::Armor,
+            InvulnKind::Projectile,
+            InvulnKind::Throw,
+            InvulnKind::UpperBody,
+            InvulnKind::LowProfile,
+            InvulnKind::Airborne,
+            InvulnKind::Other,
+        ] {
+            assert_ne!(invuln_emoji(kind), gap);
+        }
+    }
+
+    #[test]
+    fn test_emoji_frames() {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.startup = Some(3);
+        move_info.active = "1(2)1".to_string();
+        move_info.recovery = None;
+        let timeline = Timeline::from_move(&move_info).unwrap();
+
+        assert_eq!(startup_frames(&timeline), GREEN_CIRCLE.repeat(2));
+        assert_eq!(
+            active_frames(&timeline),
+            format!("{RED_SQUARE}({}){RED_SQUARE}", BLUE_DIAMOND.repeat(2))
+        );
+        assert_eq!(recovery_frames(&timeline), "-");
+
+        move_info.active = "Until Landing".to_string();
+        let timeline = Timeline::from_move(&move_info).unwrap();
+        assert_eq!(active_frames(&timeline), "Until Landing");
+    }
+
+    #[test]
+    fn test_invuln_summary() {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.invincibility = "1-9 Strike, 18F onwards Airborne".to_string();
+        let timeline = Timeline::from_move(&move_info).unwrap();
+        assert_eq!(
+            invuln_summary(&timeline),
+            vec!["🟨 Strike 1-9", "🩵 Airborne 18-…"]
+        );
+    }
+
+    #[test]
+    fn test_meter_cells() {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.startup = Some(4);
+        move_info.active = "2(1)1".to_string();
+        move_info.recovery = Some(3);
+        move_info.invincibility = "1-2 Strike".to_string();
+
+        let sequence = Sequence::from_moves(std::slice::from_ref(&move_info)).unwrap();
+        let kinds: Vec<FrameKind> = meter_cells(&sequence).iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                vec![FrameKind::Startup; 3],
+                vec![FrameKind::Active; 2],
+                vec![FrameKind::Gap],
+                vec![FrameKind::Active],
+                vec![FrameKind::Recovery; 3],
+            ]
+            .concat()
+        );
+        let cells = meter_cells(&sequence);
+        assert_eq!(cells[0].invuln, Some(InvulnKind::Strike));
+        assert_eq!(cells[1].invuln, Some(InvulnKind::Strike));
+        assert_eq!(cells[2].invuln, None);
+    }
+
+    #[test]
+    fn test_step_summary() {
+        let mut from = create_test_move_info().remove(0);
+        from.input = "5K".to_string();
+        let mut to = from.clone();
+        to.input = "近S".to_string();
+        assert_eq!(
+            step_summary(&from, &to, Transition::Cancel, Some(2)),
+            "`5K` → `近S`: Cancel, 2F gap"
+        );
+        assert_eq!(
+            step_summary(&from, &to, Transition::Link, Some(-1)),
+            "`5K` → `近S`: Link, no gap"
+        );
+        assert_eq!(
+            step_summary(&from, &to, Transition::Link, None),
+            "`5K` → `近S`: Link, gap unknown"
+        );
+    }
+}
@@ -12,10 +12,11 @@
 //! # 注意
 //! コマンド実行前に必要なデータファイル（dataフォルダ内のJSONファイル）が存在していること。
 
-use crate::{check, error::AppError, find, Context, ImageLinks, MoveInfo, EMBED_COLOR};
+use crate::common::embed::{self, EmbedView};
+use crate::guild::GuildConfig;
+use crate::{check, error::AppError, find, guild, image_cache, utils, Context, MoveInfo};
 use colored::Colorize;
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
-use std::{fs, string::String};
+use std::string::String;
 
 /// デフォルト画像URL
 const IMAGE_DEFAULT: &str = "https://www.dustloop.com/wiki/images/5/54/GGST_Logo_Sparkly.png";
@@ -30,7 +31,13 @@ const IMAGE_DEFAULT: &str = "https://www.dustloop.com/wiki/images/5/54/GGST_Logo
 /// 成功時は正式なキャラクター名、失敗時はエラー
 async fn load_character_data(character: &str, ctx: &Context<'_>) -> Result<String, AppError> {
     // キャラクター検索　完全名取得
-    let character_arg_altered = match find::find_character(&character.to_string()).await {
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
         Ok(name) => name, // キャラクター正式名取得
         Err(err) => {
             // エラー表示　メッセージ送信
@@ -43,129 +50,6 @@ async fn load_character_data(character: &str, ctx: &Context<'_>) -> Result<Strin
     Ok(character_arg_altered)
 }
 
-/// 入力文字列から括弧を除去した技名と括弧内のコンテンツを抽出する
-///
-/// # 引数
-/// * `input` - 入力文字列（例：「2HS(2HS)」）
-///
-/// # 戻り値
-/// (括弧を除去した技名, 括弧内のコンテンツ) のタプル
-fn extract_move_name_parts(input: &str) -> (String, String) {
-    // 括弧を除去した技名を作成（例：「2HS(2HS)」→「2HS」）
-    let cleaned_input = if input.contains('(') {
-        input.split('(').next().unwrap_or("").trim().to_string()
-    } else {
-        input.to_string()
-    };
-
-    // 括弧内のコマンドを取得（例：「2HS(2HS)」→「2HS」）
-    let bracket_content = if input.contains('(') && input.contains(')') {
-        let start = input.find('(').unwrap_or(0) + 1;
-        let end = input.find(')').unwrap_or(input.len());
-        if start < end {
-            input[start..end].to_string()
-        } else {
-            String::new()
-        }
-    } else {
-        String::new()
-    };
-
-    (cleaned_input, bracket_content)
-}
-
-/// 技情報と画像リンクを照合して適切な画像URLを見つける
-///
-/// # 引数
-/// * `move_data` - 技情報
-/// * `image_links` - 画像リンク情報の配列
-/// * `default_image` - デフォルト画像URL
-///
-/// # 戻り値
-/// 画像のURL（見つからない場合はデフォルト画像）
-fn find_matching_image(
-    move_data: &MoveInfo,
-    image_links: &[ImageLinks],
-    default_image: &str,
-) -> String {
-    let mut embed_image = default_image.to_string();
-
-    // 括弧除去と括弧内コンテンツの抽出
-    let (cleaned_input, bracket_content) = extract_move_name_parts(&move_data.input);
-
-    println!(
-        "デバッグ - 検索する技: '{}'（大文字小文字変換: '{}'）",
-        move_data.input,
-        move_data.input.to_lowercase()
-    );
-    println!("デバッグ - 括弧除去後の技名: '{cleaned_input}', 括弧内容: '{bracket_content}'");
-    println!("デバッグ - 画像リンク配列の要素数: {}", image_links.len());
-
-    for (i, img_links) in image_links.iter().enumerate() {
-        println!(
-            "デバッグ - [{}]: img_links.input='{}' (小文字: '{}'), img_links.move_img='{}'",
-            i,
-            img_links.input,
-            img_links.input.to_lowercase(),
-            img_links.move_img
-        );
-
-        // 元の入力との完全一致
-        if move_data.input.to_lowercase() == img_links.input.to_lowercase()
-            && !img_links.move_img.is_empty()
-        {
-            println!(
-                "デバッグ - 完全一致！ 入力: '{}' == '{}'",
-                move_data.input.to_lowercase(),
-                img_links.input.to_lowercase()
-            );
-            embed_image = img_links.move_img.to_string(); // 画像リンク更新
-            break; // ループ抜け
-        }
-        // 括弧を除去した技名との一致
-        else if !cleaned_input.is_empty()
-            && cleaned_input.to_lowercase() == img_links.input.to_lowercase()
-            && !img_links.move_img.is_empty()
-        {
-            println!(
-                "デバッグ - 括弧除去後の一致！ 除去後: '{}' == '{}'",
-                cleaned_input.to_lowercase(),
-                img_links.input.to_lowercase()
-            );
-            embed_image = img_links.move_img.to_string(); // 画像リンク更新
-            break; // ループ抜け
-        }
-        // 括弧内のコマンドとの一致
-        else if !bracket_content.is_empty()
-            && bracket_content.to_lowercase() == img_links.input.to_lowercase()
-            && !img_links.move_img.is_empty()
-        {
-            println!(
-                "デバッグ - 括弧内コマンド一致！ 括弧内: '{}' == '{}'",
-                bracket_content.to_lowercase(),
-                img_links.input.to_lowercase()
-            );
-            embed_image = img_links.move_img.to_string(); // 画像リンク更新
-            break; // ループ抜け
-        }
-        // 部分一致の場合 - 上記のいずれにも該当しない場合のバックアップ
-        else if img_links
-            .input
-            .to_lowercase()
-            .contains(&move_data.input.to_lowercase())
-            && !img_links.move_img.is_empty()
-        {
-            println!(
-                "デバッグ - 部分一致！ JSON入力: '{}', 技入力: '{}'",
-                img_links.input, move_data.input
-            );
-            embed_image = img_links.move_img.to_string(); // 画像リンク更新
-        }
-    }
-
-    embed_image
-}
-
 /// 技情報と画像データを読み込む関数
 ///
 /// # 引数
@@ -180,15 +64,8 @@ async fn find_move_data(
     character_move: &str,
     ctx: &Context<'_>,
 ) -> Result<(MoveInfo, String), AppError> {
-    // JSONファイルパス組み立て　対象キャラクターのデータファイル
-    let char_file_path =
-        "data/".to_owned() + character_arg_altered + "/" + character_arg_altered + ".json";
-    // JSONファイル読み込み　ファイル内容取得
-    let char_file_data = fs::read_to_string(&char_file_path)
-        .expect(&("\nFailed to read '".to_owned() + character_arg_altered + ".json" + "' file."));
-
-    // JSONデータデシリアライズ　技データ配列取得
-    let moves_info = serde_json::from_str::<Vec<MoveInfo>>(&char_file_data).unwrap();
+    // 技情報読み込み　データディレクトリ基準
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, character_arg_altered)?;
 
     // 読み込み成功表示
     println!(
@@ -198,9 +75,11 @@ async fn find_move_data(
 
     // 技インデックス検索　指定技の位置特定
     let index = match find::find_move_index(
+        &ctx.data().data_dir,
         &character_arg_altered.to_string(),
         character_move.to_string(),
         &moves_info,
+        GuildConfig::for_context(ctx).as_ref(),
     )
     .await
     {
@@ -214,16 +93,8 @@ async fn find_move_data(
         }
     };
 
-    // 画像JSONファイル読み込み　対象キャラクターの画像データ取得
-    let image_links = fs::read_to_string(
-        "data/".to_owned() + character_arg_altered + "/images.json",
-    )
-    .expect(
-        &("\nFailed to read 'data/".to_owned() + character_arg_altered + "'/images.json' file."),
-    );
-
-    // 画像データデシリアライズ　画像リンク配列取得
-    let image_links = serde_json::from_str::<Vec<ImageLinks>>(&image_links).unwrap();
+    // 画像リンク読み込み　データディレクトリ基準
+    let image_links = utils::load_image_links(&ctx.data().data_dir, character_arg_altered)?;
     // 対象技情報取得
     let move_data = moves_info[index].clone();
 
@@ -239,67 +110,13 @@ async fn find_move_data(
     );
 
     // 画像マッチング処理
-    let embed_image = find_matching_image(&move_data, &image_links, IMAGE_DEFAULT);
+    let embed_image = find::find_move_image(&move_data.input, &image_links)
+        .unwrap_or(IMAGE_DEFAULT)
+        .to_string();
 
     Ok((move_data, embed_image))
 }
 
-/// 技情報の埋め込みメッセージを作成する関数
-///
-/// # 引数
-/// * `move_data` - 技情報
-/// * `embed_image` - 埋め込む画像のURL
-/// * `character_arg_altered` - 正式なキャラクター名
-///
-/// # 戻り値
-/// 埋め込みメッセージ
-fn create_move_embed(
-    move_data: &MoveInfo,
-    embed_image: &str,
-    character_arg_altered: &str,
-) -> CreateEmbed {
-    // 埋め込みタイトル組み立て　キャラクター名と技情報を連結
-    let embed_title = format!("__**{}：{}**__", character_arg_altered, move_data.input);
-
-    // 埋め込みURL組み立て　Dustloop Wiki の対象キャラクターページ
-    let embed_url = "https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview";
-    // 埋め込みフッター作成　キャプション利用
-    let embed_footer = CreateEmbedFooter::new(&move_data.caption);
-
-    // 埋め込みメッセージ作成　各種フィールド追加
-    CreateEmbed::new()
-        .color(EMBED_COLOR) // 埋め込みカラー設定
-        .title(embed_title) // タイトル設定
-        .url(embed_url) // URL設定
-        .image(embed_image) // 画像設定
-        .fields(vec![
-            (
-                "ダメージ",
-                &move_data.damage.map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            ("ガード", &move_data.guard, true),
-            ("無敵", &move_data.invincibility, true),
-            (
-                "始動",
-                &move_data.startup.map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            ("持続", &move_data.active, true),
-            (
-                "硬直",
-                &move_data
-                    .recovery
-                    .map_or("-".to_string(), |v| v.to_string()),
-                true,
-            ),
-            ("ヒット時", &move_data.on_hit, true),
-            ("ガード時", &move_data.on_block, true),
-            ("カウンター", &move_data.counter.to_string(), true),
-        ])
-        .footer(embed_footer) // フッター設定
-}
-
 /// キャラクターの技情報を埋め込み表示する指定
 #[poise::command(prefix_command, slash_command)]
 pub async fn simple(
@@ -311,6 +128,10 @@ pub async fn simple(
     #[rename = "move"]
     #[description = "技名、入力、またはエイリアス"]
     character_move: String,
+    #[description = "表示する項目（\",\" 区切り、例：startup,active,on_block）"] fields: Option<
+        String,
+    >,
+    #[description = "空の項目（-）を表示しない"] hide_empty: Option<bool>,
 ) -> Result<(), AppError> {
     // コマンド引数表示
     println!(
@@ -333,6 +154,21 @@ pub async fn simple(
         return Ok(());
     }
 
+    // 表示項目決定（コマンド引数 → サーバー設定 → 既定値）
+    let selection = match guild::resolve_selection(
+        &ctx.data().data_dir,
+        ctx.guild_id().map(|id| id.get()),
+        EmbedView::Simple,
+        fields.as_deref(),
+        hide_empty,
+    ) {
+        Ok(selection) => selection,
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            return Ok(());
+        }
+    };
+
     // キャラクターデータ読み込み
     let Ok(character_arg_altered) = load_character_data(&character, &ctx).await else {
         return Ok(());
@@ -345,11 +181,19 @@ pub async fn simple(
         return Ok(());
     };
 
+    // リモート利用不可画像はローカル複製へ置換
+    let mut local_images = image_cache::LocalImages::load(&ctx.data().data_dir);
+    let embed_image = local_images.url(&embed_image);
+
     // 埋め込みメッセージ作成
-    let embed = create_move_embed(&move_data, &embed_image, &character_arg_altered);
+    let embed =
+        embed::create_move_embed(&character_arg_altered, &move_data, &embed_image, &selection);
 
     // 埋め込みメッセージ送信　Discordへ出力
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    let reply = local_images
+        .attach(poise::CreateReply::default().embed(embed))
+        .await;
+    ctx.send(reply).await?;
 
     // 新バージョン通知（コメントアウト）
     // ctx.channel_id().say(ctx, r"[__**Patch.**__](<https://github.com/yakiimoninja/baiken/releases>)").await?;
@@ -0,0 +1,210 @@
+//! # gap.rs
+//!
+//! 技間の隙間判定コマンドモジュール  
+//! Discordコマンド /gap 実装モジュール  
+//! 同一キャラクターの 2 技について、ガード時の隙間・連続ガード可否・割り込める共通行動を表示する処理を提供
+
+use crate::calc::gap::{self, GapVerdict};
+use crate::common::resolve;
+use crate::guild::GuildConfig;
+use crate::timeline::Transition;
+use crate::{check, error::AppError, find, utils, Context, MoveInfo, EMBED_COLOR};
+use colored::Colorize;
+use poise::serenity_prelude::CreateEmbed;
+
+/// 隙間判定結果の埋め込みメッセージ作成関数
+///
+/// # 引数
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `first` - 先に出す技
+/// * `second` - 次に出す技
+/// * `report` - 隙間の解析結果
+///
+/// # 戻り値
+/// 埋め込みメッセージ
+fn create_gap_embed(
+    character_arg_altered: &str,
+    first: &MoveInfo,
+    second: &MoveInfo,
+    report: &gap::GapReport,
+) -> CreateEmbed {
+    let transition = match report.transition {
+        Transition::Cancel => "Cancel",
+        Transition::Link => "Link",
+    };
+    let beaten_by = if report.beaten_by.is_empty() {
+        "Nothing (block or Faultless Defense only)".to_string()
+    } else {
+        report
+            .beaten_by
+            .iter()
+            .map(|option| format!("{} ({})", option.name, option.note))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let mut description = format!("**{}**", report.verdict.label());
+    if report.verdict != GapVerdict::TrueBlockstring {
+        description += &format!(
+            "\n`{}` comes out {}F after the opponent can act.",
+            second.input, report.gap
+        );
+    }
+    // 直前ガードや FD の押し戻しで隙間・間合いは変化する
+    description += &format!(
+        "\nFaultless Defense on `{}` adds pushback and may make `{}` whiff.",
+        first.input, second.input
+    );
+
+    CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .title(format!(
+            "__**{}：{} → {}**__",
+            character_arg_altered, first.input, second.input
+        ))
+        .url("https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview")
+        .description(description)
+        .fields(vec![
+            (
+                format!("{} on block", first.input),
+                first.on_block.clone(),
+                true,
+            ),
+            (
+                format!("{} startup", second.input),
+                second
+                    .startup
+                    .map_or("-".to_string(), |startup| startup.to_string()),
+                true,
+            ),
+            ("Connection".to_string(), transition.to_string(), true),
+            ("Gap".to_string(), format!("{}F", report.gap), true),
+            ("Beaten by".to_string(), beaten_by, false),
+        ])
+}
+
+/// 技間の隙間を判定するコマンド処理
+///
+/// # 概要
+/// 先の技のガード硬直差と次の技の発生から隙間を計算し、
+/// 連続ガード・フレームトラップ・切り返し可能な隙間のいずれかと、割り込める共通行動を表示する。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `first_move` - 先に出す技の名前・入力またはエイリアス
+/// * `second_move` - 次に出す技の名前・入力またはエイリアス
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn gap(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[min_length = 2]
+    #[rename = "move1"]
+    #[description = "First move name, input or alias."]
+    first_move: String,
+    #[min_length = 2]
+    #[rename = "move2"]
+    #[description = "Second move name, input or alias."]
+    second_move: String,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned()
+            + &character
+            + ", "
+            + &first_move
+            + ", "
+            + &second_move
+            + "'")
+            .purple()
+    );
+
+    if (check::adaptive_check(
+        ctx,
+        check::CheckOptions::DATA_FOLDER
+            | check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
+    )
+    .await)
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    // キャラクター検索
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        &character,
+        GuildConfig::for_context(&ctx).as_ref(),
+    )
+    .await
+    {
+        Ok(character_arg_altered) => character_arg_altered,
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+            return Ok(());
+        }
+    };
+
+    // 技データ読み込み
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, &character_arg_altered)?;
+
+    let Some(moves) = resolve::resolve_moves(
+        &ctx,
+        &character_arg_altered,
+        &[&first_move, &second_move],
+        &moves_info,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+    let (first, second) = (&moves[0], &moves[1]);
+
+    match gap::analyze(first, second) {
+        Ok(report) => {
+            let embed = create_gap_embed(&character_arg_altered, first, second, &report);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        }
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+
+    #[test]
+    fn test_create_gap_embed() {
+        let mut first = create_test_move_info().remove(0);
+        first.input = "5K".to_string();
+        first.on_block = "-2".to_string();
+        let mut second = first.clone();
+        second.input = "6P".to_string();
+        second.startup = Some(9);
+        let report = gap::GapReport {
+            transition: Transition::Link,
+            gap: 10,
+            verdict: GapVerdict::classify(10),
+            beaten_by: gap::beating_options(10),
+        };
+        let embed =
+            serde_json::to_value(create_gap_embed("Sol_Badguy", &first, &second, &report)).unwrap();
+        assert_eq!(embed["title"], "__**Sol_Badguy：5K → 6P**__");
+        assert!(embed["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("**Reversal window (10F gap)**"));
+        assert_eq!(embed["fields"][3]["value"], "10F");
+    }
+}
@@ -0,0 +1,291 @@
+//! # gauge.rs
+//!
+//! R.I.S.C.・壁耐久計算コマンドモジュール  
+//! Discordコマンド /gauge 実装モジュール  
+//! ガード連係で溜まる R.I.S.C. レベル（risc サブコマンド）と、コンボの壁ダメージ・壁破壊判定（wall サブコマンド）を表示する処理を提供
+
+use crate::calc::gauge::{self, GaugeResult};
+use crate::common::resolve;
+use crate::guild::GuildConfig;
+use crate::{check, error::AppError, find, utils, Context, MoveInfo, EMBED_COLOR};
+use colored::Colorize;
+use poise::serenity_prelude::CreateEmbed;
+
+/// 連係・コンボの最大技数
+const MAX_GAUGE_MOVES: usize = 20;
+
+/// 内訳表の技入力の最大表示文字数
+const INPUT_WIDTH: usize = 12;
+
+/// 内訳表生成関数（コードブロック）
+fn breakdown_table(result: &GaugeResult) -> String {
+    let mut table = format!(
+        "```\n{:>2} {:<INPUT_WIDTH$} {:>6} {:>6}\n",
+        "#", "Move", "Amount", "Gauge"
+    );
+    for (i, step) in result.steps.iter().enumerate() {
+        let input: String = step.input.chars().take(INPUT_WIDTH).collect();
+        table += &format!(
+            "{:>2} {:<INPUT_WIDTH$} {:>6} {:>5.1}%\n",
+            i + 1,
+            input,
+            step.amount,
+            step.percent
+        );
+    }
+    table + "```"
+}
+
+/// 計算結果の埋め込みメッセージ作成関数
+///
+/// # 引数
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `heading` - 結果の要約（1 行目）
+/// * `result` - 計算結果
+///
+/// # 戻り値
+/// 埋め込みメッセージ
+fn create_gauge_embed(
+    character_arg_altered: &str,
+    heading: &str,
+    result: &GaugeResult,
+) -> CreateEmbed {
+    let inputs: Vec<&str> = result
+        .steps
+        .iter()
+        .map(|step| step.input.as_str())
+        .collect();
+    let mut description = heading.to_string() + "\n";
+    if !result.skipped.is_empty() {
+        description += &format!("No data: {}\n", result.skipped.join(", "));
+    }
+    description += &breakdown_table(result);
+
+    CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .title(format!(
+            "__**{}：{}**__",
+            character_arg_altered,
+            utils::truncate_string(&inputs.join(" > "), 200)
+        ))
+        .url("https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview")
+        .description(utils::truncate_string(&description, 4096))
+}
+
+/// R.I.S.C. 蓄積結果の要約生成関数
+fn risc_heading(result: &GaugeResult) -> String {
+    let mut heading = format!(
+        "**R.I.S.C. {:.1}% → {:.1}%** (+{})",
+        result.start,
+        result.end,
+        result.total()
+    );
+    if let Some(index) = result.full_at {
+        heading += &format!(
+            "\nR.I.S.C. is full after `{}`: the next hit is a counter hit.",
+            result.steps[index].input
+        );
+    }
+    heading
+}
+
+/// 壁ダメージ蓄積結果の要約生成関数
+fn wall_heading(result: &GaugeResult, risc_loss: f64) -> String {
+    let mut heading = format!(
+        "**Wall {:.1}% → {:.1}%** (+{} / {})",
+        result.start,
+        result.end,
+        result.total(),
+        gauge::WALL_MAX
+    );
+    heading += &match result.full_at {
+        Some(index) => format!("\nWall break on `{}`.", result.steps[index].input),
+        None => format!(
+            "\nNo wall break ({} more wall damage needed).",
+            (gauge::WALL_MAX * (100.0 - result.end) / 100.0).ceil()
+        ),
+    };
+    heading += &format!("\nOpponent R.I.S.C. reduced by {risc_loss:.1}%.");
+    heading
+}
+
+/// 連係・コンボ指定の技を検索する関数（見つからない場合は案内を送信）
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `sequence` - ">" 区切りの技指定
+///
+/// # 戻り値
+/// 正式なキャラクター名と技情報一覧（入力順）、案内送信済みの場合は None
+async fn load_sequence(
+    ctx: &Context<'_>,
+    character: &str,
+    sequence: &str,
+) -> Result<Option<(String, Vec<MoveInfo>)>, AppError> {
+    if (check::adaptive_check(
+        *ctx,
+        check::CheckOptions::DATA_FOLDER
+            | check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
+    )
+    .await)
+        .is_err()
+    {
+        return Ok(None);
+    }
+
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
+        Ok(character_arg_altered) => character_arg_altered,
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+            return Ok(None);
+        }
+    };
+
+    let parts = utils::split_sequence(sequence);
+    if parts.is_empty() || parts.len() > MAX_GAUGE_MOVES {
+        ctx.say(format!(
+            "Specify between 1 and {MAX_GAUGE_MOVES} moves separated by `>`."
+        ))
+        .await?;
+        return Ok(None);
+    }
+
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, &character_arg_altered)?;
+    let moves = resolve::resolve_moves(ctx, &character_arg_altered, &parts, &moves_info).await?;
+    Ok(moves.map(|moves| (character_arg_altered, moves)))
+}
+
+/// R.I.S.C.・壁耐久計算コマンド
+///
+/// # 概要
+/// 以下のサブコマンドを提供する：
+/// - risc: ガード連係で溜まる R.I.S.C. レベルを表示
+/// - wall: コンボの壁ダメージと壁破壊の有無を表示
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands("risc", "wall"),
+    subcommand_required
+)]
+pub async fn gauge(_: Context<'_>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// ガード連係の R.I.S.C. 蓄積を表示するコマンド処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `blockstring` - ">" 区切りのガード連係
+/// * `start` - 開始時の R.I.S.C. ゲージ（%）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn risc(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[min_length = 2]
+    #[description = "Blocked moves separated by '>'."]
+    blockstring: String,
+    #[min = 0]
+    #[max = 100]
+    #[description = "Opponent R.I.S.C. gauge (%) before the blockstring. Defaults to 0."]
+    start: Option<u32>,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned() + &character + ", " + &blockstring + "'").purple()
+    );
+
+    let Some((character_arg_altered, moves)) =
+        load_sequence(&ctx, &character, &blockstring).await?
+    else {
+        return Ok(());
+    };
+    let result = gauge::risc_buildup(&moves, f64::from(start.unwrap_or(0)));
+    let embed = create_gauge_embed(&character_arg_altered, &risc_heading(&result), &result);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// コンボの壁ダメージを表示するコマンド処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `combo` - ">" 区切りのコンボ
+/// * `start` - 開始時の壁ダメージ蓄積（%）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn wall(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[min_length = 2]
+    #[description = "Combo moves separated by '>'."]
+    combo: String,
+    #[min = 0]
+    #[max = 100]
+    #[description = "Wall gauge (%) already filled before the combo. Defaults to 0."]
+    start: Option<u32>,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned() + &character + ", " + &combo + "'").purple()
+    );
+
+    let Some((character_arg_altered, moves)) = load_sequence(&ctx, &character, &combo).await?
+    else {
+        return Ok(());
+    };
+    let result = gauge::wall_buildup(&moves, f64::from(start.unwrap_or(0)));
+    let heading = wall_heading(&result, gauge::risc_loss_percent(&moves));
+    let embed = create_gauge_embed(&character_arg_altered, &heading, &result);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+
+    fn wall_moves(count: usize) -> Vec<MoveInfo> {
+        let mut info = create_test_move_info().remove(0);
+        info.input = "2D".to_string();
+        info.wall_damage = Some(1000);
+        vec![info; count]
+    }
+
+    #[test]
+    fn test_wall_heading() {
+        let result = gauge::wall_buildup(&wall_moves(3), 0.0);
+        assert!(wall_heading(&result, 0.0).contains("Wall break on `2D`."));
+        let result = gauge::wall_buildup(&wall_moves(1), 50.0);
+        assert!(wall_heading(&result, 0.0).contains("(500 more wall damage needed)"));
+    }
+
+    #[test]
+    fn test_breakdown_table() {
+        let result = gauge::wall_buildup(&wall_moves(1), 0.0);
+        let table = breakdown_table(&result);
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.contains("1000  33.3%"));
+    }
+}
@@ -7,7 +7,7 @@
 
 // futures クレートから、Stream 型や拡張メソッド StreamExt をインポートする。
 // これにより、非同期ストリームの操作が可能になる。
-use crate::serenity::futures::{self, Stream, StreamExt};
+use poise::serenity_prelude::futures::{self, Stream, StreamExt};
 
 // Discord コマンドの実行コンテキスト (Context) とエラー型 (AppError) を定義しているモジュールをインポート
 use crate::{error::AppError, Context};
@@ -30,15 +30,25 @@ async fn autocomplete_help<'a>(
     // ヘルプ候補の一覧を列挙したストリームを作成する
     futures::stream::iter(&[
         "general",
+        "alias",
+        "combo",
+        "config",
         "frames",
         "hitboxes",
         "fmeter",
+        "gap",
+        "gauge",
         "moves",
         "nicknames",
         "notes",
+        "oki",
         "specifics",
         "register",
         "update",
+        "doctor",
+        "export",
+        "imagecache",
+        "sql",
         "feedback",
     ])
     // ユーザー入力 (partial) にマッチする候補のみフィルタリングする
@@ -72,16 +82,27 @@ pub async fn help(
 
     // ユーザーの入力に応じて、対応するヘルプ関数を呼び出す
     match option.trim() {
+        "alias" => help_alias(ctx).await,
+        "combo" => help_combo(ctx).await,
+        "config" => help_config(ctx).await,
+        "doctor" => help_doctor(ctx).await,
+        "export" => help_export(ctx).await,
         "feedback" => help_feedback(ctx).await,
         "fmeter" => help_fmeter(ctx).await,
         "frames" => help_frames(ctx).await,
+        "gap" => help_gap(ctx).await,
+        "gauge" => help_gauge(ctx).await,
         "general" => help_general(ctx).await,
         "hitboxes" => help_hitboxes(ctx).await,
+        "imagecache" => help_imagecache(ctx).await,
         "moves" => help_moves(ctx).await,
         "nicknames" => help_nicknames(ctx).await,
         "notes" => help_notes(ctx).await,
+        "oki" => help_oki(ctx).await,
         "register" => help_register(ctx).await,
         "specifics" => help_specifics(ctx).await,
+        #[cfg(feature = "sqlite")]
+        "sql" => help_sql(ctx).await,
         "update" => help_update(ctx).await,
         _ => {
             // 入力に該当するヘルプがない場合、エラーメッセージを生成
@@ -105,8 +126,14 @@ __**コマンドリスト**__
 ```frames``````
 hitboxes``````
 fmeter``````
+gap``````
+combo``````
+gauge``````
+oki``````
 moves``````
+export``````
 nicknames``````
+alias``````
 feedback``````
 help```
 
@@ -119,14 +146,78 @@ __<https://github.com/yakiimoninja/baiken>__
     let _ = ctx.say(help_msg).await;
 }
 
+/// エイリアス提案用ヘルプメッセージを送信する関数
+async fn help_alias(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/alias suggest`.
+__**Example**__: `/alias suggest sol 236K Gunflame`.
+
+__**character**__: Character name or nickname.
+__**move**__: Move name, input or alias.
+__**alias**__: The new alias. Cannot already be an alias, name or input of one of the character's moves.
+
+Suggests a new alias for a move. The dev reviews it and you get a DM (or a mention if DMs are closed) once it is accepted or rejected.
+Accepted aliases can be used right away in every command.
+
+__**Owner only**__:
+`/alias pending`: Shows suggestions awaiting review with Accept / Reject buttons."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
+/// コンボダメージ計算用ヘルプメッセージを送信する関数
+async fn help_combo(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/combo`.
+__**Example**__: `/combo sol CH 6H > c.S > 2H > 236K vs potemkin`.
+
+__**character_arg**__: Character name or nickname. Cannot be empty.
+__**recipe**__: Moves separated by `>`. Prefix a move with `CH` for a counter hit. Add `vs <character>` to use that character's defense and guts.
+__**health**__: Optional. Defender health (%) when the combo starts. Defaults to 100.
+
+Calculates the combo damage with proration, guts and defense applied, the tension gained, and a per-hit breakdown."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
+/// サーバー設定用ヘルプメッセージを送信する関数
+async fn help_config(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/config show` / `/config fields` / `/config hide_empty`.
+__**Example**__: `/config fields simple startup, active, recovery, on_block`.
+
+__**view**__: `simple` or `advanced`.
+__**fields**__: Fields in display order, separated by `,`. Leave empty to reset to the default.
+__**hide**__: Hide fields whose value is `-`.
+
+Configures which fields `/frames simple` and `/frames advanced` show in this server. Requires the Manage Server permission.
+
+`/config alias add <character> <move> <alias>` / `/config alias remove <character> <alias>`: Server-only move aliases.
+`/config nickname add <character> <nickname>` / `/config nickname remove <character> <nickname>`: Server-only character nicknames.
+Server aliases and nicknames are checked before the shared ones and cannot clash with them.
+`fields` and `hide_empty` can also be given directly to `/frames simple` and `/frames advanced`.
+
+__**Available fields**__: `name`, `move_type`, `damage`, `guard`, `invincibility`, `startup`, `active`, `recovery`, `on_hit`, `on_block`, `counter`, `counter_hit`, `level`, `stun`, `hitstop`, `cancel`, `risc_gain`, `risc_loss`, `wall_damage`, `input_tension`, `chip_ratio`, `scaling`."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
 /// フィードバック用ヘルプメッセージを送信する関数
 async fn help_feedback(ctx: Context<'_>) {
     let help_msg = r#"
-__**Command**__: `/feedback`.
+__**Command**__: `/feedback submit`.
+__**Example**__: `/feedback submit text:Sol 5K startup is wrong category:data`.
 
 __**text**__: Any text. Cannot be empty.
+__**category**__: bug, request, data, alias or other. Defaults to other.
+
+Sends feedback or a request to the dev and replies with its id.
+You will get a DM (or a mention if DMs are closed) when the dev replies or resolves it.
 
-Sends feedback or a request to the dev."#;
+__**Owner only**__:
+`/feedback list [status]`: Lists feedback (default: open).
+`/feedback resolve <id> [message] [rejected]`: Closes feedback and notifies the submitter.
+`/feedback reply <id> <text>`: Sends a reply to the submitter."#;
 
     // ヘルプメッセージ送信
     let _ = ctx.say(help_msg).await;
@@ -140,16 +231,34 @@ Sends feedback or a request to the dev."#;
         .await;
 }
 
+/// 技データ出力用ヘルプメッセージを送信する関数
+async fn help_export(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/export`.
+__**Example**__: `/export sol markdown`.
+
+__**character**__: Character name or nickname. Cannot be empty.
+__**format**__: `csv`, `markdown` or `json`. Defaults to `csv`.
+
+Attaches the full moveset of a character as a file, including move aliases and image / hitbox URLs."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
 /// フレームメーター用ヘルプメッセージを送信する関数
 async fn help_fmeter(ctx: Context<'_>) {
     let help_msg = r#"
 __**Command**__: `/fmeter`.
 __**Example**__: `/fmeter cz super`.
+__**Example**__: `/fmeter sol 5K > c.S > 2H`.
 
 __**character_arg**__: キャラクター名は空欄に出来ないよ！
-__**character_move_arg**__: キャラクターのコマンド名や技名は空欄に出来ないよ！
+__**character_move_arg**__: キャラクターのコマンド名や技名は空欄に出来ないよ！`>` 区切りで連係も指定できるよ！
+__**emoji**__: 省略可。true にすると画像の代わりに絵文字でメーターを表示するよ！
 
-キャラクターの技の始動フレーム、持続フレーム、後隙フレームを視覚的に表示するよ！"#;
+キャラクターの技の始動フレーム、持続フレーム、後隙フレームを視覚的に表示するよ！
+トレーニングモード風のメーター画像で、無敵や攻撃判定の無い区間も確認できるよ！
+連係を指定するとキャンセル・目押しで繋いだメーターと、技間のガード時の隙間を表示するよ！"#;
 
     let _ = ctx.say(help_msg).await;
     // 関連画像の URL を送信
@@ -184,6 +293,40 @@ Displays the frame data of a move along with an image."#;
         .await;
 }
 
+/// 技間の隙間判定用ヘルプメッセージを送信する関数
+async fn help_gap(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/gap`.
+__**Example**__: `/gap sol 5K 6P`.
+
+__**character_arg**__: Character name or nickname. Cannot be empty.
+__**move1**__: First move name, input or alias. Cannot be empty.
+__**move2**__: Second move name, input or alias. Cannot be empty.
+
+Calculates the gap on block between two moves (cancel or link).
+Shows whether it is a true blockstring, a frame trap or a reversal window, and which universal options beat it."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
+/// R.I.S.C.・壁耐久計算用ヘルプメッセージを送信する関数
+async fn help_gauge(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/gauge risc` / `/gauge wall`.
+__**Example**__: `/gauge risc sol 5K > c.S > 2H > 2D`.
+__**Example**__: `/gauge wall sol c.S > 2H > 236K start:40`.
+
+__**character_arg**__: Character name or nickname. Cannot be empty.
+__**blockstring**__ / __**combo**__: Moves separated by `>`. Cannot be empty.
+__**start**__: Optional. Gauge (%) already filled beforehand. Defaults to 0.
+
+`risc`: R.I.S.C. gain is added every time the opponent blocks a move. At 100% the next hit is a counter hit.
+`wall`: Wall damage is added on every hit in the corner. When it fills the wall gauge, the wall breaks.
+R.I.S.C. loss is how much of the opponent's R.I.S.C. gauge a hit removes."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
 /// ヒットボックス表示用ヘルプメッセージを送信する関数
 async fn help_hitboxes(ctx: Context<'_>) {
     let help_msg = r#"
@@ -192,6 +335,7 @@ __**Example**__: `/hitboxes goldlewis 426H`.
 
 __**character_arg**__: Character name or nickname. Cannot be empty.
 __**character_move_arg**__: Character move name, input or alias. Cannot be empty.
+__**composite**__: Optional. `grid` stitches every hitbox frame into one image, `gif` plays them as an animation.
 
 Displays the hitbox images of a character's move."#;
 
@@ -210,11 +354,15 @@ Displays the hitbox images of a character's move."#;
 async fn help_moves(ctx: Context<'_>) {
     let help_msg = r#"
 __**Command**__: `/moves`.
-__**Example**__: `/moves sol`.
+__**Example**__: `/moves sol all`.
+__**Example**__: `/moves sol normals frames:true`.
 
 __**character_arg**__: Character name or nickname. Cannot be empty.
+__**type**__: `all`, `normals`, `specials` or `supers`.
+__**frames**__: Also show startup, on block and guard for each move.
 
-Displays all the moves, inputs and move aliases of a character."#;
+Displays the moves, inputs and move aliases of a character, grouped by move type.
+Long lists are split into pages you can flip through with the buttons."#;
 
     let _ = ctx.say(help_msg).await;
     // 技一覧画像の URL を送信
@@ -231,8 +379,15 @@ Displays all the moves, inputs and move aliases of a character."#;
 async fn help_nicknames(ctx: Context<'_>) {
     let help_msg = r#"
 __**Command**__: `/nicknames`.
+__**Example**__: `/nicknames sol`.
+
+__**character**__: Character name or nickname. Leave empty to page through all characters.
+
+Displays the nicknames of a character, or of every character.
 
-Displays all the nicknames for each character."#;
+__**Owner / admin only**__:
+`/nickname add <character> <nickname>`: Adds a nickname. Cannot match another nickname or character name.
+`/nickname remove <character> <nickname>`: Removes a nickname."#;
 
     let _ = ctx.say(help_msg).await;
     // 愛称一覧画像の URL を送信
@@ -245,6 +400,21 @@ Displays all the nicknames for each character."#;
         .await;
 }
 
+/// 起き攻め計算用ヘルプメッセージを送信する関数
+async fn help_oki(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/oki`.
+__**Example**__: `/oki sol 2D dash > 5K`.
+
+__**character_arg**__: Character name or nickname. Cannot be empty.
+__**knockdown**__: Knockdown move name, input or alias. Cannot be empty.
+__**actions**__: Optional. Actions before the meaty, separated by `>`: `dash`, `jump`, `backdash`, a number of frames to wait, or a move to whiff.
+
+Subtracts the actions from the knockdown advantage and lists the moves that hit meaty on wake-up, with how many active frames are used up."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
 /// 使用上の注意点を説明するヘルプメッセージを送信する関数
 async fn help_notes(ctx: Context<'_>) {
     let help_msg = r#"
@@ -295,6 +465,52 @@ Registers or removes all slash commands in the current server or every server th
         .await;
 }
 
+/// データ診断コマンドのヘルプメッセージを送信する関数
+async fn help_doctor(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/doctor`.
+
+**This command only works for owners.**
+Validates every character's frame data, image links, aliases and nicknames.
+Shows error/warning counts per character and attaches the full report."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
+/// 画像キャッシュコマンドのヘルプメッセージを送信する関数
+async fn help_imagecache(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/imagecache sync` or `/imagecache report`.
+__**Example**__: `/imagecache sync sol`.
+
+__**character**__: Character name or nickname. Defaults to all characters.
+__**force**__: Re-check images that are already cached (`sync` only).
+
+**This command only works for owners.**
+`sync` downloads move and hitbox images into the data folder and records images that return 404.
+`report` lists the missing images per character.
+When a remote image is unavailable, the cached copy is sent as an attachment instead."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
+/// データベースコマンドのヘルプメッセージを送信する関数
+#[cfg(feature = "sqlite")]
+async fn help_sql(ctx: Context<'_>) {
+    let help_msg = r#"
+__**Command**__: `/sql query` or `/sql rebuild`.
+__**Example**__: `/sql query SELECT character, input, startup FROM moves WHERE startup <= 4`.
+
+__**sql**__: A single read-only statement. Write statements are rejected.
+
+**This command only works for owners and bots built with the `sqlite` feature.**
+`rebuild` imports the JSON data into `baiken.sqlite3` in the data folder.
+Tables: `characters`, `moves`, `aliases`, `nicknames`, `images`, `hitboxes`, `update_history`.
+Up to 50 rows are shown. Set `DATA_BACKEND=sqlite` to read move data from the database."#;
+
+    let _ = ctx.say(help_msg).await;
+}
+
 /// キャラクター固有の仕様について説明するヘルプメッセージを送信する関数
 async fn help_specifics(ctx: Context<'_>) {
     let help_msg = r#"
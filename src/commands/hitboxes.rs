@@ -5,10 +5,15 @@
 //! 指定されたキャラクターと技に対応するヒットボックス画像をDiscord上に埋め込み表示する。
 
 // 必要なインポート
-use crate::{check, error::AppError, find, Context, ImageLinks, MoveInfo, EMBED_COLOR}; // 各種機能とデータ型
+use crate::common::paginate::{paginate, Page}; // ページ送り表示
+use crate::guild::GuildConfig;
+use crate::render::hitbox::{self as hitbox_render, CompositeMode}; // ヒットボックス画像合成
+use crate::{
+    check, error::AppError, find, image_cache, utils, Context, ImageLinks, MoveInfo, EMBED_COLOR,
+}; // 各種機能とデータ型
 use colored::Colorize; // ターミナル出力の色付け
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter}; // Discord埋め込み作成
-use std::{fs, string::String}; // ファイル操作と文字列型
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, CreateEmbedFooter}; // Discord埋め込み作成
+use std::string::String; // 文字列型
 
 /// デフォルトヒットボックス画像URL
 const HITBOX_DEFAULT: &str =
@@ -25,7 +30,13 @@ const HITBOX_DEFAULT: &str =
 async fn load_character_data(character: &str, ctx: &Context<'_>) -> Result<String, AppError> {
     // キャラクター名検索
     // ユーザー入力がエイリアスの場合、正式なキャラクター名を取得
-    let character_arg_altered = match find::find_character(&character.to_string()).await {
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
         Ok(character_arg_altered) => character_arg_altered,
         Err(err) => {
             // キャラクター未検出時のエラーメッセージ送信
@@ -52,84 +63,22 @@ async fn find_move_and_images(
     character_move: &str,
     ctx: &Context<'_>,
 ) -> Result<(MoveInfo, Vec<ImageLinks>), AppError> {
-    // キャラクターデータファイルのパス設定
-    let file_path = format!("data/{character_arg_altered}/{character_arg_altered}.json");
-    let images_path = format!("data/{character_arg_altered}/images.json");
-
-    println!(
-        "{}",
-        format!(
-            "Looking for move '{}' for character '{}'",
-            character_move, character_arg_altered
-        )
-        .blue()
-    );
-
-    // キャラクターJSONファイル読み込み
-    let data = fs::read_to_string(&file_path).map_err(|_| {
-        let error_msg = format!("Failed to load character data from {file_path}");
-        println!("{}", error_msg.red());
-        AppError::FileNotFound(error_msg)
-    })?;
-
-    // JSONデシリアライズ
-    let moves_info: Vec<MoveInfo> = serde_json::from_str(&data).map_err(|e| {
-        let error_msg = format!("Failed to parse character data: {e}");
-        println!("{}", error_msg.red());
-        AppError::Json(e)
-    })?;
-
-    println!(
-        "{}",
-        format!(
-            "Loaded {} moves for character '{}'",
-            moves_info.len(),
-            character_arg_altered
-        )
-        .blue()
-    );
+    // 技情報読み込み　データディレクトリ基準
+    let moves_info = utils::load_move_info(&ctx.data().data_dir, character_arg_altered)?;
 
-    // 画像JSONファイル読み込み
-    let image_data = fs::read_to_string(&images_path).map_err(|_| {
-        let error_msg = format!("Failed to load image data from {images_path}");
-        println!("{}", error_msg.red());
-        AppError::FileNotFound(error_msg)
-    })?;
-
-    // 画像JSONデシリアライズ
-    let image_links: Vec<ImageLinks> = serde_json::from_str(&image_data).map_err(|e| {
-        let error_msg = format!("Failed to parse image data: {e}");
-        println!("{}", error_msg.red());
-        AppError::Json(e)
-    })?;
-
-    println!(
-        "{}",
-        format!("Loaded {} image entries", image_links.len()).blue()
-    );
+    // 画像リンク読み込み　データディレクトリ基準
+    let image_links = utils::load_image_links(&ctx.data().data_dir, character_arg_altered)?;
 
     // 6kや5hsなどの技入力で直接検索（大文字小文字区別なし）
     let normalized_input = character_move.to_lowercase().replace(' ', "");
-    println!(
-        "{}",
-        format!("Normalized input for direct search: '{}'", normalized_input).blue()
-    );
 
     // image_links内から直接一致するものを探す
-    println!(
-        "{}",
-        "Searching for direct match in image entries...".blue()
-    );
     let mut direct_match_index = None;
     for (i, img) in image_links.iter().enumerate() {
         let normalized_img_input = img.input.to_lowercase().replace(' ', "");
         if normalized_img_input == normalized_input
-            || img.input.eq_ignore_ascii_case(&character_move)
+            || img.input.eq_ignore_ascii_case(character_move)
         {
-            println!(
-                "{}",
-                format!("Found direct match at index {}: '{}'", i, img.input).green()
-            );
             direct_match_index = Some(i);
             break;
         }
@@ -137,7 +86,6 @@ async fn find_move_and_images(
 
     // 直接一致する技が見つかった場合
     if let Some(idx) = direct_match_index {
-        println!("{}", "Using direct match for hitbox images".green());
         // 対応するmove_infoを探す
         let img_link = &image_links[idx];
         let img_input = &img_link.input;
@@ -149,67 +97,50 @@ async fn find_move_and_images(
                 || normalized_input == m.input.to_lowercase().replace(' ', "")
             {
                 matched_move = Some(m.clone());
-                println!(
-                    "{}",
-                    format!("Found matching move info: {}", m.name).green()
-                );
                 break;
             }
         }
 
         // 対応する技情報が見つからない場合は、最初のmove_infoを使用して応急処置
-        let move_data = matched_move.unwrap_or_else(|| {
-            println!(
-                "{}",
-                "No exact move info match found, generating placeholder".yellow()
-            );
-            MoveInfo {
-                name: img_input.clone(),
-                input: img_input.clone(),
-                damage: None,
-                guard: "".to_string(),
-                startup: None,
-                active: "".to_string(),
-                recovery: None,
-                on_hit: "".to_string(),
-                on_block: "".to_string(),
-                level: "".to_string(),
-                counter: "".to_string(),
-                move_type: "".to_string(),
-                risc_gain: None,
-                risc_loss: None,
-                wall_damage: None,
-                input_tension: None,
-                chip_ratio: None,
-                scaling: None,
-                invincibility: "".to_string(),
-                cancel: "".to_string(),
-                caption: "".to_string(),
-                notes: "".to_string(),
-            }
+        let move_data = matched_move.unwrap_or_else(|| MoveInfo {
+            name: img_input.clone(),
+            input: img_input.clone(),
+            damage: None,
+            guard: "".to_string(),
+            startup: None,
+            active: "".to_string(),
+            recovery: None,
+            on_hit: "".to_string(),
+            on_block: "".to_string(),
+            level: "".to_string(),
+            counter: "".to_string(),
+            move_type: "".to_string(),
+            risc_gain: None,
+            risc_loss: None,
+            wall_damage: None,
+            input_tension: None,
+            chip_ratio: None,
+            scaling: None,
+            invincibility: "".to_string(),
+            cancel: "".to_string(),
+            caption: "".to_string(),
+            notes: "".to_string(),
         });
 
         return Ok((move_data, image_links.to_vec()));
     }
 
     // 直接一致が見つからない場合は通常の検索フローを使用
-    println!(
-        "{}",
-        "No direct match found, using normal search flow".yellow()
-    );
-
     // character_moveを小文字に変換して処理
     let character_move_lower = character_move.to_lowercase();
-    println!(
-        "{}",
-        format!("Using normalized move name: '{}'", character_move_lower).blue()
-    );
 
     // 技名インデックス検索
     let move_index = match find::find_move_index(
+        &ctx.data().data_dir,
         &character_arg_altered.to_string(),
         character_move_lower,
         &moves_info,
+        GuildConfig::for_context(ctx).as_ref(),
     )
     .await
     {
@@ -225,43 +156,6 @@ async fn find_move_and_images(
     // 見つかった技情報の取得
     let move_data = &moves_info[move_index];
 
-    println!(
-        "{}",
-        format!(
-            "Found move at index {}: {} (input: {})",
-            move_index, move_data.name, move_data.input
-        )
-        .blue()
-    );
-
-    // 技の入力コマンドをログ出力
-    println!(
-        "{}",
-        format!("Looking for move with input: '{}'", move_data.input).blue()
-    );
-
-    // image_links内の各要素のinput値をログに出力
-    println!("{}", "Available image inputs:".blue());
-    for (i, img) in image_links.iter().enumerate() {
-        if i < 20 {
-            // 最初の20個だけ表示（多すぎるとログが見にくくなるため）
-            println!("  {}: '{}'", i, img.input);
-        }
-    }
-
-    // 6Kに直接一致するエントリがあるかのチェック
-    if character_move.eq_ignore_ascii_case("6k") {
-        println!("{}", "Specifically checking for 6K entries...".blue());
-        for (i, img) in image_links.iter().enumerate() {
-            if img.input.eq_ignore_ascii_case("6k") {
-                println!(
-                    "{}",
-                    format!("Found direct 6K match at index {}", i).green()
-                );
-            }
-        }
-    }
-
     Ok((move_data.clone(), image_links))
 }
 
@@ -288,27 +182,11 @@ fn create_hitbox_embeds(
     // 技入力の正規化（検索用）
     // 大文字小文字を区別せず、スペースを削除
     let normalized_move_input = move_info.input.to_lowercase().replace(' ', "");
-    println!(
-        "{}",
-        format!(
-            "Normalized move input for search: '{}'",
-            normalized_move_input
-        )
-        .cyan()
-    );
 
     // 直接マッチングを試みる（大文字小文字を区別せず）
-    println!(
-        "{}",
-        "Trying direct case-insensitive matching first...".cyan()
-    );
     let mut target_move_index = None;
     for (i, img) in image_links.iter().enumerate() {
         if img.input.eq_ignore_ascii_case(&move_info.input) {
-            println!(
-                "{}",
-                format!("Found direct case-insensitive match at index {}!", i).green()
-            );
             target_move_index = Some(i);
             break;
         }
@@ -330,20 +208,6 @@ fn create_hitbox_embeds(
                 .url(&embed_url)
         };
 
-        println!(
-            "{}",
-            format!(
-                "Using direct match! hitbox_img: {} (raw), {} (valid)",
-                img_links.hitbox_img.len(),
-                img_links
-                    .hitbox_img
-                    .iter()
-                    .filter(|url| !url.is_empty())
-                    .count()
-            )
-            .blue()
-        );
-
         // 空文字列を除外した有効なヒットボックス画像URLを収集
         let valid_hitbox_images: Vec<String> = img_links
             .hitbox_img
@@ -384,14 +248,6 @@ fn create_hitbox_embeds(
         for img_links in image_links {
             // 対象技の画像リンク検索（正規化して比較）
             let normalized_img_input = img_links.input.to_lowercase().replace(' ', "");
-            println!(
-                "{}",
-                format!(
-                    "Comparing with: '{}' (normalized: '{}')",
-                    img_links.input, normalized_img_input
-                )
-                .cyan()
-            );
 
             // 入力が一致するか確認
             if normalized_move_input == normalized_img_input {
@@ -404,20 +260,6 @@ fn create_hitbox_embeds(
                         .url(&embed_url)
                 };
 
-                println!(
-                    "{}",
-                    format!(
-                        "Found matching move! hitbox_img: {} (raw), {} (valid)",
-                        img_links.hitbox_img.len(),
-                        img_links
-                            .hitbox_img
-                            .iter()
-                            .filter(|url| !url.is_empty())
-                            .count()
-                    )
-                    .blue()
-                );
-
                 // 空文字列を除外した有効なヒットボックス画像URLを収集
                 let valid_hitbox_images: Vec<String> = img_links
                     .hitbox_img
@@ -463,17 +305,8 @@ fn create_hitbox_embeds(
 
     // 技名の一部が含まれている場合の処理（完全一致しない場合のフォールバック）
     if !found_matching_move {
-        println!(
-            "{}",
-            format!("No exact match found. Trying partial matching...").yellow()
-        );
-
         // 技入力を5HやjKなど基本形に変換して再検索
         let simplified_input = simplified_move_input(&normalized_move_input);
-        println!(
-            "{}",
-            format!("Simplified input: '{}'", simplified_input).yellow()
-        );
 
         for img_links in image_links {
             let normalized_img_input = img_links.input.to_lowercase().replace(' ', "");
@@ -482,12 +315,6 @@ fn create_hitbox_embeds(
             if normalized_img_input.contains(&normalized_move_input)
                 || normalized_img_input.contains(&simplified_input)
             {
-                // 部分一致を見つけたことをログに出力
-                println!(
-                    "{}",
-                    format!("Found partial match: '{}'", img_links.input).green()
-                );
-
                 // 埋め込みの基本設定を作成
                 let base_embed = CreateEmbed::new()
                     .color(EMBED_COLOR)
@@ -569,6 +396,88 @@ fn simplified_move_input(input: &str) -> String {
     input
 }
 
+/// 合成ヒットボックス画像送信関数
+///
+/// # 概要
+/// 技の全ヒットボックス画像を 1 枚に合成（保存済みがあれば再利用）して添付送信する。
+/// 画像が 1 枚以下の場合や合成に失敗した場合は通常表示と同じ埋め込みで送信する。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `move_info` - 技情報
+/// * `image_links` - 画像リンク情報（ローカル置換前）
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `mode` - 合成形式
+async fn send_composite(
+    ctx: Context<'_>,
+    move_info: &MoveInfo,
+    image_links: &[ImageLinks],
+    character_arg_altered: &str,
+    mode: CompositeMode,
+) -> Result<(), AppError> {
+    let urls: Vec<String> = find::find_image_index(&move_info.input, image_links)
+        .map(|idx| {
+            image_links[idx]
+                .hitbox_img
+                .iter()
+                .filter(|url| !url.is_empty())
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if urls.len() > 1 {
+        // 画像取得・合成はブロッキング処理のため専用スレッドで実行
+        ctx.defer().await?;
+        let data_dir = ctx.data().data_dir.clone();
+        let character = character_arg_altered.to_string();
+        let count = urls.len();
+        let rendered = tokio::task::spawn_blocking(move || {
+            hitbox_render::render_composite(&data_dir, &character, &urls, mode)
+        })
+        .await
+        .map_err(|e| AppError::DataProcessing(e.to_string()))?;
+
+        match rendered {
+            Ok(path) => {
+                let attachment = CreateAttachment::path(&path).await?;
+                let embed = CreateEmbed::new()
+                    .color(EMBED_COLOR)
+                    .title(format!("__**{}**__", move_info.input))
+                    .url(format!(
+                        "https://dustloop.com/w/GGST/{character_arg_altered}#Overview"
+                    ))
+                    .image(format!("attachment://{}", attachment.filename))
+                    .footer(CreateEmbedFooter::new(format!(
+                        "Move has {count} hitbox images."
+                    )));
+                ctx.send(
+                    poise::CreateReply::default()
+                        .embed(embed)
+                        .attachment(attachment),
+                )
+                .await?;
+                return Ok(());
+            }
+            Err(err) => {
+                println!("{}", format!("Composite render failed: {err}").red());
+            }
+        }
+    }
+
+    // 合成不要または合成失敗時は通常表示
+    let mut image_links = image_links.to_vec();
+    let mut local_images = image_cache::LocalImages::load(&ctx.data().data_dir);
+    local_images.localize_links(&mut image_links);
+    let vec_embeds = create_hitbox_embeds(move_info, &image_links, character_arg_altered);
+    let mut pages = Vec::new();
+    for embed in vec_embeds {
+        let files = local_images.files_for(std::slice::from_ref(&embed)).await;
+        pages.push(Page { embed, files });
+    }
+    paginate(ctx, pages).await
+}
+
 /// ヒットボックス表示コマンド
 ///
 /// 指定されたキャラクターの技のヒットボックス画像を表示する
@@ -577,6 +486,7 @@ fn simplified_move_input(input: &str) -> String {
 /// * `ctx` - コマンドコンテキスト
 /// * `character` - キャラクター名またはニックネーム（最低2文字以上）
 /// * `character_move` - 技名、入力コマンド、またはエイリアス（最低2文字以上）
+/// * `composite` - 指定時は全ヒットボックス画像を 1 枚（格子または GIF）に合成して表示
 ///
 /// # 戻り値
 /// 成功時は `Ok(())`, エラー時は `Err(Error)` を返す
@@ -590,6 +500,7 @@ pub async fn hitboxes(
     #[rename = "move"]
     #[description = "Move name, input or alias."]
     mut character_move: String,
+    #[description = "Stitch all hitbox frames into one image."] composite: Option<CompositeMode>,
 ) -> Result<(), AppError> {
     // コマンド引数のログ出力
     println!(
@@ -613,18 +524,8 @@ pub async fn hitboxes(
         return Ok(());
     };
 
-    // デバッグ出力: 正規化されたキャラクター名を表示
-    println!(
-        "{}",
-        format!("Normalized character name: '{character_arg_altered}'").yellow()
-    );
-
     // 技名の前処理（両端の空白を削除、大文字小文字の正規化）
     character_move = character_move.trim().to_lowercase();
-    println!(
-        "{}",
-        format!("Normalized move input: '{character_move}'").yellow()
-    );
 
     // 技情報と画像データ読み込み
     let result = find_move_and_images(&character_arg_altered, &character_move, &ctx).await;
@@ -633,39 +534,29 @@ pub async fn hitboxes(
         println!("{}", format!("Error finding move: {err}").red());
     }
 
-    let Ok((move_data, image_links)) = result else {
+    let Ok((move_data, mut image_links)) = result else {
         return Ok(());
     };
 
-    // デバッグ出力: 見つかった技情報を表示
-    println!(
-        "{}",
-        format!(
-            "Found move: '{}' (input: '{}')",
-            move_data.name, move_data.input
-        )
-        .yellow()
-    );
+    // 合成画像指定時は 1 枚にまとめて送信
+    if let Some(mode) = composite {
+        return send_composite(ctx, &move_data, &image_links, &character_arg_altered, mode).await;
+    }
 
-    // デバッグ出力: 見つかった画像リンクの数
-    println!(
-        "{}",
-        format!("Found {} image links", image_links.len()).yellow()
-    );
+    // リモート利用不可画像はローカル複製へ置換
+    let mut local_images = image_cache::LocalImages::load(&ctx.data().data_dir);
+    local_images.localize_links(&mut image_links);
 
     // 埋め込みメッセージ作成
     let vec_embeds = create_hitbox_embeds(&move_data, &image_links, &character_arg_altered);
 
-    // デバッグ出力: 作成された埋め込みの数
-    println!(
-        "{}",
-        format!("Created {} embeds", vec_embeds.len()).yellow()
-    );
-
-    // 返信作成と送信
-    let mut reply = poise::CreateReply::default();
-    reply.embeds.extend(vec_embeds);
-    ctx.send(reply).await?;
+    // 画像毎にページ送り表示
+    let mut pages = Vec::new();
+    for embed in vec_embeds {
+        let files = local_images.files_for(std::slice::from_ref(&embed)).await;
+        pages.push(Page { embed, files });
+    }
+    paginate(ctx, pages).await?;
 
     Ok(())
 }
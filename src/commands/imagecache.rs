@@ -0,0 +1,170 @@
+//! # imagecache.rs
+//!
+//! 画像キャッシュ管理モジュール
+//! Discordコマンド /imagecache 実装モジュール
+//! 技画像・ヒットボックス画像のローカル保存と、取得不可画像のキャラクター別報告を提供
+//! このコマンドは、Botの所有者のみ実行可能
+
+use crate::guild::GuildConfig;
+use crate::image_cache::{self, CacheManifest, SyncSummary};
+use crate::{
+    error::{AppError, Result},
+    find, Context, CHARS, EMBED_COLOR,
+};
+use colored::Colorize;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+
+/// 対象キャラクター解決関数
+/// 未指定または "all" の場合は全キャラクターを返す。
+async fn target_characters(ctx: &Context<'_>, character: Option<String>) -> Option<Vec<String>> {
+    let Some(character) = character.filter(|c| !c.trim().eq_ignore_ascii_case("all")) else {
+        return Some(CHARS.iter().map(|c| (*c).to_string()).collect());
+    };
+    match find::find_character(
+        &ctx.data().data_dir,
+        &character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
+        Ok(character_arg_altered) => Some(vec![character_arg_altered]),
+        Err(err) => {
+            let _ = ctx.say(err.to_string()).await;
+            println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+            None
+        }
+    }
+}
+
+/// 画像キャッシュ管理コマンド
+#[poise::command(
+    prefix_command,
+    slash_command,
+    owners_only,
+    subcommands("sync", "report"),
+    subcommand_required
+)]
+pub async fn imagecache(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// 画像を取得してローカルへ保存し、取得不可画像を記録する
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn sync(
+    ctx: Context<'_>,
+    #[description = "キャラクター名または愛称（省略時は全キャラクター）"] character: Option<String>,
+    #[description = "保存済み画像も再確認する"] force: Option<bool>,
+) -> Result<()> {
+    let Some(characters) = target_characters(&ctx, character).await else {
+        return Ok(());
+    };
+    ctx.say(format!(
+        "Image sync started for {} character(s)...",
+        characters.len()
+    ))
+    .await?;
+
+    // ブロッキング取得処理を専用スレッドで実行
+    let data_dir = ctx.data().data_dir.clone();
+    let force = force.unwrap_or(false);
+    let summaries = tokio::task::spawn_blocking(move || -> Result<Vec<SyncSummary>> {
+        let mut manifest = CacheManifest::load(&data_dir);
+        let mut summaries = Vec::new();
+        for character in &characters {
+            println!("{}", format!("Syncing images for '{character}'.").green());
+            summaries.push(image_cache::sync_character(
+                &data_dir,
+                character,
+                &mut manifest,
+                force,
+            )?);
+            // 中断時も進捗が残るようキャラクター毎に保存
+            manifest.save(&data_dir)?;
+        }
+        Ok(summaries)
+    })
+    .await
+    .map_err(|e| AppError::DataProcessing(e.to_string()))??;
+
+    let mut description = "```diff".to_string();
+    for s in &summaries {
+        let mark = if s.not_found.is_empty() && s.failed.is_empty() {
+            '+'
+        } else {
+            '-'
+        };
+        description += &format!(
+            "\n{mark} {}: {} new, {} cached, {} not found, {} failed",
+            s.character,
+            s.downloaded,
+            s.already_cached,
+            s.not_found.len(),
+            s.failed.len()
+        );
+    }
+    description += "\n```";
+
+    let embed = CreateEmbed::new()
+        .title("Image Cache Sync")
+        .description(description)
+        .color(EMBED_COLOR);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// 取得不可と記録された画像をキャラクター別に表示する
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn report(
+    ctx: Context<'_>,
+    #[description = "キャラクター名または愛称（省略時は全キャラクター）"] character: Option<String>,
+) -> Result<()> {
+    let Some(characters) = target_characters(&ctx, character).await else {
+        return Ok(());
+    };
+    let manifest = CacheManifest::load(&ctx.data().data_dir);
+    if manifest.entries.is_empty() {
+        ctx.say("The image cache is empty. Please execute `/imagecache sync` first.")
+            .await?;
+        return Ok(());
+    }
+
+    let mut description = "```diff".to_string();
+    let mut details = String::new();
+    for character in &characters {
+        let missing = manifest.missing_for(character);
+        if missing.is_empty() {
+            continue;
+        }
+        let local = missing.iter().filter(|(_, e)| e.file.is_some()).count();
+        description += &format!(
+            "\n- {character}: {} missing ({local} served from cache)",
+            missing.len()
+        );
+        for (url, entry) in missing {
+            details += &format!("[{character}] {:?} {} {url}\n", entry.status, entry.detail);
+        }
+    }
+
+    if details.is_empty() {
+        ctx.say("No missing images recorded.").await?;
+        return Ok(());
+    }
+    description += "\n```";
+
+    let embed = CreateEmbed::new()
+        .title("Missing Images")
+        .description(description)
+        .color(EMBED_COLOR);
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(
+                details.into_bytes(),
+                "missing_images.txt",
+            )),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,3 +1,23 @@
+/// alias モジュールを公開する  
+/// 技エイリアスの追加提案と開発者による承認／却下を行う処理を含む
+pub mod alias;
+
+/// combo モジュールを公開する  
+/// コンボレシピの補正込みダメージ・獲得テンションの計算結果を表示する処理を含む
+pub mod combo;
+
+/// config モジュールを公開する  
+/// フレームデータ表示項目などのサーバー毎の設定を行う処理を含む（サーバー管理権限が必要）
+pub mod config;
+
+/// doctor モジュールを公開する  
+/// データファイルの存在・整合性診断結果を表示する処理を含む（所有者専用コマンド）
+pub mod doctor;
+
+/// export モジュールを公開する  
+/// キャラクターの技データを CSV・Markdown・JSON ファイルとして出力する処理を含む
+pub mod export;
+
 /// feedback モジュールを公開する  
 /// ユーザーからのフィードバック送信に関するコマンドや処理を含む
 pub mod feedback;
@@ -6,6 +26,14 @@ pub mod feedback;
 /// キャラクターの技のフレームデータ表示および関連画像の送信処理を含む
 pub mod frames;
 
+/// gap モジュールを公開する  
+/// 2 技間のガード時の隙間判定（連続ガード・フレームトラップ）を表示する処理を含む
+pub mod gap;
+
+/// gauge モジュールを公開する  
+/// ガード連係の R.I.S.C. 蓄積やコンボの壁ダメージ・壁破壊判定を表示する処理を含む
+pub mod gauge;
+
 /// help モジュールを公開する  
 /// 各コマンドの使用方法やヘルプ情報を提供する処理を含む
 pub mod help;
@@ -14,18 +42,31 @@ pub mod help;
 /// キャラクターの技のヒットボックス画像表示に関するコマンドや処理を含む
 pub mod hitboxes;
 
+/// imagecache モジュールを公開する  
+/// 技画像・ヒットボックス画像のローカル保存と取得不可画像の報告を行う処理を含む（所有者専用コマンド）
+pub mod imagecache;
+
 /// moves モジュールを公開する  
 /// キャラクターの全技一覧表示や、エイリアス情報の提供を行う処理を含む
 pub mod moves;
 
 /// nicknames モジュールを公開する  
-/// キャラクターの愛称（ニックネーム）情報の表示を行う処理を含む
+/// キャラクターの愛称（ニックネーム）情報の表示・追加・削除を行う処理を含む
 pub mod nicknames;
 
+/// oki モジュールを公開する  
+/// ダウン後の行動を挟んだ起き攻めで重なる技を表示する処理を含む
+pub mod oki;
+
 /// register モジュールを公開する  
 /// Discord のスラッシュコマンド登録／解除などの処理を含む（所有者専用コマンド）
 pub mod register;
 
+/// sql モジュールを公開する  
+/// SQLite データベースの再構築と読み取り専用 SQL の実行を行う処理を含む（所有者専用コマンド、`sqlite` 機能有効時のみ）
+#[cfg(feature = "sqlite")]
+pub mod sql;
+
 /// update モジュールを公開する  
 /// キャラクターのフレームデータや画像データの更新処理を行うコマンドを含む
 pub mod update;
@@ -3,15 +3,17 @@
 //! このファイルは、キャラクターの技、入力、エイリアス情報を表示するためのコマンド処理を実装する。
 //! キャラクター名および技情報の取得、整形、埋め込みメッセージ生成を行う。
 
-mod utils; // ユーティリティ関数群
+pub mod utils; // ユーティリティ関数群
+use crate::common::paginate::{paginate, Page};
+use crate::guild::GuildConfig;
 use crate::{check, error::AppError, find, Context, MoveAliases, MoveInfo, EMBED_COLOR}; // 必要な型・関数群
 use colored::Colorize; // 文字色変換用
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter}; // 埋め込み生成用
-use std::{fs, string::String}; // ファイル操作・文字列操作用
-use utils::{get_normal_moves, get_special_moves, get_super_moves}; // ユーティリティ関数取得
+use std::string::String; // 文字列操作用
+use utils::{category_entries, paginate_entries, MAX_DESCRIPTION_LEN}; // ユーティリティ関数取得
 
 /// ムーブタイプ選択列挙体
-#[derive(Debug, poise::ChoiceParameter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
 pub enum TypeChoice {
     #[name = "all"]
     All, // 全て選択
@@ -38,7 +40,13 @@ async fn load_character_data(
     ctx: &Context<'_>,
 ) -> Result<(String, Vec<MoveInfo>, Vec<MoveAliases>), AppError> {
     // キャラクター探索処理　find関数呼出
-    let character_arg_altered = match find::find_character(&character.to_string()).await {
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(ctx).as_ref(),
+    )
+    .await
+    {
         Ok(character_arg_altered) => character_arg_altered, // キャラクター名称取得
         Err(err) => {
             ctx.say(err.to_string()).await?; // エラーメッセージ送信
@@ -47,135 +55,99 @@ async fn load_character_data(
         }
     };
 
-    // キャラクターファイルパス生成　文字列結合
-    let char_file_path =
-        "data/".to_owned() + &character_arg_altered + "/" + &character_arg_altered + ".json";
-    // キャラクターファイル読み込み　ファイル読み出し
-    let char_file_data = fs::read_to_string(char_file_path)
-        .expect(&("\nFailed to read '".to_owned() + character + ".json" + "' file."));
-
-    // キャラクター情報デシリアライズ　JSONパース
-    let moves_info = serde_json::from_str::<Vec<MoveInfo>>(&char_file_data).unwrap();
+    // 技情報読み込み　データディレクトリ基準
+    let moves_info = crate::utils::load_move_info(&ctx.data().data_dir, &character_arg_altered)?;
 
     println!(
         "{}",
         ("Successfully read '".to_owned() + &character_arg_altered + ".json' file.").green() // 成功出力
     );
 
-    // エイリアスファイルパス生成　文字列結合
-    let aliases_path = "data/".to_owned() + &character_arg_altered + "/aliases.json";
-    // エイリアスファイル読み込み　ファイル読み出し
-    let aliases_data = fs::read_to_string(&aliases_path)
-        .expect(&("\nFailed to read '".to_owned() + &aliases_path + "' file."));
-
-    // エイリアス情報デシリアライズ　JSONパース
-    let aliases_data = serde_json::from_str::<Vec<MoveAliases>>(&aliases_data).unwrap();
+    // エイリアス情報読み込み　データディレクトリ基準
+    let aliases_data =
+        crate::utils::load_move_aliases(&ctx.data().data_dir, &character_arg_altered)?;
 
     Ok((character_arg_altered, moves_info, aliases_data))
 }
 
-/// 指定された技種別に対する埋め込みメッセージを作成する関数
+/// 技種別の表示名取得関数
+fn category_label(category: TypeChoice) -> &'static str {
+    match category {
+        TypeChoice::All => "All",
+        TypeChoice::Normals => "Normals",
+        TypeChoice::Specials => "Specials",
+        TypeChoice::Supers => "Supers",
+    }
+}
+
+/// 指定された技種別の一覧ページを作成する関数
 ///
 /// # 引数
 /// * `category` - 技の種別
 /// * `moves_info` - 技情報
 /// * `aliases_data` - エイリアス情報
 /// * `character_arg_altered` - キャラクター名
+/// * `show_frames` - 発生・ガード硬直差・ガード方向を表示するか否か
 ///
 /// # 戻り値
-/// 埋め込みメッセージのベクター
-async fn create_embeds_for_move_type(
-    category: &TypeChoice,
+/// ページ送り表示用のページ群（種別毎に説明文上限内で分割）
+fn create_move_pages(
+    category: TypeChoice,
     moves_info: &[MoveInfo],
     aliases_data: &[MoveAliases],
     character_arg_altered: &str,
-) -> Vec<CreateEmbed> {
-    let mut vec_embeds = Vec::new(); // 埋め込みメッセージ群格納用ベクター
-
+    show_frames: bool,
+) -> Vec<Page> {
     // 埋め込みタイトル生成　キャラクター名表示
     let embed_title =
         "__**".to_owned() + &character_arg_altered.replace('_', " ") + " Moves / Aliases**__";
     // 埋め込みURL生成　Dustloop Wiki URL構築
     let embed_url = "https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview";
     // 埋め込みフッター生成　補足メッセージ
-    let embed_footer = CreateEmbedFooter::new(
-        "Try the \"/help notes\" command for usage notes and specifics.\nOr \"/report\" to request a new aliases."
-    );
-
-    // 技種別に応じた処理分岐
-    match category {
-        TypeChoice::All => {
-            // 通常技取得　ユーティリティ関数呼出
-            let normal_moves = get_normal_moves(moves_info, aliases_data).await;
-            // スペシャル技取得　ユーティリティ関数呼出
-            let special_moves = get_special_moves(moves_info, aliases_data).await;
-            // 必殺技取得　ユーティリティ関数呼出
-            let super_moves = get_super_moves(moves_info, aliases_data).await;
-
-            // 通常技埋め込み作成　CreateEmbed呼出
-            let normals_embed = CreateEmbed::new()
-                .color(EMBED_COLOR) // 埋め込み色設定
-                .title(embed_title.clone()) // タイトル設定
-                .url(embed_url.clone()) // URL設定
-                .description(normal_moves); // 説明文設定
-
-            // スペシャル技埋め込み作成
-            let specials_embed = CreateEmbed::new()
-                .color(EMBED_COLOR)
-                .description(special_moves); // 説明文設定
-
-            // 必殺技埋め込み作成　フッター追加
-            let supers_embed = CreateEmbed::new()
-                .color(EMBED_COLOR)
-                .description(super_moves)
-                .footer(embed_footer);
-
-            vec_embeds.push(normals_embed); // 埋め込み追加
-            vec_embeds.push(specials_embed); // 埋め込み追加
-            vec_embeds.push(supers_embed); // 埋め込み追加
-        }
-        TypeChoice::Normals => {
-            // 通常技取得　ユーティリティ関数呼出
-            let normal_moves = get_normal_moves(moves_info, aliases_data).await;
-
-            let normals_embed = CreateEmbed::new()
-                .color(EMBED_COLOR)
-                .title(embed_title)
-                .url(embed_url)
-                .description(normal_moves)
-                .footer(embed_footer);
-
-            vec_embeds.push(normals_embed); // 埋め込み追加
-        }
-        TypeChoice::Specials => {
-            // スペシャル技取得　ユーティリティ関数呼出
-            let special_moves = get_special_moves(moves_info, aliases_data).await;
+    let footer_text = if show_frames {
+        "Columns: startup, on block, guard.\nTry the \"/help notes\" command for usage notes and specifics.\nOr \"/alias suggest\" to request a new alias."
+    } else {
+        "Try the \"/help notes\" command for usage notes and specifics.\nOr \"/alias suggest\" to request a new alias."
+    };
 
-            let specials_embed = CreateEmbed::new()
-                .color(EMBED_COLOR)
-                .title(embed_title)
-                .url(embed_url)
-                .description(special_moves)
-                .footer(embed_footer);
+    let categories = match category {
+        TypeChoice::All => vec![
+            TypeChoice::Normals,
+            TypeChoice::Specials,
+            TypeChoice::Supers,
+        ],
+        single => vec![single],
+    };
 
-            vec_embeds.push(specials_embed); // 埋め込み追加
+    // 種別毎に一覧を作成し、上限を超える場合は複数ページへ分割
+    let mut pages = Vec::new();
+    for category in categories {
+        let entries = category_entries(category, moves_info, aliases_data, show_frames);
+        for description in paginate_entries(&entries, MAX_DESCRIPTION_LEN) {
+            pages.push(
+                CreateEmbed::new()
+                    .color(EMBED_COLOR)
+                    .title(format!("{embed_title} — {}", category_label(category)))
+                    .url(embed_url.clone())
+                    .description(description)
+                    .footer(CreateEmbedFooter::new(footer_text))
+                    .into(),
+            );
         }
-        TypeChoice::Supers => {
-            // 必殺技取得　ユーティリティ関数呼出
-            let super_moves = get_super_moves(moves_info, aliases_data).await;
+    }
 
-            let supers_embed = CreateEmbed::new()
+    // 該当技が無い場合
+    if pages.is_empty() {
+        pages.push(
+            CreateEmbed::new()
                 .color(EMBED_COLOR)
-                .title(embed_title)
+                .title(format!("{embed_title} — {}", category_label(category)))
                 .url(embed_url)
-                .description(super_moves)
-                .footer(embed_footer);
-
-            vec_embeds.push(supers_embed); // 埋め込み追加
-        }
-    };
-
-    vec_embeds
+                .description("No moves found.")
+                .into(),
+        );
+    }
+    pages
 }
 
 /// 技一覧コマンド
@@ -186,6 +158,7 @@ async fn create_embeds_for_move_type(
 /// * `ctx` - コマンド実行コンテキスト
 /// * `character` - キャラクター指定文字列
 /// * `category` - 技種別選択（"all", "normals", "specials", "supers"）
+/// * `frames` - 発生・ガード硬直差・ガード方向を併記するか否か
 /// # 戻り値
 /// `Result<(), Error>` を返す
 #[poise::command(prefix_command, slash_command)]
@@ -197,6 +170,7 @@ pub async fn moves(
     #[rename = "type"]
     #[description = "Move type."] // 技種別指定
     category: TypeChoice, // 技種別選択値
+    #[description = "Show startup, on block and guard for each move."] frames: Option<bool>,
 ) -> Result<(), AppError> {
     println!(
         "{}",
@@ -224,20 +198,21 @@ pub async fn moves(
         return Ok(());
     };
 
-    // 埋め込みメッセージ作成
-    let vec_embeds = create_embeds_for_move_type(
-        &category,
+    // サーバー独自のエイリアスを併記
+    let mut aliases_data = aliases_data;
+    if let Some(guild) = GuildConfig::for_context(&ctx) {
+        if let Some(guild_aliases) = guild.aliases.get(&character_arg_altered) {
+            aliases_data.extend(guild_aliases.iter().cloned());
+        }
+    }
+
+    // 一覧ページ作成　ボタンでページ送り表示
+    let pages = create_move_pages(
+        category,
         &moves_info,
         &aliases_data,
         &character_arg_altered,
-    )
-    .await;
-
-    // 返信オブジェクト初期化　CreateReply生成
-    let mut reply = poise::CreateReply::default();
-    reply.embeds.extend(vec_embeds); // 埋め込みメッセージ追加
-
-    ctx.send(reply).await?; // 返信送信
-
-    Ok(()) // 正常終了返却
+        frames.unwrap_or(false),
+    );
+    paginate(ctx, pages).await // 返信送信
 }
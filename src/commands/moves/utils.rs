@@ -1,123 +1,173 @@
 //! `utils.rs`
 //!
-//! このファイルは、ムーブのフレームメーター表示機能に関連する関数群を定義する。
-//! 各ムーブの開始、アクティブ、リカバリーフレームをシンボルとして表現し、
-//! コマンド実行時に視覚的なフレームメーターを生成する。
+//! このファイルは、技一覧表示に関連する関数群を定義する。
+//! 技を `move_type` で分類し（JSON 内の並び順に依存しない）、入力・エイリアス・簡易フレームデータを整形して
+//! 埋め込み説明文の上限を超えないようページ単位に分割する。
 
-use crate::{MoveAliases, MoveInfo};
+use super::TypeChoice;
+use crate::{find, MoveAliases, MoveInfo};
 
-/// 与えられた `MoveInfo` と `MoveAliases` のデータから、
-/// 通常技の情報を文字列として整形して返す非同期関数。
-///
-/// # 引数
-/// * `moves_info` - ムーブ情報のスライス
-/// * `aliases_data` - ムーブのエイリアス情報のスライス
+/// 1 ページあたりの説明文最大文字数（埋め込み説明文上限 4096 文字未満）
+pub const MAX_DESCRIPTION_LEN: usize = 3800;
+
+/// 技種別の分類関数
 ///
-/// # 戻り値
-/// 通常技の一覧を改行区切りの文字列で返す。
-pub async fn get_normal_moves(moves_info: &[MoveInfo], aliases_data: &[MoveAliases]) -> String {
-    // 通常技情報格納用文字列初期化
-    let mut normal_moves = String::new();
-    // moves_info から通常技のみを抽出（move_type が "normal" であるもの）
-    for moves in moves_info
-        .iter()
-        .take_while(|x| x.move_type.to_lowercase() == "normal")
-    {
-        // 技情報を整形して追加：入力と名称を表示
-        normal_moves = normal_moves.clone() + "\n- **" + &moves.input + "**";
-        // エイリアス情報を確認
-        for moves_aliases in aliases_data {
-            // 一致する入力があればエイリアス情報を追加
-            if moves.input == moves_aliases.input {
-                normal_moves += "\n\t別名 → `";
-                // エイリアス一覧をカンマ区切りで追加
-                for a in 0..moves_aliases.aliases.len() {
-                    if a == moves_aliases.aliases.len() - 1 {
-                        normal_moves = normal_moves.clone() + &moves_aliases.aliases[a];
-                    } else {
-                        normal_moves = normal_moves.clone() + &moves_aliases.aliases[a] + "`, `";
-                    }
-                }
-                normal_moves += "`\n";
-            }
-        }
+/// `normal` は通常技、`super` は覚醒必殺技、それ以外（`special`・`other`・`spell` 等）は必殺技に分類する。
+pub fn move_category(move_info: &MoveInfo) -> TypeChoice {
+    match move_info.move_type.to_lowercase().as_str() {
+        "normal" => TypeChoice::Normals,
+        "super" => TypeChoice::Supers,
+        _ => TypeChoice::Specials,
     }
-    // 整形済み通常技情報文字列を返却
-    normal_moves
 }
 
-/// 与えられた `MoveInfo` と `MoveAliases` のデータから、
-/// スペシャル技の情報を文字列として整形して返す非同期関数。
+/// 簡易フレームデータ列の生成関数（例："`4F` `-1` `Mid`"）
+pub fn frames_column(move_info: &MoveInfo) -> String {
+    let startup = move_info
+        .startup
+        .map_or_else(|| "-".to_string(), |s| format!("{s}F"));
+    let or_dash = |value: &str| {
+        if value.trim().is_empty() {
+            "-".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    format!(
+        "`{startup}` `{}` `{}`",
+        or_dash(&move_info.on_block),
+        or_dash(&move_info.guard)
+    )
+}
+
+/// 技 1 件分の表示文字列生成関数
 ///
 /// # 引数
-/// * `moves_info` - ムーブ情報のスライス
-/// * `aliases_data` - ムーブのエイリアス情報のスライス
+/// * `move_info` - 技情報
+/// * `aliases` - 技のエイリアス一覧
+/// * `show_frames` - 簡易フレームデータ列を表示するか否か
 ///
 /// # 戻り値
-/// スペシャル技の一覧を改行区切りの文字列で返す。
-pub async fn get_special_moves(moves_info: &[MoveInfo], aliases_data: &[MoveAliases]) -> String {
-    let mut special_moves = String::new();
-    // 通常技をスキップし、move_type が "special" または "other" の技を対象
-    for moves in moves_info
-        .iter()
-        .skip_while(|x| x.move_type.to_lowercase() == "normal")
-        .take_while(|x| {
-            x.move_type.to_lowercase() == "special" || x.move_type.to_lowercase() == "other"
-        })
-    {
-        special_moves = special_moves.clone() + "\n- **" + &moves.input + "**";
-        for moves_aliases in aliases_data {
-            // 一致する入力があればエイリアス情報を追加
-            if moves.input == moves_aliases.input {
-                special_moves += "\n\t別名 → `";
-                // エイリアス一覧をカンマ区切りで追加
-                for a in 0..moves_aliases.aliases.len() {
-                    if a == moves_aliases.aliases.len() - 1 {
-                        special_moves = special_moves.clone() + &moves_aliases.aliases[a];
-                    } else {
-                        special_moves = special_moves.clone() + &moves_aliases.aliases[a] + "`, `";
-                    }
-                }
-                special_moves = special_moves.clone() + "`\n";
-            }
-        }
+/// 入力（・フレームデータ）とエイリアスを含む表示文字列
+pub fn move_entry(move_info: &MoveInfo, aliases: &[&str], show_frames: bool) -> String {
+    let mut entry = format!("- **{}**", move_info.input);
+    if show_frames {
+        entry += &format!(" {}", frames_column(move_info));
     }
-    special_moves
+
+    if !aliases.is_empty() {
+        entry += &format!("\n\t別名 → `{}`", aliases.join("`, `"));
+    }
+    entry
 }
 
-/// 与えられた `MoveInfo` と `MoveAliases` のデータから、
-/// スーパー技の情報を文字列として整形して返す非同期関数。
+/// 指定種別の技一覧（表示文字列）を JSON 内の順序のまま返す関数
+pub fn category_entries(
+    category: TypeChoice,
+    moves_info: &[MoveInfo],
+    aliases_data: &[MoveAliases],
+    show_frames: bool,
+) -> Vec<String> {
+    let aliases = find::aliases_by_move(moves_info, aliases_data);
+    moves_info
+        .iter()
+        .zip(&aliases)
+        .filter(|(move_info, _)| move_category(move_info) == category)
+        .map(|(move_info, aliases)| move_entry(move_info, aliases, show_frames))
+        .collect()
+}
+
+/// 表示文字列を説明文上限内のページへ分割する関数
 ///
 /// # 引数
-/// * `moves_info` - ムーブ情報のスライス
-/// * `aliases_data` - ムーブのエイリアス情報のスライス
+/// * `entries` - 技 1 件毎の表示文字列
+/// * `max_len` - 1 ページあたりの最大文字数
 ///
 /// # 戻り値
-/// スーパー技の一覧を改行区切りの文字列で返す。
-pub async fn get_super_moves(moves_info: &[MoveInfo], aliases_data: &[MoveAliases]) -> String {
-    let mut super_moves = String::new();
-    // move_type が "super" の技を対象に抽出
-    for moves in moves_info
-        .iter()
-        .skip_while(|x| x.move_type.to_lowercase() != "super")
-    {
-        super_moves = super_moves.clone() + "\n- **" + &moves.input + "**";
-        for moves_aliases in aliases_data {
-            // 一致する入力があればエイリアス情報を追加
-            if moves.input == moves_aliases.input {
-                super_moves += "\n\t別名 → `";
-                // エイリアス一覧をカンマ区切りで追加
-                for a in 0..moves_aliases.aliases.len() {
-                    if a == moves_aliases.aliases.len() - 1 {
-                        super_moves = super_moves.clone() + &moves_aliases.aliases[a];
-                    } else {
-                        super_moves = super_moves.clone() + &moves_aliases.aliases[a] + "`, `";
-                    }
-                }
-                super_moves = super_moves.clone() + "`\n";
-            }
-            continue;
+/// ページ毎の説明文（技の途中では分割しない）
+pub fn paginate_entries(entries: &[String], max_len: usize) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut current = String::new();
+    for entry in entries {
+        let added = entry.chars().count() + 1;
+        if !current.is_empty() && current.chars().count() + added > max_len {
+            pages.push(std::mem::take(&mut current));
         }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current += entry;
+    }
+    if !current.is_empty() {
+        pages.push(current);
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_move_aliases, create_test_move_info};
+
+    /// 種別を指定した技情報を生成する
+    fn move_with_type(input: &str, move_type: &str) -> MoveInfo {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.input = input.to_string();
+        move_info.move_type = move_type.to_string();
+        move_info
+    }
+
+    #[test]
+    fn test_category_entries_unsorted() {
+        // 種別順に並んでいないデータでも分類できること
+        let moves = vec![
+            move_with_type("632146H", "super"),
+            move_with_type("5P", "normal"),
+            move_with_type("236K", "Special"),
+            move_with_type("5K", "Normal"),
+            move_with_type("Spell", "spell"),
+        ];
+        let normals = category_entries(TypeChoice::Normals, &moves, &[], false);
+        assert_eq!(normals, vec!["- **5P**", "- **5K**"]);
+        assert_eq!(
+            category_entries(TypeChoice::Specials, &moves, &[], false).len(),
+            2
+        );
+        assert_eq!(
+            category_entries(TypeChoice::Supers, &moves, &[], false),
+            vec!["- **632146H**"]
+        );
+    }
+
+    #[test]
+    fn test_move_entry() {
+        let moves = create_test_move_info();
+        assert_eq!(
+            move_entry(&moves[0], &["Punch", "P"], true),
+            "- **5P** `4F` `-1` `Mid`\n\t別名 → `Punch`, `P`"
+        );
+        assert_eq!(
+            category_entries(
+                TypeChoice::Normals,
+                &moves,
+                &create_test_move_aliases(),
+                false
+            ),
+            vec!["- **5P**\n\t別名 → `Punch`, `P`"]
+        );
+        let mut no_startup = moves[1].clone();
+        no_startup.startup = None;
+        no_startup.on_block = String::new();
+        assert_eq!(frames_column(&no_startup), "`-` `-` `Mid`");
+    }
+
+    #[test]
+    fn test_paginate_entries() {
+        let entries: Vec<String> = (0..10).map(|i| format!("entry{i:02}")).collect();
+        let pages = paginate_entries(&entries, 25);
+        assert_eq!(pages.len(), 4);
+        assert_eq!(pages[0], "entry00\nentry01\nentry02");
+        assert!(pages.iter().all(|p| p.chars().count() <= 25));
+        assert!(paginate_entries(&[], 25).is_empty());
     }
-    super_moves
 }
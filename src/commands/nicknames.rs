@@ -1,67 +1,272 @@
 //! # nicknames.rs
 //!
-//! キャラクターのニックネーム一覧表示モジュール  
-//! Discordコマンド /nicknames 実装モジュール  
-//! JSONファイルからキャラクターごとのニックネームを取得し、整形後に送信する処理を提供
+//! キャラクターのニックネーム一覧表示・管理モジュール  
+//! Discordコマンド /nicknames・/nickname 実装モジュール  
+//! キャラクターごとのニックネームをページ送りで表示し、Bot 所有者による共通ニックネームの追加・削除を行う処理を提供
 
-use crate::{check, error::AppError, Context, Nicknames};
-use std::{fs, string::String};
+use crate::common::paginate::{paginate, Page};
+use crate::guild::GuildConfig;
+use crate::nickname_store as store;
+use crate::{check, error::AppError, find, Context, Nicknames, EMBED_COLOR};
+use colored::Colorize;
+use poise::serenity_prelude::CreateEmbed;
 
-/// キャラクターごとのニックネーム一覧を表示する処理  
-#[poise::command(prefix_command, slash_command, aliases("n"))]
-pub async fn nicknames(ctx: Context<'_>) -> Result<(), AppError> {
-    // 入力検証および必要なファイルの存在確認処理
-    // adaptive_check: ファイルの整合性確認（失敗時は処理を中断）
-    if (check::adaptive_check(
+/// 一覧 1 ページあたりのキャラクター数
+const CHARACTERS_PER_PAGE: usize = 10;
+
+/// ニックネーム一覧の表示文字列生成関数（例："`SO`, `ソル`"）
+fn nicknames_text(nicknames: &[String]) -> String {
+    if nicknames.is_empty() {
+        return "-".to_string();
+    }
+    nicknames
+        .iter()
+        .map(|nickname| format!("`{nickname}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// ニックネーム一覧ページ生成関数
+fn nickname_pages(entries: &[Nicknames]) -> Vec<Page> {
+    entries
+        .chunks(CHARACTERS_PER_PAGE)
+        .map(|chunk| {
+            let fields = chunk.iter().map(|entry| {
+                (
+                    entry.character.replace('_', " "),
+                    nicknames_text(&entry.nicknames),
+                    false,
+                )
+            });
+            CreateEmbed::new()
+                .color(EMBED_COLOR)
+                .title("__**Character Nicknames**__")
+                .fields(fields)
+                .into()
+        })
+        .collect()
+}
+
+/// ニックネームデータ検証処理（失敗時は中断）
+async fn check_nicknames(ctx: Context<'_>) -> bool {
+    check::adaptive_check(
         ctx,
-        check::CheckOptions::CHARACTER_FOLDERS | check::CheckOptions::CHARACTER_JSONS,
+        check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
     )
-    .await)
-        .is_err()
+    .await
+    .is_ok()
+}
+
+/// キャラクター検索処理（見つからない場合は案内を送信して None）
+async fn resolve_character(ctx: Context<'_>, character: &str) -> Result<Option<String>, AppError> {
+    match find::find_character(
+        &ctx.data().data_dir,
+        character,
+        GuildConfig::for_context(&ctx).as_ref(),
+    )
+    .await
     {
+        Ok(character_arg_altered) if !character_arg_altered.is_empty() => {
+            Ok(Some(character_arg_altered))
+        }
+        Ok(_) => {
+            ctx.say("Pick a single character.").await?;
+            Ok(None)
+        }
+        Err(err) => {
+            ctx.say(err.to_string()).await?;
+            println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+            Ok(None)
+        }
+    }
+}
+
+/// 共通の愛称の変更可否判定処理（poise の `check` 用）
+///
+/// Bot 所有者のみ実行を許可し、それ以外のユーザーにはサーバー独自の愛称設定（`/config nickname`）を案内する。
+async fn owner_only(ctx: Context<'_>) -> Result<bool, AppError> {
+    if ctx.framework().options().owners.contains(&ctx.author().id) {
+        return Ok(true);
+    }
+    ctx.send(
+        poise::CreateReply::default()
+            .content(
+                "Shared nicknames can only be changed by the bot owner. \
+                 Server managers can add server nicknames with `/config nickname add`.",
+            )
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(false)
+}
+
+/// キャラクターごとのニックネーム一覧を表示する処理
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - 表示するキャラクター名または愛称（省略時は全キャラクターをページ送り表示）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command, aliases("n"))]
+pub async fn nicknames(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname. Leave empty for all characters."]
+    character: Option<String>,
+) -> Result<(), AppError> {
+    if !check_nicknames(ctx).await {
         return Ok(());
     }
 
-    // ニックネーム情報JSONファイルの読み込み処理
-    let data_from_file =
-        fs::read_to_string("data/nicknames.json").expect("\nFailed to read 'nicknames.json' file.");
-
-    // JSON文字列を Nicknames 型の Vec にデシリアライズ
-    let vec_nicknames = serde_json::from_str::<Vec<Nicknames>>(&data_from_file).unwrap();
-
-    // Discord 送信用のメッセージ文字列の初期化
-    let mut nicks_as_msg = "__**Character Nicknames**__\n```diff".to_string();
-
-    // ニックネーム情報を整形し、メッセージ文字列に追加
-    for nicknames in vec_nicknames {
-        // キャラクター名の追加
-        nicks_as_msg = nicks_as_msg.clone() + "\n* Character: " + &nicknames.character.to_string();
-
-        // ニックネームの追加
-        nicks_as_msg += "\n+ Nicknames: ";
-
-        for x in 0..nicknames.nicknames.len() {
-            if x == nicknames.nicknames.len() - 1 {
-                nicks_as_msg.push_str(&nicknames.nicknames[x]);
-            } else {
-                // 空のニックネームが含まれている場合を考慮し、カンマ区切りで追加
-                if nicknames.nicknames[x].is_empty() {
-                    nicks_as_msg.push_str(&nicknames.nicknames[x]);
-                } else {
-                    nicks_as_msg.push_str(&nicknames.nicknames[x]);
-                    nicks_as_msg.push_str(", ");
-                }
-            }
-        }
-        // 各キャラクターのニックネーム情報の終了マーク
-        nicks_as_msg = nicks_as_msg.clone() + ".\n";
+    let entries = store::load(&ctx.data().data_dir)?;
+    let Some(character) = character else {
+        return paginate(ctx, nickname_pages(&entries)).await;
+    };
+
+    let Some(character_arg_altered) = resolve_character(ctx, &character).await? else {
+        return Ok(());
+    };
+    let text = entries
+        .iter()
+        .find(|entry| entry.character == character_arg_altered)
+        .map_or_else(|| "-".to_string(), |entry| nicknames_text(&entry.nicknames));
+    let embed = CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .title(format!(
+            "__**{} Nicknames**__",
+            character_arg_altered.replace('_', " ")
+        ))
+        .description(text);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// ニックネーム管理コマンド
+///
+/// # 概要
+/// 以下のサブコマンドを提供する（共通の愛称を変更するため Bot 所有者のみ）：
+/// - add: ニックネームを追加
+/// - remove: ニックネームを削除
+#[poise::command(
+    prefix_command,
+    slash_command,
+    check = "owner_only",
+    subcommands("add", "remove"),
+    subcommand_required
+)]
+pub async fn nickname(_: Context<'_>) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// ニックネームを追加するコマンド処理
+///
+/// 他キャラクターの愛称・正式名と重複する場合は追加しない。
+#[poise::command(prefix_command, slash_command, check = "owner_only")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname."] character: String,
+    #[rest]
+    #[description = "Nickname to add."]
+    nickname: String,
+) -> Result<(), AppError> {
+    if !check_nicknames(ctx).await {
+        return Ok(());
     }
+    let Some(character_arg_altered) = resolve_character(ctx, &character).await? else {
+        return Ok(());
+    };
 
-    // メッセージ終端のマーク
-    nicks_as_msg += "```";
+    let added = store::update(&ctx.data().data_dir, |entries| {
+        Ok(store::add_nickname(
+            entries,
+            &character_arg_altered,
+            &nickname,
+        ))
+    })?;
+    match added {
+        Ok(()) => {
+            println!(
+                "{}",
+                format!("愛称追加: {character_arg_altered} ← {}", nickname.trim()).yellow()
+            );
+            ctx.say(format!(
+                "Added `{}` to `{}`.",
+                nickname.trim(),
+                character_arg_altered
+            ))
+            .await?;
+        }
+        Err(msg) => {
+            ctx.say(msg).await?;
+        }
+    }
+    Ok(())
+}
 
-    // Discordへメッセージを送信
-    ctx.say(&nicks_as_msg).await?;
+/// ニックネームを削除するコマンド処理
+#[poise::command(prefix_command, slash_command, check = "owner_only")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname."] character: String,
+    #[rest]
+    #[description = "Nickname to remove."]
+    nickname: String,
+) -> Result<(), AppError> {
+    if !check_nicknames(ctx).await {
+        return Ok(());
+    }
+    let Some(character_arg_altered) = resolve_character(ctx, &character).await? else {
+        return Ok(());
+    };
 
+    let removed = store::update(&ctx.data().data_dir, |entries| {
+        Ok(store::remove_nickname(
+            entries,
+            &character_arg_altered,
+            &nickname,
+        ))
+    })?;
+    match removed {
+        Ok(removed) => {
+            println!(
+                "{}",
+                format!("愛称削除: {character_arg_altered} ← {removed}").yellow()
+            );
+            ctx.say(format!(
+                "Removed `{removed}` from `{character_arg_altered}`."
+            ))
+            .await?;
+        }
+        Err(msg) => {
+            ctx.say(msg).await?;
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nicknames_text() {
+        assert_eq!(
+            nicknames_text(&["SO".to_string(), "ソル".to_string()]),
+            "`SO`, `ソル`"
+        );
+        assert_eq!(nicknames_text(&[]), "-");
+    }
+
+    #[test]
+    fn test_nickname_pages() {
+        let entries: Vec<Nicknames> = (0..25)
+            .map(|i| Nicknames {
+                character: format!("Char_{i}"),
+                nicknames: vec![format!("c{i}")],
+            })
+            .collect();
+        assert_eq!(nickname_pages(&entries).len(), 3);
+    }
+}
@@ -0,0 +1,238 @@
+//! # oki.rs
+//!
+//! 起き攻め計算コマンドモジュール  
+//! Discordコマンド /oki 実装モジュール  
+//! ダウン技の後に移動・空振り等の行動を挟んだ場合に、相手の起き上がりへ重なる技を表示する処理を提供
+
+use crate::calc::oki::{self, Meaty, Movement, OkiAction};
+use crate::common::resolve;
+use crate::guild::GuildConfig;
+use crate::{check, error::AppError, find, utils, Context, EMBED_COLOR};
+use colored::Colorize;
+use poise::serenity_prelude::CreateEmbed;
+
+/// 挟む行動の最大数
+const MAX_OKI_ACTIONS: usize = 10;
+
+/// 表示する重なる技の最大数
+const MAX_MEATY_LINES: usize = 25;
+
+/// 起き攻め計算結果の説明文生成関数
+///
+/// # 引数
+/// * `advantage` - ダウン時の硬直差
+/// * `actions` - 挟んだ行動（入力順）
+/// * `meaties` - 重なる技の一覧
+///
+/// # 戻り値
+/// 埋め込みメッセージの説明文
+fn oki_description(advantage: i32, actions: &[OkiAction], meaties: &[Meaty]) -> String {
+    let mut description = format!("Knockdown: **+{advantage}**\n");
+    for action in actions {
+        let marker = if action.estimated { "*" } else { "" };
+        description += &format!("− {}{marker} ({}F)\n", action.label, action.frames);
+    }
+    if actions.iter().any(|action| action.estimated) {
+        description +=
+            "`*` Generic movement frames (dash always, jump/backdash without character data).\n";
+    }
+    let remaining = advantage - actions.iter().map(|a| a.frames as i32).sum::<i32>();
+    if remaining < 0 {
+        description += &format!(
+            "**{}F too late**: the opponent wakes up before you can act.",
+            -remaining
+        );
+        return description;
+    }
+    description += &format!(
+        "= **{remaining}F** left, the opponent wakes up on frame {}.\n\n",
+        remaining + 1
+    );
+
+    if meaties.is_empty() {
+        description += "No move is meaty with this timing.";
+        return description;
+    }
+    description += "__**Meaty moves**__\n";
+    for meaty in meaties.iter().take(MAX_MEATY_LINES) {
+        description += &format!(
+            "`{}` (startup {}F): meaty by {}, {} active frame(s) left\n",
+            meaty.input, meaty.startup, meaty.meaty_by, meaty.active_left
+        );
+    }
+    if meaties.len() > MAX_MEATY_LINES {
+        description += &format!("…and {} more", meaties.len() - MAX_MEATY_LINES);
+    }
+    description
+}
+
+/// 起き攻めの重ねを計算するコマンド処理
+///
+/// # 概要
+/// ダウン技のダウン時の硬直差から、">" 区切りで指定した行動（dash・jump・backdash・待機フレーム数・技の空振り）の時間を差し引き、
+/// 行動直後に出して相手の起き上がりに持続が重なる技を一覧表示する。
+/// ジャンプ・バックステップは `character.json`（`/update` で取得）のフレーム数を用い、ダッシュは全キャラクター共通の汎用値を用いる。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character` - キャラクター名または愛称
+/// * `knockdown` - ダウン技の名前・入力またはエイリアス
+/// * `actions` - 挟む行動（">" 区切り）
+///
+/// # 戻り値
+/// 処理結果 `Result<(), AppError>`
+#[poise::command(prefix_command, slash_command)]
+pub async fn oki(
+    ctx: Context<'_>,
+    #[min_length = 2]
+    #[description = "Character name or nickname."]
+    character: String,
+    #[min_length = 2]
+    #[description = "Knockdown move name, input or alias."]
+    knockdown: String,
+    #[description = "Actions separated by '>' (dash, jump, backdash, frames or a move). Dash frames are generic."]
+    actions: Option<String>,
+) -> Result<(), AppError> {
+    println!(
+        "{}",
+        ("Command Args: '".to_owned()
+            + &character
+            + ", "
+            + &knockdown
+            + ", "
+            + actions.as_deref().unwrap_or_default()
+            + "'")
+            .purple()
+    );
+
+    if (check::adaptive_check(
+        ctx,
+        check::CheckOptions::DATA_FOLDER
+            | check::CheckOptions::NICKNAMES_JSON
+            | check::CheckOptions::CHARACTER_FOLDERS
+            | check::CheckOptions::CHARACTER_JSONS,
+    )
+    .await)
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    // キャラクター検索
+    let guild = GuildConfig::for_context(&ctx);
+    let character_arg_altered =
+        match find::find_character(&ctx.data().data_dir, &character, guild.as_ref()).await {
+            Ok(character_arg_altered) => character_arg_altered,
+            Err(err) => {
+                ctx.say(err.to_string()).await?;
+                println!("{}", ("Error: ".to_owned() + &err.to_string()).red());
+                return Ok(());
+            }
+        };
+
+    let data_dir = &ctx.data().data_dir;
+    let moves_info = utils::load_move_info(data_dir, &character_arg_altered)?;
+    // 移動フレーム（キャラクター情報に無い項目・ダッシュは汎用値を用いて説明文に明記）
+    let movement = utils::load_character_info(data_dir, &character_arg_altered).map_or_else(
+        |_| Movement::default(),
+        |info| Movement::from_char_info(&info),
+    );
+
+    // ダウン技
+    let Some(knockdown_move) =
+        resolve::resolve_moves(&ctx, &character_arg_altered, &[&knockdown], &moves_info)
+            .await?
+            .and_then(|moves| moves.into_iter().next())
+    else {
+        return Ok(());
+    };
+    let Some(advantage) = oki::parse_knockdown(&knockdown_move.on_hit).and_then(|kd| kd.advantage)
+    else {
+        ctx.say(format!(
+            "`{}` has no knockdown advantage in its on-hit data (`{}`).",
+            knockdown_move.input, knockdown_move.on_hit
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    // 挟む行動
+    let parts = actions
+        .as_deref()
+        .map(utils::split_sequence)
+        .unwrap_or_default();
+    if parts.len() > MAX_OKI_ACTIONS {
+        ctx.say(format!("Specify up to {MAX_OKI_ACTIONS} actions."))
+            .await?;
+        return Ok(());
+    }
+    let mut oki_actions = Vec::new();
+    for part in parts {
+        if let Some(action) = OkiAction::parse(part, &movement) {
+            oki_actions.push(action);
+            continue;
+        }
+        let Some(whiff_move) =
+            resolve::resolve_moves(&ctx, &character_arg_altered, &[part], &moves_info).await?
+        else {
+            return Ok(());
+        };
+        let Some(whiff) = OkiAction::whiff(&whiff_move[0]) else {
+            ctx.say(format!("The total frames of `{part}` are unknown."))
+                .await?;
+            return Ok(());
+        };
+        oki_actions.push(whiff);
+    }
+
+    let remaining = advantage - oki_actions.iter().map(|a| a.frames as i32).sum::<i32>();
+    let meaties = oki::meaty_moves(remaining, &moves_info);
+    let embed = CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .title(format!(
+            "__**{}：{} ({})**__",
+            character_arg_altered, knockdown_move.input, knockdown_move.on_hit
+        ))
+        .url("https://dustloop.com/w/GGST/".to_owned() + &character_arg_altered + "#Overview")
+        .description(utils::truncate_string(
+            &oki_description(advantage, &oki_actions, &meaties),
+            4096,
+        ));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oki_description() {
+        let actions = vec![OkiAction {
+            label: "Dash".to_string(),
+            frames: 10,
+            estimated: false,
+        }];
+        let meaty = Meaty {
+            input: "5K".to_string(),
+            startup: 5,
+            meaty_by: 2,
+            active_left: 1,
+        };
+        let description = oki_description(20, &actions, &[meaty]);
+        assert!(description.contains("**10F** left"));
+        assert!(description.contains("`5K` (startup 5F): meaty by 2"));
+
+        let description = oki_description(5, &actions, &[]);
+        assert!(description.contains("**5F too late**"));
+        assert!(!description.contains("Generic movement frames"));
+
+        let estimated = vec![OkiAction {
+            estimated: true,
+            ..actions[0].clone()
+        }];
+        let description = oki_description(20, &estimated, &[]);
+        assert!(description.contains("− Dash* (10F)"));
+        assert!(description.contains("`*` Generic movement frames"));
+    }
+}
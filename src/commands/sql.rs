@@ -0,0 +1,88 @@
+//! # sql.rs
+//!
+//! SQLite データベース操作モジュール（`sqlite` 機能有効時のみ）
+//! Discordコマンド /sql 実装モジュール
+//! JSON データからのデータベース再構築と、読み取り専用 SQL による問い合わせを提供
+//! このコマンドは、Botの所有者のみ実行可能
+
+use crate::database::{self, MAX_QUERY_ROWS, QUERY_TIME_LIMIT};
+use crate::{
+    error::{AppError, Result},
+    utils, Context,
+};
+use colored::Colorize;
+
+/// 返信本文の最大文字数（コードブロック記法を含めて 2000 文字未満）
+const MAX_REPLY_LEN: usize = 1900;
+
+/// データベース操作コマンド
+#[poise::command(
+    prefix_command,
+    slash_command,
+    owners_only,
+    subcommands("query", "rebuild"),
+    subcommand_required
+)]
+pub async fn sql(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// 読み取り専用 SQL を実行し、結果を表形式で表示する
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn query(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "読み取り専用の SQL 文（SELECT 等）"]
+    sql: String,
+) -> Result<()> {
+    println!("{}", ("Command Args: '".to_owned() + &sql + "'").purple());
+
+    let sql = sql
+        .trim()
+        .trim_start_matches("```sql")
+        .trim_matches('`')
+        .to_string();
+    // 同期処理のため専用スレッドで実行（制限時間超過時は中断）
+    let data_dir = ctx.data().data_dir.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        database::query_read_only(&data_dir, &sql, MAX_QUERY_ROWS, QUERY_TIME_LIMIT)
+    })
+    .await
+    .map_err(|e| AppError::Database(format!("問い合わせに失敗しました: {e}")))?;
+    let result = match result {
+        Ok(result) => result,
+        Err(err @ (AppError::Database(_) | AppError::FileNotFound(_))) => {
+            ctx.say(format!("Query failed: {err}")).await?;
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+
+    let mut text = format!(
+        "```\n{}\n```",
+        utils::truncate_string(&result.to_table(), MAX_REPLY_LEN)
+    );
+    text += &format!("{} row(s)", result.rows.len());
+    if result.truncated {
+        text += &format!(" (limited to {MAX_QUERY_ROWS})");
+    }
+    ctx.say(text).await?;
+    Ok(())
+}
+
+/// JSON データからデータベースを再構築する
+#[poise::command(prefix_command, slash_command, owners_only)]
+pub async fn rebuild(ctx: Context<'_>) -> Result<()> {
+    let data_dir = ctx.data().data_dir.clone();
+    let summary =
+        tokio::task::spawn_blocking(move || database::rebuild(&data_dir, "all", "rebuild"))
+            .await
+            .map_err(|e| AppError::Database(format!("再構築に失敗しました: {e}")))??;
+
+    ctx.say(format!(
+        "Database rebuilt: {} character(s), {} move(s), {} alias(es), {} nickname(s), {} image(s).",
+        summary.characters, summary.moves, summary.aliases, summary.nicknames, summary.images
+    ))
+    .await?;
+    Ok(())
+}
@@ -8,32 +8,68 @@ mod framedata; // framedata.rs モジュール　フレームデータ更新処
 mod framedata_json; // framedata_json.rs モジュール　フレームデータJSON変換処理群
 mod images; // images.rs モジュール　画像データ更新処理群
 mod images_json; // images_json.rs モジュール　画像データJSON変換処理群
+mod info; // info.rs モジュール　キャラクター情報更新処理群
+mod info_json; // info_json.rs モジュール　キャラクター情報JSON変換処理群
+pub mod pipeline; // pipeline.rs モジュール　取得・差分・書き込み処理群（コマンドラインツール用）
 
-use crate::{check, error::Result, find, Context, CHARS}; // 共通チェック関数、検索関数、型定義群
+use crate::guild::GuildConfig;
+use crate::{
+    check,
+    error::{AppError, Result},
+    find, Context, CHARS,
+}; // 共通チェック関数、検索関数、型定義群
 use colored::Colorize; // 文字色変換機能
+use std::path::Path; // パス操作
 
-/// 更新対象選択列挙体
-#[derive(Debug, poise::ChoiceParameter)]
-pub enum UpdateChoice {
-    #[name = "all"]
-    All, // 全更新選択
-    #[name = "frames"]
-    Frames, // フレーム更新選択
-    #[name = "images"]
-    Images, // 画像更新選択
+/// 更新実行・結果通知関数
+///
+/// # 概要
+/// 対象キャラクターのフレームデータ・画像データ・キャラクター情報をデータディレクトリへ更新し、
+/// 失敗したキャラクターがあればその一覧を、無ければ完了を通知する。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `characters` - 対象キャラクター（正式名）
+async fn run_and_report(ctx: Context<'_>, characters: Vec<String>) -> Result<()> {
+    ctx.say("Update started!").await?; // 更新開始通知
+
+    let options = pipeline::UpdateOptions {
+        characters,
+        kinds: vec![
+            pipeline::UpdateKind::Frames,
+            pipeline::UpdateKind::Images,
+            pipeline::UpdateKind::Info,
+        ],
+        dry_run: false,
+        output_dir: Path::new(&ctx.data().data_dir).to_path_buf(),
+    };
+    let (_, failures) = pipeline::run_update(&options).await;
+
+    if failures.is_empty() {
+        ctx.say("Update succesful!").await?; // 更新完了通知
+    } else {
+        let lines: Vec<String> = failures
+            .iter()
+            .map(|(target, err)| format!("- {target}: {err}"))
+            .collect();
+        ctx.say(format!(
+            "Update finished with errors:\n{}",
+            lines.join("\n")
+        ))
+        .await?; // 失敗対象通知
+    }
+
+    Ok(())
 }
 
 /// アップデートコマンド本体  
 /// ロール名：BotOwner が必須
 #[poise::command(prefix_command, slash_command, owners_only)]
 pub async fn update(ctx: Context<'_>) -> Result<()> {
-    // 各種チェック実行（データフォルダ、JSONファイル等の存在確認）
+    // 各種チェック実行（欠損したキャラクターデータを復元できるよう、キャラクター単位の確認は行わない）
     if (check::adaptive_check(
         ctx,
-        check::CheckOptions::DATA_FOLDER
-            | check::CheckOptions::NICKNAMES_JSON
-            | check::CheckOptions::CHARACTER_FOLDERS
-            | check::CheckOptions::CHARACTER_JSONS,
+        check::CheckOptions::DATA_FOLDER | check::CheckOptions::NICKNAMES_JSON,
     )
     .await)
         .is_err()
@@ -41,15 +77,8 @@ pub async fn update(ctx: Context<'_>) -> Result<()> {
         return Ok(());
     }
 
-    ctx.say("Update started!").await?; // 更新開始通知
-
     // 全キャラクター情報更新
-    framedata::get_char_data(&CHARS, "all").await; // フレームデータ更新
-    images::get_char_images(&CHARS, "all").await; // 画像データ更新
-
-    ctx.say("Update succesful!").await?; // 更新完了通知
-
-    Ok(()) // 正常終了
+    run_and_report(ctx, CHARS.iter().map(|c| c.to_string()).collect()).await
 }
 
 /// キャラクター別アップデートサブコマンド  
@@ -58,13 +87,10 @@ pub async fn character(
     ctx: Context<'_>,
     #[description = "対象キャラクター名"] character: String,
 ) -> Result<()> {
-    // 各種チェック実行（データフォルダ、JSONファイル等の存在確認）
+    // 各種チェック実行（欠損したキャラクターデータを復元できるよう、キャラクター単位の確認は行わない）
     if (check::adaptive_check(
         ctx,
-        check::CheckOptions::DATA_FOLDER
-            | check::CheckOptions::NICKNAMES_JSON
-            | check::CheckOptions::CHARACTER_FOLDERS
-            | check::CheckOptions::CHARACTER_JSONS,
+        check::CheckOptions::DATA_FOLDER | check::CheckOptions::NICKNAMES_JSON,
     )
     .await)
         .is_err()
@@ -73,7 +99,13 @@ pub async fn character(
     }
 
     // キャラクター探索処理　find関数呼出
-    let character_arg_altered = match find::find_character(&character).await {
+    let character_arg_altered = match find::find_character(
+        &ctx.data().data_dir,
+        &character,
+        GuildConfig::for_context(&ctx).as_ref(),
+    )
+    .await
+    {
         Ok(character_arg_altered) => character_arg_altered, // キャラクター名称取得
         Err(err) => {
             ctx.say(err.to_string()).await?; // エラーメッセージ送信
@@ -82,12 +114,23 @@ pub async fn character(
         }
     };
 
-    // 更新対象分岐処理
-    ctx.say("Update started!").await?; // 更新開始通知
-    framedata::get_char_data(&CHARS, &character_arg_altered).await; // フレームデータ更新
-    images::get_char_images(&CHARS, &character_arg_altered).await; // 画像データ更新
+    // 指定キャラクター情報更新
+    run_and_report(ctx, vec![character_arg_altered]).await
+}
 
-    ctx.say("Update succesful!").await?; // 更新完了通知
+/// 欠損データ自動修復関数  
+/// 指定キャラクターのフレームデータと画像データを再取得し、データディレクトリへ書き込む。  
+/// キャラクターフォルダが無い場合は作成する。
+pub async fn repair_characters(data_dir: &str, characters: &[String]) -> Result<()> {
+    for character in characters {
+        for kind in [pipeline::UpdateKind::Frames, pipeline::UpdateKind::Images] {
+            pipeline::update_character(character, kind, Path::new(data_dir), false)
+                .await
+                .map_err(|e| {
+                    AppError::DataProcessing(format!("{character} の再取得に失敗しました: {e}"))
+                })?;
+        }
+    }
 
     Ok(()) // 正常終了
 }
@@ -8,10 +8,9 @@
 
 // 外部クレートおよびモジュールのインポート
 use crate::common::preprocess;
-use crate::{MoveInfo, CHARS}; // MoveInfo構造体、キャラクター定数群
+use crate::error::Result;
+use crate::MoveInfo; // MoveInfo構造体
 use serde::Deserialize; // JSONデシリアライズ用
-use std::fs::File; // ファイル操作用
-use std::io::Write; // ファイル書き込み用
 
 extern crate ureq; // HTTPクライアント（参考用）
 
@@ -110,8 +109,7 @@ fn should_skip_move(move_data: &Data) -> bool {
     }
 
     // 特定の技名称の場合、処理スキップ
-    if move_data.title.name.is_some() {
-        let name = move_data.title.name.as_ref().unwrap();
+    if let Some(name) = &move_data.title.name {
         if name == "Dash Cancel"
             || name == "Hoverdash"
             || name == "Finish Blow"
@@ -367,12 +365,14 @@ fn create_move_info(move_data: &Data, empty: &str) -> MoveInfo {
     }
 }
 
-/// フレームデータをJSON形式に変換するメイン関数
-pub async fn frames_to_json(
-    mut char_page_response_json: String,
-    mut file: &File,
-    char_count: usize,
-) {
+/// Dustloop の応答 JSON を技情報一覧へ変換する関数（ファイルへは書き込まない）
+///
+/// # 引数
+/// * `char_page_response_json` - Dustloop API の応答 JSON 文字列
+///
+/// # 戻り値
+/// 変換済みの技情報一覧、応答を解析できない場合はエラー
+pub async fn parse_frames(mut char_page_response_json: String) -> Result<Vec<MoveInfo>> {
     // "-" をプレースホルダーとして設定
     let empty = String::from("-");
 
@@ -380,7 +380,7 @@ pub async fn frames_to_json(
     char_page_response_json = remove_tags(char_page_response_json).await;
 
     // JSON 文字列を Response 構造体へデシリアライズ　結果：move_data_response 取得
-    let mut move_data_response: Response = serde_json::from_str(&char_page_response_json).unwrap();
+    let mut move_data_response: Response = serde_json::from_str(&char_page_response_json)?;
     // 技情報群の可変参照取得　結果：char_move_data
     let char_move_data = &mut move_data_response.cargoquery;
     // MoveInfo 変換済みデータ格納用ベクター初期化　結果：vec_processed_moves_info
@@ -401,7 +401,5 @@ pub async fn frames_to_json(
         vec_processed_moves_info.push(processed_moves_info);
     }
 
-    // 変換済み MoveInfo ベクターを整形済み JSON としてファイルへ書き込み
-    file.write_all(&(serde_json::to_vec_pretty(&vec_processed_moves_info).unwrap()))
-        .expect(&("\nFailed to serialize '".to_owned() + CHARS[char_count] + ".json'."));
+    Ok(vec_processed_moves_info)
 }
@@ -7,10 +7,10 @@
 
 extern crate ureq;
 use crate::common::preprocess; // JSON 前処理関数群の利用
-use crate::{ImageLinks, CHARS}; // ImageLinks 構造体およびキャラクター定数群の利用
+use crate::error::Result;
+use crate::ImageLinks; // ImageLinks 構造体の利用
 use md5::{Digest, Md5}; // MD5 ハッシュ計算用
 use serde::Deserialize; // JSON デシリアライズ用
-use std::{fs::File, io::Write}; // ファイル操作および書き込み用
 
 // ======================================================================
 // JSON デシリアライズ用構造体定義
@@ -131,7 +131,7 @@ async fn process_image_data(image_data: &mut ImageData) -> ImageLinks {
     // 入力文字列が未定義の場合は空文字に置換
     if image_data.title.input.is_none() {
         image_data.title.input = Some(String::new());
-    } else if *image_data.title.input.as_ref().unwrap() == "j.XX during Homing Jump" {
+    } else if image_data.title.input.as_deref() == Some("j.XX during Homing Jump") {
         // 特定の入力（"j.XX during Homing Jump"）の場合は空のデータを返す
         return ImageLinks {
             input: String::new(),
@@ -162,13 +162,19 @@ async fn process_image_data(image_data: &mut ImageData) -> ImageLinks {
     }
 }
 
-/// メイン処理：キャラクター画像データをJSONファイルに変換する関数
-pub async fn images_to_json(char_images_response_json: String, mut file: &File, char_count: usize) {
+/// Dustloop の応答 JSON を画像リンク一覧へ変換する関数（ファイルへは書き込まない）
+///
+/// # 引数
+/// * `char_images_response_json` - Dustloop API の応答 JSON 文字列
+///
+/// # 戻り値
+/// 変換済みの画像リンク一覧、応答を解析できない場合はエラー
+pub async fn parse_images(char_images_response_json: String) -> Result<Vec<ImageLinks>> {
     // JSONデータを前処理
     let preprocessed_json = preprocess_images_json(char_images_response_json).await;
 
     // JSON 文字列を ImageResponse 構造体にデシリアライズ　結果：image_data_response
-    let mut image_data_response: ImageResponse = serde_json::from_str(&preprocessed_json).unwrap();
+    let mut image_data_response: ImageResponse = serde_json::from_str(&preprocessed_json)?;
 
     // 画像データエントリの可変参照取得　結果：char_image_data
     let char_image_data = &mut image_data_response.cargoquery;
@@ -200,9 +206,7 @@ pub async fn images_to_json(char_images_response_json: String, mut file: &File,
         vec_processed_imagedata.push(processed_imagedata);
     }
 
-    // 変換済みの ImageLinks ベクターを整形済み JSON としてファイルへ書き込み
-    file.write_all(&(serde_json::to_vec_pretty(&vec_processed_imagedata).unwrap()))
-        .expect(&("\nFailed to serialize '".to_owned() + CHARS[char_count] + ".json'."));
+    Ok(vec_processed_imagedata)
 }
 
 /// 画像リンクを生成する関数
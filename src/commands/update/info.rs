@@ -0,0 +1,58 @@
+//! # info.rs
+//!
+//! キャラクター情報更新モジュール。
+//! Dustloopウェブサイトからキャラクターの防御値・ガッツ・移動性能を取得するためのリクエストリンクを提供する。
+//! 取得・書き込み処理は `pipeline.rs` が行う。
+
+/// Dustloop の項目名と `CharInfo` の項目名の対応
+pub const FIELDS: [(&str, &str); 30] = [
+    ("defense", "defense"),
+    ("guts", "guts"),
+    ("guardBalance", "guard_balance"),
+    ("prejump", "prejump"),
+    ("umo", "umo"),
+    ("forwarddash", "forward_dash"),
+    ("backdash", "backdash"),
+    ("backdashDuration", "backdash_duration"),
+    ("backdashInvincibility", "backdash_invincibility"),
+    ("backdashAirborne", "backdash_airborne"),
+    ("backdashDistance", "backdash_distance"),
+    ("jumpDuration", "jump_duration"),
+    ("jumpHeight", "jump_height"),
+    ("highjumpDuration", "high_jump_duration"),
+    ("highjumpHeight", "high_jump_height"),
+    ("earliestIAD", "earliest_iad"),
+    ("ADDuration", "ad_duration"),
+    ("ADDistance", "ad_distance"),
+    ("ABDDuration", "abd_duration"),
+    ("ABDDistance", "abd_distance"),
+    ("movementTension", "movement_tension"),
+    ("jumpTension", "jump_tension"),
+    ("airdashTension", "airdash_tension"),
+    ("walkSpeed", "walk_speed"),
+    ("backWalkSpeed", "back_walk_speed"),
+    ("dashinitialSpeed", "dash_initial_speed"),
+    ("dashAcceleration", "dash_acceleration"),
+    ("dashFriction", "dash_friction"),
+    ("jumpGravity", "jump_gravity"),
+    ("highjumpGravity", "high_jump_gravity"),
+];
+
+/// Dustloop API リクエスト前半部
+const SITE_LINK: &str =
+    "https://www.dustloop.com/wiki/api.php?action=cargoquery&format=json&limit=1&tables=ggstCharacters&fields=";
+
+/// Dustloop API のリクエストリンク生成関数（キャラクター情報）
+///
+/// 応答の項目名が `CharInfo` の項目名となるよう、各項目に別名を付けて要求する。
+pub fn character_link(char_id: &str) -> String {
+    let fields: Vec<String> = FIELDS
+        .iter()
+        .map(|(field, alias)| format!("ggstCharacters.{field}%3D{alias}"))
+        .collect();
+    SITE_LINK.to_owned()
+        + &fields.join("%2C")
+        + "&where=ggstCharacters.name%3D%22"
+        + &char_id.replace('_', "%20")
+        + "%22"
+}
@@ -0,0 +1,49 @@
+//! `info_json.rs`
+//!
+//! Dustloop Wiki から取得したキャラクター情報 JSON を `CharInfo` 構造体へ変換するための機能群。
+
+use super::info::FIELDS;
+use crate::error::{AppError, Result};
+use crate::CharInfo;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug)]
+struct Response {
+    cargoquery: Vec<Data>, // 検索結果（1 件）
+}
+
+#[derive(Deserialize, Debug)]
+struct Data {
+    title: Map<String, Value>, // 項目名（別名）→ 値
+}
+
+/// Dustloop の応答 JSON をキャラクター情報へ変換する関数（ファイルへは書き込まない）
+///
+/// # 引数
+/// * `char_info_response_json` - Dustloop API の応答 JSON 文字列
+///
+/// # 戻り値
+/// 変換済みのキャラクター情報、応答を解析できない・該当キャラクターが無い場合はエラー
+pub async fn parse_info(char_info_response_json: String) -> Result<CharInfo> {
+    let response: Response = serde_json::from_str(&char_info_response_json)?;
+    let Some(data) = response.cargoquery.into_iter().next() else {
+        return Err(AppError::DataProcessing(
+            "Dustloop にキャラクター情報がありません".to_string(),
+        ));
+    };
+
+    // 応答に無い項目・null は空文字として扱う（数値項目は None になる）
+    let title: Map<String, Value> = FIELDS
+        .iter()
+        .map(|(_, alias)| {
+            let value = match data.title.get(*alias) {
+                Some(Value::String(s)) => Value::String(s.clone()),
+                Some(Value::Null) | None => Value::String(String::new()),
+                Some(other) => Value::String(other.to_string()),
+            };
+            ((*alias).to_string(), value)
+        })
+        .collect();
+    Ok(serde_json::from_value(Value::Object(title))?)
+}
@@ -0,0 +1,526 @@
+//! `pipeline.rs`
+//!
+//! Dustloop からのデータ更新処理（取得・変換・差分計算・書き込み）を Discord に依存しない形で提供する。
+//! 出力先ディレクトリの指定と、書き込まずに差分のみを返すドライランに対応し、コマンドラインツールと `/update` から利用する。
+
+use super::{framedata, framedata_json, images, images_json, info, info_json};
+use crate::error::{AppError, Result};
+use crate::{utils, CharInfo, ImageLinks, MoveInfo};
+use colored::Colorize;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 500 エラー時の最大再試行回数
+const MAX_RETRIES: usize = 5;
+
+/// 更新対象データ種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateKind {
+    /// フレームデータ（`<キャラクター名>.json`）
+    Frames,
+    /// 画像データ（`images.json`）
+    Images,
+    /// キャラクター情報（`character.json`）
+    Info,
+}
+
+impl UpdateKind {
+    /// 表示名を返す。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Frames => "frames",
+            Self::Images => "images",
+            Self::Info => "info",
+        }
+    }
+
+    /// 出力ファイルパスを返す。
+    pub fn path(self, output_dir: &Path, character: &str) -> PathBuf {
+        let char_dir = output_dir.join(character);
+        match self {
+            Self::Frames => char_dir.join(character.to_owned() + ".json"),
+            Self::Images => char_dir.join("images.json"),
+            Self::Info => char_dir.join("character.json"),
+        }
+    }
+}
+
+/// 更新設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateOptions {
+    /// 対象キャラクター（正式名）
+    pub characters: Vec<String>,
+    /// 対象データ種別
+    pub kinds: Vec<UpdateKind>,
+    /// 書き込まずに差分のみを返すか否か
+    pub dry_run: bool,
+    /// 出力先ディレクトリ（差分はこのディレクトリ内の既存ファイルと比較する）
+    pub output_dir: PathBuf,
+}
+
+/// 差分種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// 追加
+    Added,
+    /// 削除
+    Removed,
+    /// 変更
+    Modified,
+}
+
+impl ChangeKind {
+    /// 表示記号を返す。
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Added => "+",
+            Self::Removed => "-",
+            Self::Modified => "~",
+        }
+    }
+}
+
+/// 技 1 件分の差分
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// 差分種別
+    pub kind: ChangeKind,
+    /// 技入力（同一入力が複数ある場合は " #2" 等を付加）
+    pub input: String,
+    /// 変更された項目（項目名, 変更前, 変更後）
+    pub fields: Vec<(String, String, String)>,
+}
+
+/// キャラクター・データ種別毎の更新結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateReport {
+    /// キャラクター名
+    pub character: String,
+    /// データ種別
+    pub kind: UpdateKind,
+    /// 出力ファイルパス
+    pub path: PathBuf,
+    /// 差分
+    pub changes: Vec<Change>,
+    /// ファイルへ書き込んだか否か
+    pub written: bool,
+}
+
+impl UpdateReport {
+    /// 差分種別毎の件数を返す。
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+/// Dustloop API 取得関数（500 エラー時は再試行）
+pub fn fetch(link: &str) -> Result<String> {
+    let mut attempt = 0;
+    loop {
+        match ureq::get(link).call() {
+            Ok(response) => return Ok(response.into_string()?),
+            Err(ureq::Error::Status(500, _)) if attempt < MAX_RETRIES => attempt += 1,
+            Err(e) => {
+                return Err(AppError::DataProcessing(format!(
+                    "Dustloop からの取得に失敗しました: {e}"
+                )))
+            }
+        }
+    }
+}
+
+/// 表示用の値変換関数（文字列はそのまま、null は "-"）
+fn value_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "-".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// 比較用キー付きの項目一覧を生成する（同一キーの 2 件目以降は " #n" を付加）。
+fn keyed_records<T: Serialize>(
+    records: &[T],
+    key: impl Fn(&T) -> &str,
+) -> Result<Vec<(String, serde_json::Map<String, Value>)>> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    records
+        .iter()
+        .map(|record| {
+            let count = seen.entry(key(record).to_string()).or_insert(0);
+            *count += 1;
+            let key = if *count == 1 {
+                key(record).to_string()
+            } else {
+                format!("{} #{count}", key(record))
+            };
+            let Value::Object(map) = serde_json::to_value(record)? else {
+                return Err(AppError::DataProcessing(format!(
+                    "{key} をオブジェクトへ変換できません"
+                )));
+            };
+            Ok((key, map))
+        })
+        .collect()
+}
+
+/// 差分計算関数
+///
+/// # 概要
+/// 技入力をキーとして変更前後の一覧を比較し、追加・変更（新しい一覧の順）、削除（変更前の一覧の順）を返す。
+///
+/// # 引数
+/// * `old` - 変更前の一覧
+/// * `new` - 変更後の一覧
+/// * `key` - 比較キー（技入力）取得関数
+///
+/// # 戻り値
+/// 差分一覧
+pub fn diff_records<T: Serialize>(
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> &str + Copy,
+) -> Result<Vec<Change>> {
+    let old = keyed_records(old, key)?;
+    let new = keyed_records(new, key)?;
+    let old_map: HashMap<&str, &serde_json::Map<String, Value>> =
+        old.iter().map(|(k, v)| (k.as_str(), v)).collect();
+
+    let mut changes = Vec::new();
+    for (input, record) in &new {
+        let Some(previous) = old_map.get(input.as_str()) else {
+            changes.push(Change {
+                kind: ChangeKind::Added,
+                input: input.clone(),
+                fields: Vec::new(),
+            });
+            continue;
+        };
+        let fields: Vec<(String, String, String)> = record
+            .iter()
+            .filter(|(field, value)| previous.get(*field) != Some(*value))
+            .map(|(field, value)| {
+                (
+                    field.clone(),
+                    value_text(previous.get(field)),
+                    value_text(Some(value)),
+                )
+            })
+            .collect();
+        if !fields.is_empty() {
+            changes.push(Change {
+                kind: ChangeKind::Modified,
+                input: input.clone(),
+                fields,
+            });
+        }
+    }
+
+    let new_keys: Vec<&str> = new.iter().map(|(k, _)| k.as_str()).collect();
+    changes.extend(
+        old.iter()
+            .filter(|(k, _)| !new_keys.contains(&k.as_str()))
+            .map(|(k, _)| Change {
+                kind: ChangeKind::Removed,
+                input: k.clone(),
+                fields: Vec::new(),
+            }),
+    );
+    Ok(changes)
+}
+
+/// 既存ファイルとの差分計算・書き込み関数（ファイルが無い場合は全件追加扱い）
+fn compare_and_write<T: Serialize + DeserializeOwned>(
+    path: &Path,
+    new: &[T],
+    key: impl Fn(&T) -> &str + Copy,
+    dry_run: bool,
+) -> Result<(Vec<Change>, bool)> {
+    let old: Vec<T> = if path.exists() {
+        utils::read_json_file(path)?
+    } else {
+        Vec::new()
+    };
+    let changes = diff_records(&old, new, key)?;
+    if dry_run {
+        return Ok((changes, false));
+    }
+    if let Some(parent) = path.parent() {
+        utils::ensure_directory_exists(parent)?;
+    }
+    utils::write_json_file(path, &new)?;
+    Ok((changes, true))
+}
+
+/// キャラクター情報の差分計算・書き込み関数（ファイルが無い場合は追加扱い）
+fn compare_and_write_info(
+    path: &Path,
+    new: &CharInfo,
+    dry_run: bool,
+) -> Result<(Vec<Change>, bool)> {
+    let old: Vec<CharInfo> = if path.exists() {
+        vec![utils::read_json_file(path)?]
+    } else {
+        Vec::new()
+    };
+    let changes = diff_records(&old, std::slice::from_ref(new), |_| "character")?;
+    if dry_run {
+        return Ok((changes, false));
+    }
+    if let Some(parent) = path.parent() {
+        utils::ensure_directory_exists(parent)?;
+    }
+    utils::write_json_file(path, new)?;
+    Ok((changes, true))
+}
+
+/// 1 キャラクター・1 データ種別の更新関数
+///
+/// # 引数
+/// * `character` - キャラクター名（正式名）
+/// * `kind` - データ種別
+/// * `output_dir` - 出力先ディレクトリ
+/// * `dry_run` - 書き込まずに差分のみを返すか否か
+///
+/// # 戻り値
+/// 更新結果
+pub async fn update_character(
+    character: &str,
+    kind: UpdateKind,
+    output_dir: &Path,
+    dry_run: bool,
+) -> Result<UpdateReport> {
+    let path = kind.path(output_dir, character);
+    let (changes, written) = match kind {
+        UpdateKind::Frames => {
+            let body = fetch(&framedata::character_link(character))?;
+            let moves_info = framedata_json::parse_frames(body).await?;
+            compare_and_write(&path, &moves_info, |m: &MoveInfo| &m.input, dry_run)?
+        }
+        UpdateKind::Images => {
+            let body = fetch(&images::character_link(character))?;
+            let image_links = images_json::parse_images(body).await?;
+            compare_and_write(&path, &image_links, |i: &ImageLinks| &i.input, dry_run)?
+        }
+        UpdateKind::Info => {
+            let body = fetch(&info::character_link(character))?;
+            compare_and_write_info(&path, &info_json::parse_info(body).await?, dry_run)?
+        }
+    };
+    Ok(UpdateReport {
+        character: character.to_string(),
+        kind,
+        path,
+        changes,
+        written,
+    })
+}
+
+/// データベース再取り込み関数（`sqlite` 機能有効時、更新履歴も記録）
+///
+/// # 引数
+/// * `output_dir` - 出力先ディレクトリ
+/// * `target` - 更新対象（全キャラクターの場合は "all"）
+#[cfg(feature = "sqlite")]
+async fn refresh_database(output_dir: &Path, target: String) -> Result<()> {
+    let data_dir = output_dir.to_string_lossy().into_owned();
+    if !crate::database::db_path(&data_dir).exists() && !crate::database::backing_store_enabled() {
+        return Ok(()); // データベース未使用時は何もしない
+    }
+    tokio::task::spawn_blocking(move || crate::database::rebuild(&data_dir, &target, "update"))
+        .await
+        .map_err(|e| AppError::Database(format!("再構築に失敗しました: {e}")))??;
+    Ok(())
+}
+
+/// 更新処理実行関数
+///
+/// # 概要
+/// 対象キャラクター・データ種別毎に取得・差分計算・書き込みを行う。
+/// 失敗したキャラクターがあっても残りの処理を続け、進捗は標準エラー出力へ表示する。
+/// 書き込みがあった場合は（`sqlite` 機能有効時）データベースを再取り込みし、更新履歴を記録する。
+///
+/// # 引数
+/// * `options` - 更新設定
+///
+/// # 戻り値
+/// 成功した更新結果と、失敗した対象（"キャラクター名 種別", エラー）
+pub async fn run_update(options: &UpdateOptions) -> (Vec<UpdateReport>, Vec<(String, AppError)>) {
+    let mut reports = Vec::new();
+    let mut failures = Vec::new();
+    for character in &options.characters {
+        for kind in &options.kinds {
+            let target = format!("{character} {}", kind.name());
+            match update_character(character, *kind, &options.output_dir, options.dry_run).await {
+                Ok(report) => {
+                    eprintln!(
+                        "{}",
+                        format!(
+                            "{target}: +{} -{} ~{} ({})",
+                            report.count(ChangeKind::Added),
+                            report.count(ChangeKind::Removed),
+                            report.count(ChangeKind::Modified),
+                            if report.written {
+                                format!("written to {}", report.path.display())
+                            } else {
+                                "dry run".to_string()
+                            }
+                        )
+                        .green()
+                    );
+                    reports.push(report);
+                }
+                Err(err) => {
+                    eprintln!("{}", format!("{target}: {err}").red());
+                    failures.push((target, err));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    if reports.iter().any(|r| r.written) {
+        let target = if options.characters.len() == crate::CHARS.len() {
+            "all".to_string()
+        } else {
+            options.characters.join(",")
+        };
+        if let Err(err) = refresh_database(&options.output_dir, target).await {
+            eprintln!("{}", format!("database: {err}").red());
+            failures.push(("database".to_string(), err));
+        }
+    }
+    (reports, failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_records() {
+        let old = create_test_move_info();
+        let mut new = old.clone();
+        new[0].startup = Some(5);
+        new.remove(1);
+        let mut added = old[0].clone();
+        added.input = "6P".to_string();
+        new.push(added);
+
+        let changes = diff_records(&old, &new, |m| &m.input).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].kind, ChangeKind::Modified);
+        assert_eq!(
+            changes[0].fields,
+            vec![("startup".to_string(), "4".to_string(), "5".to_string())]
+        );
+        assert_eq!(changes[1].kind, ChangeKind::Added);
+        assert_eq!(changes[1].input, "6P");
+        assert_eq!(changes[2].kind, ChangeKind::Removed);
+        assert_eq!(changes[2].input, old[1].input);
+
+        assert!(diff_records(&old, &old, |m| &m.input).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_records_duplicate_inputs() {
+        let mut old = create_test_move_info();
+        old[1].input = old[0].input.clone();
+        let mut new = old.clone();
+        new[1].damage = Some(99);
+        let changes = diff_records(&old, &new, |m| &m.input).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].input, format!("{} #2", old[0].input));
+    }
+
+    #[test]
+    fn test_compare_and_write() {
+        let dir = tempdir().unwrap();
+        let path = UpdateKind::Frames.path(dir.path(), "Sol_Badguy");
+        let moves = create_test_move_info();
+
+        // ドライランでは書き込まない
+        let (changes, written) = compare_and_write(&path, &moves, |m| &m.input, true).unwrap();
+        assert_eq!(changes.len(), moves.len());
+        assert!(!written && !path.exists());
+
+        let (_, written) = compare_and_write(&path, &moves, |m| &m.input, false).unwrap();
+        assert!(written && path.exists());
+        let (changes, _) = compare_and_write(&path, &moves, |m| &m.input, true).unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_refresh_database() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+
+        // データベース未作成時は何もしない
+        refresh_database(dir.path(), "Sol_Badguy".to_string())
+            .await
+            .unwrap();
+        assert!(!crate::database::db_path(data_dir).exists());
+
+        crate::database::open(data_dir).unwrap();
+        refresh_database(dir.path(), "Sol_Badguy".to_string())
+            .await
+            .unwrap();
+        let history = crate::database::query_read_only(
+            data_dir,
+            "SELECT character, kind FROM update_history",
+            10,
+            crate::database::QUERY_TIME_LIMIT,
+        )
+        .unwrap();
+        assert_eq!(
+            history.rows,
+            vec![vec!["Sol_Badguy".to_string(), "update".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_info() {
+        let body = r#"{"cargoquery":[{"title":{
+            "defense":"0.95","guts":"2","prejump":"4","umo":"",
+            "backdash_duration":"23","backdash_airborne":"Yes","jump_duration":"45"
+        }}]}"#;
+        let info = info_json::parse_info(body.to_string()).await.unwrap();
+        assert_eq!(info.defense, Some(0.95));
+        assert_eq!(info.guts, Some(2.0));
+        assert_eq!(info.backdash_duration, Some(23));
+        assert_eq!(info.backdash_airborne, Some(true));
+        assert_eq!(info.jump_duration, Some(45));
+        assert_eq!(info.walk_speed, None);
+        assert!(info_json::parse_info(r#"{"cargoquery":[]}"#.to_string())
+            .await
+            .is_err());
+
+        let link = info::character_link("Sol_Badguy");
+        assert!(link.contains("ggstCharacters.guardBalance%3Dguard_balance"));
+        assert!(link.ends_with("ggstCharacters.name%3D%22Sol%20Badguy%22"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_frames() {
+        let body = r#"{"cargoquery":[
+            {"title":{"input":"5P","name":"5P","damage":"20","guard":"All","startup":"4","onBlock":"-1","type":"normal"}},
+            {"title":{"input":"236K","name":"Test Special","startup":"13","type":"special"}},
+            {"title":{"name":"Dash Cancel"}}
+        ]}"#;
+        let moves = framedata_json::parse_frames(body.to_string())
+            .await
+            .unwrap();
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0].input, "5P(5P)");
+        assert_eq!(moves[0].startup, Some(4));
+        assert_eq!(moves[1].input, "Test Special(236K)");
+        assert!(framedata_json::parse_frames("not json".to_string())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! `embed.rs`
+//!
+//! 技情報の埋め込みメッセージを組み立てる共通処理を提供する。
+//! 表示する項目・並び順・空項目（"-"）の非表示を指定でき、simple・advanced・meter 各表示で共通の見出し・URL を用いる。
+
+use crate::calc::counter;
+use crate::error::{AppError, Result};
+use crate::{utils, MoveInfo, EMBED_COLOR};
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde::{Deserialize, Serialize};
+
+/// 埋め込みメッセージの最大フィールド数（Discord の上限）
+pub const MAX_EMBED_FIELDS: usize = 25;
+
+/// フィールド値の最大文字数（Discord の上限）
+const MAX_FIELD_VALUE: usize = 1024;
+
+/// 空項目の表記
+const EMPTY_VALUE: &str = "-";
+
+/// 埋め込みに表示できる技情報の項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveField {
+    /// 技名
+    Name,
+    /// 技種別
+    MoveType,
+    /// ダメージ
+    Damage,
+    /// ガード
+    Guard,
+    /// 無敵
+    Invincibility,
+    /// 発生
+    Startup,
+    /// 持続
+    Active,
+    /// 硬直
+    Recovery,
+    /// ヒット時
+    OnHit,
+    /// ガード時
+    OnBlock,
+    /// カウンター種別
+    Counter,
+    /// カウンターヒット時の硬直差（計算値）
+    CounterHit,
+    /// 技レベル
+    Level,
+    /// ヒット/ガード硬直（技レベルからの計算値）
+    Stun,
+    /// ヒットストップ（技レベルからの計算値）
+    Hitstop,
+    /// キャンセル
+    Cancel,
+    /// リスク増加
+    RiscGain,
+    /// リスク減少
+    RiscLoss,
+    /// 壁ダメージ
+    WallDamage,
+    /// 入力緊張度
+    InputTension,
+    /// チップ比率
+    ChipRatio,
+    /// スケーリング
+    Scaling,
+}
+
+/// 数値項目の表示文字列変換（値が無い場合は "-"）
+fn or_empty<T: ToString>(value: Option<T>) -> String {
+    value.map_or(EMPTY_VALUE.to_string(), |v| v.to_string())
+}
+
+impl MoveField {
+    /// 全項目（既定の並び順）
+    pub const ALL: [Self; 22] = [
+        Self::Name,
+        Self::MoveType,
+        Self::Damage,
+        Self::Guard,
+        Self::Invincibility,
+        Self::Startup,
+        Self::Active,
+        Self::Recovery,
+        Self::OnHit,
+        Self::OnBlock,
+        Self::Counter,
+        Self::CounterHit,
+        Self::Level,
+        Self::Stun,
+        Self::Hitstop,
+        Self::Cancel,
+        Self::RiscGain,
+        Self::RiscLoss,
+        Self::WallDamage,
+        Self::InputTension,
+        Self::ChipRatio,
+        Self::Scaling,
+    ];
+
+    /// 指定用のキー（例："on_block"）を返す。
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::MoveType => "move_type",
+            Self::Damage => "damage",
+            Self::Guard => "guard",
+            Self::Invincibility => "invincibility",
+            Self::Startup => "startup",
+            Self::Active => "active",
+            Self::Recovery => "recovery",
+            Self::OnHit => "on_hit",
+            Self::OnBlock => "on_block",
+            Self::Counter => "counter",
+            Self::CounterHit => "counter_hit",
+            Self::Level => "level",
+            Self::Stun => "stun",
+            Self::Hitstop => "hitstop",
+            Self::Cancel => "cancel",
+            Self::RiscGain => "risc_gain",
+            Self::RiscLoss => "risc_loss",
+            Self::WallDamage => "wall_damage",
+            Self::InputTension => "input_tension",
+            Self::ChipRatio => "chip_ratio",
+            Self::Scaling => "scaling",
+        }
+    }
+
+    /// 表示名を返す。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "技名",
+            Self::MoveType => "種別",
+            Self::Damage => "ダメージ",
+            Self::Guard => "ガード",
+            Self::Invincibility => "無敵",
+            Self::Startup => "始動",
+            Self::Active => "持続",
+            Self::Recovery => "硬直",
+            Self::OnHit => "ヒット時",
+            Self::OnBlock => "ガード時",
+            Self::Counter => "カウンター",
+            Self::CounterHit => "カウンター時",
+            Self::Level => "技レベル",
+            Self::Stun => "ヒット/ガード硬直",
+            Self::Hitstop => "ヒットストップ",
+            Self::Cancel => "キャンセル",
+            Self::RiscGain => "リスク増加",
+            Self::RiscLoss => "リスク減少",
+            Self::WallDamage => "壁ダメージ",
+            Self::InputTension => "入力緊張度",
+            Self::ChipRatio => "チップ比率",
+            Self::Scaling => "スケーリング",
+        }
+    }
+
+    /// キーまたは表示名から項目を返す（大文字小文字・"-" と "_" の違いは無視）。
+    pub fn from_key(key: &str) -> Option<Self> {
+        let key = key.trim().to_lowercase().replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|field| field.key() == key || field.label() == key)
+    }
+
+    /// 技情報から表示値を返す。
+    pub fn value(self, move_info: &MoveInfo) -> String {
+        match self {
+            Self::Name => move_info.name.clone(),
+            Self::MoveType => move_info.move_type.clone(),
+            Self::Damage => or_empty(move_info.damage),
+            Self::Guard => move_info.guard.clone(),
+            Self::Invincibility => move_info.invincibility.clone(),
+            Self::Startup => or_empty(move_info.startup),
+            Self::Active => move_info.active.clone(),
+            Self::Recovery => or_empty(move_info.recovery),
+            Self::OnHit => move_info.on_hit.clone(),
+            Self::OnBlock => move_info.on_block.clone(),
+            Self::Counter => move_info.counter.clone(),
+            Self::CounterHit => {
+                or_empty(counter::counter_advantage(move_info).map(|advantage| {
+                    format!("{:+} (+{})", advantage.on_counter(), advantage.bonus)
+                }))
+            }
+            Self::Level => move_info.level.clone(),
+            Self::Stun => or_empty(
+                counter::level_data(&move_info.level)
+                    .map(|data| format!("{} / {}", data.hitstun, data.blockstun)),
+            ),
+            Self::Hitstop => or_empty(counter::level_data(&move_info.level).map(|d| d.hitstop)),
+            Self::Cancel => move_info.cancel.clone(),
+            Self::RiscGain => or_empty(move_info.risc_gain),
+            Self::RiscLoss => or_empty(move_info.risc_loss),
+            Self::WallDamage => or_empty(move_info.wall_damage),
+            Self::InputTension => or_empty(move_info.input_tension),
+            Self::ChipRatio => or_empty(move_info.chip_ratio),
+            Self::Scaling => or_empty(move_info.scaling),
+        }
+    }
+}
+
+/// 項目一覧の解析関数
+/// "startup, active, on_block" のような "," 区切りの指定を項目一覧へ変換する（重複は除外）。
+pub fn parse_fields(text: &str) -> Result<Vec<MoveField>> {
+    let mut fields = Vec::new();
+    for key in text.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        let field = MoveField::from_key(key).ok_or_else(|| {
+            AppError::Config(format!(
+                "Unknown field `{key}`. Available: {}",
+                MoveField::ALL.map(MoveField::key).join(", ")
+            ))
+        })?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    if fields.is_empty() {
+        return Err(AppError::Config("No field specified.".to_string()));
+    }
+    Ok(fields)
+}
+
+/// 表示形式
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedView {
+    /// 基本的なフレームデータ表示
+    #[name = "simple"]
+    Simple,
+    /// 詳細なフレームデータ表示
+    #[name = "advanced"]
+    Advanced,
+}
+
+impl EmbedView {
+    /// 既定の表示項目を返す。
+    pub fn default_fields(self) -> Vec<MoveField> {
+        use MoveField::*;
+        match self {
+            Self::Simple => vec![
+                Damage,
+                Guard,
+                Invincibility,
+                Startup,
+                Active,
+                Recovery,
+                OnHit,
+                OnBlock,
+                Counter,
+            ],
+            Self::Advanced => vec![
+                Damage,
+                Guard,
+                Invincibility,
+                Startup,
+                Active,
+                Recovery,
+                OnHit,
+                OnBlock,
+                Counter,
+                CounterHit,
+                Level,
+                Stun,
+                Hitstop,
+                RiscGain,
+                RiscLoss,
+                WallDamage,
+                InputTension,
+                ChipRatio,
+                Scaling,
+            ],
+        }
+    }
+}
+
+/// 表示項目の指定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSelection {
+    /// 表示する項目（表示順）
+    pub fields: Vec<MoveField>,
+    /// 空項目（"-"）を表示しないか否か
+    pub hide_empty: bool,
+}
+
+impl FieldSelection {
+    /// 表示形式の既定値で生成する。
+    pub fn for_view(view: EmbedView) -> Self {
+        Self {
+            fields: view.default_fields(),
+            hide_empty: false,
+        }
+    }
+
+    /// 埋め込みフィールド一覧を返す（上限 [`MAX_EMBED_FIELDS`] 件）。
+    pub fn embed_fields(&self, move_info: &MoveInfo) -> Vec<(String, String, bool)> {
+        self.fields
+            .iter()
+            .map(|field| (field.label(), field.value(move_info)))
+            .filter(|(_, value)| {
+                !self.hide_empty || !(value.trim().is_empty() || value.trim() == EMPTY_VALUE)
+            })
+            .take(MAX_EMBED_FIELDS)
+            .map(|(label, value)| {
+                let value = if value.is_empty() {
+                    EMPTY_VALUE.to_string()
+                } else {
+                    utils::truncate_string(&value, MAX_FIELD_VALUE)
+                };
+                (label.to_string(), value, true)
+            })
+            .collect()
+    }
+}
+
+/// 技情報埋め込みの共通部分（色・タイトル・Dustloop URL）生成関数
+pub fn move_embed_base(character_arg_altered: &str, move_info: &MoveInfo) -> CreateEmbed {
+    CreateEmbed::new()
+        .color(EMBED_COLOR)
+        .title(format!(
+            "__**{}：{}**__",
+            character_arg_altered, move_info.input
+        ))
+        .url("https://dustloop.com/w/GGST/".to_owned() + character_arg_altered + "#Overview")
+}
+
+/// 技情報の埋め込みメッセージ生成関数
+///
+/// # 引数
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `move_info` - 技情報
+/// * `embed_image` - 埋め込む画像のURL
+/// * `selection` - 表示項目の指定
+///
+/// # 戻り値
+/// 埋め込みメッセージ（キャプションをフッターに表示）
+pub fn create_move_embed(
+    character_arg_altered: &str,
+    move_info: &MoveInfo,
+    embed_image: &str,
+    selection: &FieldSelection,
+) -> CreateEmbed {
+    move_embed_base(character_arg_altered, move_info)
+        .image(embed_image)
+        .fields(selection.embed_fields(move_info))
+        .footer(CreateEmbedFooter::new(&move_info.caption))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+
+    #[test]
+    fn test_field_keys_round_trip() {
+        for field in MoveField::ALL {
+            assert_eq!(MoveField::from_key(field.key()), Some(field));
+            assert_eq!(MoveField::from_key(field.label()), Some(field));
+            assert_eq!(
+                serde_json::to_value(field).unwrap(),
+                serde_json::Value::String(field.key().to_string())
+            );
+        }
+        assert_eq!(MoveField::from_key("On-Block"), Some(MoveField::OnBlock));
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(
+            parse_fields("startup, active,startup, ガード時").unwrap(),
+            vec![MoveField::Startup, MoveField::Active, MoveField::OnBlock]
+        );
+        assert!(parse_fields("startup, speed").is_err());
+        assert!(parse_fields(" , ").is_err());
+    }
+
+    #[test]
+    fn test_embed_fields_order_and_hide_empty() {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.startup = Some(5);
+        move_info.damage = None;
+        let mut selection = FieldSelection {
+            fields: vec![MoveField::Startup, MoveField::Damage],
+            hide_empty: false,
+        };
+        let fields = selection.embed_fields(&move_info);
+        assert_eq!(
+            fields,
+            vec![
+                ("始動".to_string(), "5".to_string(), true),
+                ("ダメージ".to_string(), "-".to_string(), true),
+            ]
+        );
+        selection.hide_empty = true;
+        assert_eq!(selection.embed_fields(&move_info).len(), 1);
+    }
+
+    #[test]
+    fn test_default_fields_fit() {
+        assert!(EmbedView::Advanced.default_fields().len() <= MAX_EMBED_FIELDS);
+        assert_eq!(FieldSelection::for_view(EmbedView::Simple).fields.len(), 9);
+    }
+}
@@ -1 +1,5 @@
+pub mod embed;
+pub mod paginate;
 pub mod preprocess;
+pub mod resolve;
+pub mod table;
@@ -0,0 +1,270 @@
+//! `paginate.rs`
+//!
+//! 複数の埋め込みメッセージをボタンでページ送り表示する共通処理を提供する。
+//! 前へ／次へボタンとページ番号（例："2/5"）を表示し、一定時間操作が無い場合はボタンを除去して終了する。
+//! ページ送りはコマンド実行者のみ操作できる。
+
+use crate::{error::Result, Context};
+use colored::Colorize;
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ComponentInteractionCollector, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, EditMessage, UserId,
+};
+use std::time::Duration;
+
+/// ページ送り操作の無操作タイムアウト
+pub const PAGINATION_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// ページ送り操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageAction {
+    /// 前のページ
+    Prev,
+    /// 次のページ
+    Next,
+}
+
+/// 1 ページ分の表示内容
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// 埋め込みメッセージ
+    pub embed: CreateEmbed,
+    /// ページ表示時に添付するファイル（`attachment://` 参照先）
+    pub files: Vec<CreateAttachment>,
+}
+
+impl From<CreateEmbed> for Page {
+    fn from(embed: CreateEmbed) -> Self {
+        Self {
+            embed,
+            files: Vec::new(),
+        }
+    }
+}
+
+/// ページ番号計算関数（先頭・末尾で循環）
+pub fn turn_page(current: usize, total: usize, action: PageAction) -> usize {
+    if total == 0 {
+        return 0;
+    }
+    match action {
+        PageAction::Prev => current.checked_sub(1).unwrap_or(total - 1),
+        PageAction::Next => (current + 1) % total,
+    }
+}
+
+/// ページ番号表示文字列生成関数（例："2/5"）
+pub fn page_label(current: usize, total: usize) -> String {
+    format!("{}/{}", current + 1, total)
+}
+
+/// ページ送りボタン行生成関数
+/// 前へ・ページ番号（押下不可）・次への 3 ボタンを並べる。
+fn page_buttons(prefix: &str, current: usize, total: usize) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{prefix}prev")).emoji('◀'),
+        CreateButton::new(format!("{prefix}count"))
+            .label(page_label(current, total))
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(true),
+        CreateButton::new(format!("{prefix}next")).emoji('▶'),
+    ])]
+}
+
+/// ページ送り状態
+///
+/// ボタン操作に対する応答を生成し、表示中のページを管理する。
+struct Paginator {
+    /// 表示ページ群
+    pages: Vec<Page>,
+    /// ボタン ID 接頭辞（コマンド実行毎に一意）
+    prefix: String,
+    /// コマンド実行者（ページ送り可能なユーザー）
+    author: UserId,
+    /// 表示中のページ番号
+    current: usize,
+}
+
+impl Paginator {
+    /// ページ送り状態を作成する（先頭ページ表示）。
+    fn new(pages: Vec<Page>, prefix: String, author: UserId) -> Self {
+        Self {
+            pages,
+            prefix,
+            author,
+            current: 0,
+        }
+    }
+
+    /// 表示中ページのボタン行を返す。
+    fn buttons(&self) -> Vec<CreateActionRow> {
+        page_buttons(&self.prefix, self.current, self.pages.len())
+    }
+
+    /// ボタン操作応答生成関数
+    ///
+    /// # 概要
+    /// 実行者による前へ／次へ操作の場合はページを切り替え、メッセージ更新応答を返す。
+    /// 更新時は添付ファイルを新しいページのもので置き換える（添付の無いページでは既存の添付を除去する）。
+    /// 実行者以外の操作には本人のみに見える案内を返し、ページは切り替えない。
+    ///
+    /// # 引数
+    /// * `custom_id` - 押下されたボタンの ID
+    /// * `user` - 押下したユーザー
+    ///
+    /// # 戻り値
+    /// 応答内容（このページ送り対象外のボタンの場合は None）
+    fn respond(&mut self, custom_id: &str, user: UserId) -> Option<CreateInteractionResponse> {
+        let action = match custom_id.strip_prefix(&self.prefix) {
+            Some("prev") => PageAction::Prev,
+            Some("next") => PageAction::Next,
+            _ => return None,
+        };
+        if user != self.author {
+            return Some(CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(format!("Only <@{}> can turn these pages.", self.author))
+                    .ephemeral(true),
+            ));
+        }
+
+        self.current = turn_page(self.current, self.pages.len(), action);
+        let page = self.pages[self.current].clone();
+        Some(CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .embed(page.embed)
+                .files(page.files) // 既存の添付は置き換え
+                .components(self.buttons()),
+        ))
+    }
+}
+
+/// ページ送り表示関数
+///
+/// # 概要
+/// 先頭ページを送信し、コマンド実行者のボタン操作に応じて表示ページを切り替える。
+/// ページが 1 枚以下の場合はボタン無しで送信する。
+/// [`PAGINATION_TIMEOUT`] の間操作が無ければボタンを除去して終了する（長時間実行関数）。
+///
+/// # 引数
+/// * `ctx` - Discordのコンテキスト
+/// * `pages` - 表示ページ群
+pub async fn paginate(ctx: Context<'_>, pages: Vec<Page>) -> Result<()> {
+    let Some(first) = pages.first().cloned() else {
+        return Ok(());
+    };
+
+    let mut reply = poise::CreateReply::default().embed(first.embed);
+    for file in first.files {
+        reply = reply.attachment(file);
+    }
+    if pages.len() == 1 {
+        ctx.send(reply).await?;
+        return Ok(());
+    }
+
+    // ボタン ID はコマンド実行毎に一意
+    let ctx_id = ctx.id();
+    let mut paginator = Paginator::new(pages, ctx_id.to_string(), ctx.author().id);
+    let handle = ctx.send(reply.components(paginator.buttons())).await?;
+
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(PAGINATION_TIMEOUT)
+        .await
+    {
+        let Some(response) = paginator.respond(&press.data.custom_id, press.user.id) else {
+            continue;
+        };
+        press
+            .create_response(ctx.serenity_context(), response)
+            .await?;
+    }
+
+    // タイムアウト時はボタンのみ除去（埋め込み・添付は維持、メッセージ削除済み等の失敗は無視）
+    let removed = match handle.message().await {
+        Ok(message) => {
+            message
+                .into_owned()
+                .edit(ctx, EditMessage::new().components(Vec::new()))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = removed {
+        println!("{}", format!("Failed to remove page buttons: {e}").yellow());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_page_wraps() {
+        assert_eq!(turn_page(0, 3, PageAction::Next), 1);
+        assert_eq!(turn_page(2, 3, PageAction::Next), 0);
+        assert_eq!(turn_page(0, 3, PageAction::Prev), 2);
+        assert_eq!(turn_page(1, 3, PageAction::Prev), 0);
+        assert_eq!(turn_page(0, 0, PageAction::Next), 0);
+    }
+
+    #[test]
+    fn test_page_label() {
+        assert_eq!(page_label(1, 5), "2/5");
+    }
+
+    /// 応答の種別番号と data 部を返す。
+    fn payload(response: CreateInteractionResponse) -> (u64, serde_json::Value) {
+        let value = serde_json::to_value(response).unwrap();
+        (value["type"].as_u64().unwrap(), value["data"].clone())
+    }
+
+    #[test]
+    fn test_paginator_turns_pages_for_author() {
+        let author = UserId::new(1);
+        let pages = vec![
+            Page {
+                embed: CreateEmbed::new().title("first"),
+                files: vec![CreateAttachment::bytes(vec![1, 2, 3], "grid.png")],
+            },
+            Page::from(CreateEmbed::new().title("second")),
+        ];
+        let mut paginator = Paginator::new(pages, "42".to_string(), author);
+
+        // 添付の無いページへ移動すると既存の添付は除去される
+        let (kind, data) = payload(paginator.respond("42next", author).unwrap());
+        assert_eq!(kind, 7); // UPDATE_MESSAGE
+        assert_eq!(data["embeds"][0]["title"], "second");
+        assert_eq!(data["attachments"], serde_json::json!([]));
+        assert_eq!(data["components"][0]["components"][1]["label"], "2/2");
+
+        // 添付のあるページへ戻ると添付は置き換えられる
+        let (_, data) = payload(paginator.respond("42next", author).unwrap());
+        assert_eq!(data["embeds"][0]["title"], "first");
+        assert_eq!(data["attachments"][0]["filename"], "grid.png");
+        assert_eq!(paginator.current, 0);
+    }
+
+    #[test]
+    fn test_paginator_rejects_other_users() {
+        let author = UserId::new(1);
+        let pages = vec![
+            Page::from(CreateEmbed::new().title("first")),
+            Page::from(CreateEmbed::new().title("second")),
+        ];
+        let mut paginator = Paginator::new(pages, "42".to_string(), author);
+
+        let (kind, data) = payload(paginator.respond("42next", UserId::new(2)).unwrap());
+        assert_eq!(kind, 4); // CHANNEL_MESSAGE_WITH_SOURCE
+        assert_eq!(data["flags"], 64); // EPHEMERAL
+        assert_eq!(paginator.current, 0);
+
+        // 他のコマンドのボタン・ページ番号ボタンは対象外
+        assert!(paginator.respond("43next", author).is_none());
+        assert!(paginator.respond("42count", author).is_none());
+    }
+}
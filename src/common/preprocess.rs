@@ -719,8 +719,8 @@ lazy_static! {
 /// 置換処理後のJSON文字列
 ///
 /// # 例
-/// ```rust,no_run
-/// use your_crate::preprocess_json;
+/// ```rust
+/// use baiken::common::preprocess::preprocess_json;
 /// let input = r#"{"key": "c.S", "other": "f.S"}"#.to_string();
 /// let output = preprocess_json(input);
 /// println!("{}", output);
@@ -0,0 +1,50 @@
+//! `resolve.rs`
+//!
+//! コマンド引数の技指定から技情報を検索する共通処理を提供する。
+//! 見つからない技がある場合は `/moves` の案内を送信し、呼び出し側は処理を終了するだけで済むようにする。
+
+use crate::guild::GuildConfig;
+use crate::{error::AppError, find, Context, MoveInfo};
+
+/// 技指定の一覧から技情報を検索する関数
+///
+/// # 概要
+/// 各指定を [`find::find_move_index`] で検索し、入力順の技情報を返す。
+/// 見つからない技があれば理由と `/moves` の案内を送信して None を返す。
+///
+/// # 引数
+/// * `ctx` - コマンドコンテキスト
+/// * `character_arg_altered` - 正式なキャラクター名
+/// * `queries` - 技名・入力・エイリアスの一覧（入力順）
+/// * `moves_info` - キャラクターの技情報一覧
+///
+/// # 戻り値
+/// 入力順の技情報一覧（見つからない技がある場合は None）
+pub async fn resolve_moves(
+    ctx: &Context<'_>,
+    character_arg_altered: &str,
+    queries: &[&str],
+    moves_info: &[MoveInfo],
+) -> Result<Option<Vec<MoveInfo>>, AppError> {
+    let guild = GuildConfig::for_context(ctx);
+    let mut moves = Vec::with_capacity(queries.len());
+    for query in queries {
+        match find::find_move_index(
+            &ctx.data().data_dir,
+            &character_arg_altered.to_string(),
+            (*query).to_string(),
+            moves_info,
+            guild.as_ref(),
+        )
+        .await
+        {
+            Ok(index) => moves.push(moves_info[index].clone()),
+            Err(err) => {
+                ctx.say(err.to_string() + "\nView the moves of a character by executing `/moves`.")
+                    .await?;
+                return Ok(None);
+            }
+        }
+    }
+    Ok(Some(moves))
+}
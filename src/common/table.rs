@@ -0,0 +1,92 @@
+//! `table.rs`
+//!
+//! 等幅表示用の表（列を " | " で区切り、見出しの下に区切り線を引く）を組み立てる共通処理を提供する。
+//! コマンドラインツールの出力や `/sql` の問い合わせ結果表示で用いる。
+
+/// 表示幅計算関数（全角文字は 2、それ以外は 1 として数える）
+pub fn display_width(s: &str) -> usize {
+    s.chars()
+        .map(|c| match u32::from(c) {
+            0x1100..=0x115F
+            | 0x2E80..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x3FFFD => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// 表文字列生成関数（列幅は表示幅で揃え、行末の空白は除去する）
+///
+/// # 引数
+/// * `headers` - 見出し
+/// * `rows` - 行（各行の列数は見出しと同じであること）
+///
+/// # 戻り値
+/// 改行区切りの表文字列
+pub fn format_table<S: AsRef<str>>(headers: &[S], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|value| display_width(value))
+                .chain([display_width(header.as_ref())])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |values: &mut dyn Iterator<Item = &str>| {
+        values
+            .zip(&widths)
+            .map(|(value, width)| format!("{value}{}", " ".repeat(width - display_width(value))))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut table = line(&mut headers.iter().map(AsRef::as_ref));
+    table += "\n";
+    table += &widths
+        .iter()
+        .map(|w| "-".repeat(*w))
+        .collect::<Vec<_>>()
+        .join("-+-");
+    for row in rows {
+        table += "\n";
+        table += &line(&mut row.iter().map(String::as_str));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![
+            vec!["5P".to_string(), "4".to_string()],
+            vec!["236K".to_string(), String::new()],
+        ];
+        assert_eq!(
+            format_table(&["input", "startup"], &rows),
+            "input | startup\n------+--------\n5P    | 4\n236K  |"
+        );
+        assert_eq!(format_table(&["a"], &[]), "a\n-");
+
+        // 全角文字は 2 桁として揃える
+        let rows = vec![vec!["上段".to_string(), "x".to_string()]];
+        assert_eq!(
+            format_table(&["guard", "v"], &rows),
+            "guard | v\n------+--\n上段  | x"
+        );
+        assert_eq!(display_width("ソルK"), 5);
+    }
+}
@@ -0,0 +1,569 @@
+//! `database.rs`
+//!
+//! JSON データから SQLite データベース（データディレクトリ内の単一ファイル）を構築する機能を提供する（`sqlite` 機能有効時のみ）。
+//! キャラクター・技・エイリアス・愛称・画像・更新履歴のテーブルを持ち、読み取り専用の問い合わせと技データの読み込み元として利用できる。
+
+use crate::common::table;
+use crate::error::{AppError, Result};
+use crate::{utils, CharInfo, ImageLinks, MoveInfo, Nicknames, CHARS};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// データベースファイル名（データディレクトリ直下）
+pub const DB_FILE: &str = "baiken.sqlite3";
+
+/// 問い合わせ結果の最大行数
+pub const MAX_QUERY_ROWS: usize = 50;
+
+/// 問い合わせ 1 回あたりの最大実行時間
+pub const QUERY_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+/// 技データの読み込み元としてデータベースを使うか否か
+static BACKING_STORE: AtomicBool = AtomicBool::new(false);
+
+/// テーブル定義
+const SCHEMA: &str = r"
+CREATE TABLE IF NOT EXISTS characters (
+    name TEXT PRIMARY KEY,
+    defense REAL,
+    guts REAL,
+    guard_balance REAL,
+    prejump INTEGER,
+    forward_dash REAL,
+    backdash REAL,
+    backdash_duration INTEGER
+);
+CREATE TABLE IF NOT EXISTS moves (
+    character TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    input TEXT NOT NULL,
+    name TEXT NOT NULL,
+    damage INTEGER,
+    guard TEXT NOT NULL,
+    startup INTEGER,
+    active TEXT NOT NULL,
+    recovery INTEGER,
+    on_hit TEXT NOT NULL,
+    on_block TEXT NOT NULL,
+    level TEXT NOT NULL,
+    counter TEXT NOT NULL,
+    move_type TEXT NOT NULL,
+    risc_gain REAL,
+    risc_loss REAL,
+    wall_damage INTEGER,
+    input_tension REAL,
+    chip_ratio REAL,
+    scaling REAL,
+    invincibility TEXT NOT NULL,
+    cancel TEXT NOT NULL,
+    caption TEXT NOT NULL,
+    notes TEXT NOT NULL,
+    PRIMARY KEY (character, position)
+);
+CREATE TABLE IF NOT EXISTS aliases (
+    character TEXT NOT NULL,
+    input TEXT NOT NULL,
+    alias TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS nicknames (
+    character TEXT NOT NULL,
+    nickname TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS images (
+    character TEXT NOT NULL,
+    input TEXT NOT NULL,
+    move_img TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS hitboxes (
+    character TEXT NOT NULL,
+    input TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    url TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS update_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    character TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+";
+
+/// 取り込み件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// キャラクター数
+    pub characters: usize,
+    /// 技数
+    pub moves: usize,
+    /// エイリアス数
+    pub aliases: usize,
+    /// 愛称数
+    pub nicknames: usize,
+    /// 画像数（技画像＋ヒットボックス画像）
+    pub images: usize,
+}
+
+/// 問い合わせ結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryResult {
+    /// 列名
+    pub columns: Vec<String>,
+    /// 行（各値は文字列化済み、NULL は "NULL"）
+    pub rows: Vec<Vec<String>>,
+    /// 最大行数で打ち切ったか否か
+    pub truncated: bool,
+}
+
+impl QueryResult {
+    /// 等幅表示用の表文字列生成関数
+    pub fn to_table(&self) -> String {
+        table::format_table(&self.columns, &self.rows)
+    }
+}
+
+/// 技データの読み込み元設定関数
+pub fn set_backing_store(enabled: bool) {
+    BACKING_STORE.store(enabled, Ordering::Relaxed);
+}
+
+/// 技データの読み込み元がデータベースか否かを返す。
+pub fn backing_store_enabled() -> bool {
+    BACKING_STORE.load(Ordering::Relaxed)
+}
+
+/// データベースファイルパス生成関数
+pub fn db_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir).join(DB_FILE)
+}
+
+/// データベース接続関数（未作成時はテーブルを作成）
+pub fn open(data_dir: &str) -> Result<Connection> {
+    let conn = Connection::open(db_path(data_dir))?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// 読み取り専用のデータベース接続関数
+fn open_read_only(data_dir: &str) -> Result<Connection> {
+    let path = db_path(data_dir);
+    if !path.exists() {
+        return Err(AppError::FileNotFound(format!(
+            "{} (run `/sql rebuild` first)",
+            path.display()
+        )));
+    }
+    Ok(Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?)
+}
+
+/// 現在時刻（UNIX 秒）取得関数
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// 1 キャラクター分の JSON データ取り込み関数
+fn import_character(
+    tx: &rusqlite::Transaction<'_>,
+    data_dir: &str,
+    character: &str,
+    summary: &mut ImportSummary,
+) -> Result<()> {
+    let char_dir = Path::new(data_dir).join(character);
+    let moves_path = char_dir.join(character.to_owned() + ".json");
+    if !moves_path.exists() {
+        return Ok(());
+    }
+
+    // キャラクター情報（未作成時は NULL）
+    let info = utils::load_character_info(data_dir, character).ok();
+    tx.execute(
+        "INSERT INTO characters (name, defense, guts, guard_balance, prejump, forward_dash, backdash, backdash_duration)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            character,
+            info.as_ref().and_then(|i: &CharInfo| i.defense),
+            info.as_ref().and_then(|i| i.guts),
+            info.as_ref().and_then(|i| i.guard_balance),
+            info.as_ref().and_then(|i| i.prejump),
+            info.as_ref().and_then(|i| i.forward_dash),
+            info.as_ref().and_then(|i| i.backdash),
+            info.as_ref().and_then(|i| i.backdash_duration),
+        ],
+    )?;
+    summary.characters += 1;
+
+    // 技情報
+    let moves_info: Vec<MoveInfo> = utils::read_json_file(moves_path)?;
+    let mut insert_move = tx.prepare(
+        "INSERT INTO moves VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+         ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+    )?;
+    for (position, m) in moves_info.iter().enumerate() {
+        insert_move.execute(params![
+            character,
+            position as i64,
+            m.input,
+            m.name,
+            m.damage,
+            m.guard,
+            m.startup,
+            m.active,
+            m.recovery,
+            m.on_hit,
+            m.on_block,
+            m.level,
+            m.counter,
+            m.move_type,
+            m.risc_gain,
+            m.risc_loss,
+            m.wall_damage,
+            m.input_tension,
+            m.chip_ratio,
+            m.scaling,
+            m.invincibility,
+            m.cancel,
+            m.caption,
+            m.notes,
+        ])?;
+    }
+    summary.moves += moves_info.len();
+
+    // エイリアス
+    for entry in utils::load_move_aliases(data_dir, character)? {
+        for alias in &entry.aliases {
+            tx.execute(
+                "INSERT INTO aliases (character, input, alias) VALUES (?1, ?2, ?3)",
+                params![character, entry.input, alias],
+            )?;
+            summary.aliases += 1;
+        }
+    }
+
+    // 画像
+    let images_path = char_dir.join("images.json");
+    if images_path.exists() {
+        let image_links: Vec<ImageLinks> = utils::read_json_file(images_path)?;
+        for links in &image_links {
+            tx.execute(
+                "INSERT INTO images (character, input, move_img) VALUES (?1, ?2, ?3)",
+                params![character, links.input, links.move_img],
+            )?;
+            for (position, url) in links.hitbox_img.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO hitboxes (character, input, position, url) VALUES (?1, ?2, ?3, ?4)",
+                    params![character, links.input, position as i64, url],
+                )?;
+            }
+            summary.images += 1 + links.hitbox_img.len();
+        }
+    }
+    Ok(())
+}
+
+/// JSON データ取り込み関数
+///
+/// # 概要
+/// 更新履歴以外のテーブルを空にし、データディレクトリ内の JSON データを 1 トランザクションで取り込む。
+///
+/// # 引数
+/// * `conn` - データベース接続
+/// * `data_dir` - データディレクトリ
+///
+/// # 戻り値
+/// 取り込み件数
+pub fn import_json(conn: &mut Connection, data_dir: &str) -> Result<ImportSummary> {
+    let tx = conn.transaction()?;
+    tx.execute_batch(
+        "DELETE FROM characters; DELETE FROM moves; DELETE FROM aliases;
+         DELETE FROM nicknames; DELETE FROM images; DELETE FROM hitboxes;",
+    )?;
+
+    let mut summary = ImportSummary::default();
+    for character in CHARS {
+        import_character(&tx, data_dir, character, &mut summary)?;
+    }
+
+    let nicknames_path = Path::new(data_dir).join("nicknames.json");
+    if nicknames_path.exists() {
+        let nicknames: Vec<Nicknames> = utils::read_json_file(nicknames_path)?;
+        for entry in &nicknames {
+            for nickname in &entry.nicknames {
+                tx.execute(
+                    "INSERT INTO nicknames (character, nickname) VALUES (?1, ?2)",
+                    params![entry.character, nickname],
+                )?;
+                summary.nicknames += 1;
+            }
+        }
+    }
+
+    tx.commit()?;
+    Ok(summary)
+}
+
+/// データベース再構築関数（接続・取り込み・更新履歴記録）
+///
+/// # 引数
+/// * `data_dir` - データディレクトリ
+/// * `target` - 更新対象キャラクター（全体の場合は "all"）
+/// * `kind` - 更新種別（"update"・"rebuild" 等）
+pub fn rebuild(data_dir: &str, target: &str, kind: &str) -> Result<ImportSummary> {
+    let mut conn = open(data_dir)?;
+    let summary = import_json(&mut conn, data_dir)?;
+    record_update(&conn, target, kind)?;
+    Ok(summary)
+}
+
+/// 更新履歴記録関数
+pub fn record_update(conn: &Connection, character: &str, kind: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO update_history (character, kind, updated_at) VALUES (?1, ?2, ?3)",
+        params![character, kind, now_secs()],
+    )?;
+    Ok(())
+}
+
+/// データベースからの技情報読み込み関数（JSON 内の順序）
+pub fn load_move_info(data_dir: &str, character: &str) -> Result<Vec<MoveInfo>> {
+    let conn = open_read_only(data_dir)?;
+    let mut stmt = conn.prepare(
+        "SELECT input, name, damage, guard, startup, active, recovery, on_hit, on_block, level,
+         counter, move_type, risc_gain, risc_loss, wall_damage, input_tension, chip_ratio, scaling,
+         invincibility, cancel, caption, notes
+         FROM moves WHERE character = ?1 ORDER BY position",
+    )?;
+    let moves = stmt
+        .query_map(params![character], |row| {
+            Ok(MoveInfo {
+                input: row.get(0)?,
+                name: row.get(1)?,
+                damage: row.get(2)?,
+                guard: row.get(3)?,
+                startup: row.get(4)?,
+                active: row.get(5)?,
+                recovery: row.get(6)?,
+                on_hit: row.get(7)?,
+                on_block: row.get(8)?,
+                level: row.get(9)?,
+                counter: row.get(10)?,
+                move_type: row.get(11)?,
+                risc_gain: row.get(12)?,
+                risc_loss: row.get(13)?,
+                wall_damage: row.get(14)?,
+                input_tension: row.get(15)?,
+                chip_ratio: row.get(16)?,
+                scaling: row.get(17)?,
+                invincibility: row.get(18)?,
+                cancel: row.get(19)?,
+                caption: row.get(20)?,
+                notes: row.get(21)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    if moves.is_empty() {
+        return Err(AppError::CharacterNotFound(format!(
+            "技情報が見つかりません: {character}"
+        )));
+    }
+    Ok(moves)
+}
+
+/// 値の表示文字列変換関数
+fn value_text(value: ValueRef<'_>) -> String {
+    match value {
+        ValueRef::Null => "NULL".to_string(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => f.to_string(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned(),
+        ValueRef::Blob(b) => format!("<blob {} bytes>", b.len()),
+    }
+}
+
+/// 読み取り専用問い合わせ関数
+///
+/// # 概要
+/// 読み取り専用接続で 1 文のみ実行する。書き込みを伴う文は実行前に拒否する。
+/// 最大実行時間を超えた文は中断し、エラーを返す。
+///
+/// # 引数
+/// * `data_dir` - データディレクトリ
+/// * `sql` - SQL 文
+/// * `max_rows` - 最大行数
+/// * `time_limit` - 最大実行時間
+///
+/// # 戻り値
+/// 問い合わせ結果
+pub fn query_read_only(
+    data_dir: &str,
+    sql: &str,
+    max_rows: usize,
+    time_limit: Duration,
+) -> Result<QueryResult> {
+    let conn = open_read_only(data_dir)?;
+
+    // 監視スレッド：制限時間内に完了通知（送信側の破棄）が無ければ実行中の文を中断する
+    let interrupt = conn.get_interrupt_handle();
+    let timed_out = Arc::new(AtomicBool::new(false));
+    let (done, timer) = mpsc::channel::<()>();
+    let watchdog_flag = Arc::clone(&timed_out);
+    std::thread::spawn(move || {
+        if timer.recv_timeout(time_limit) == Err(mpsc::RecvTimeoutError::Timeout) {
+            watchdog_flag.store(true, Ordering::Relaxed);
+            interrupt.interrupt();
+        }
+    });
+    let result = run_read_only(&conn, sql, max_rows);
+    drop(done);
+    if result.is_err() && timed_out.load(Ordering::Relaxed) {
+        return Err(AppError::Database(format!(
+            "query exceeded the time limit of {}s",
+            time_limit.as_secs_f64()
+        )));
+    }
+    result
+}
+
+/// 読み取り専用の文を 1 つ実行する（`query_read_only` の本体）。
+fn run_read_only(conn: &Connection, sql: &str, max_rows: usize) -> Result<QueryResult> {
+    let mut stmt = conn.prepare(sql.trim().trim_end_matches(';'))?;
+    if !stmt.readonly() {
+        return Err(AppError::Database(
+            "only read-only statements are allowed".to_string(),
+        ));
+    }
+
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let mut result = QueryResult {
+        columns,
+        ..Default::default()
+    };
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        if result.rows.len() >= max_rows {
+            result.truncated = true;
+            break;
+        }
+        let values = (0..result.columns.len())
+            .map(|i| row.get_ref(i).map(value_text))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        result.rows.push(values);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_move_aliases, create_test_move_info};
+    use tempfile::tempdir;
+
+    /// テスト用データディレクトリを作成し、データベースを構築する
+    fn build_test_db() -> (tempfile::TempDir, ImportSummary) {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap().to_string();
+        let sol_dir = dir.path().join("Sol_Badguy");
+        std::fs::create_dir(&sol_dir).unwrap();
+        utils::write_json_file(sol_dir.join("Sol_Badguy.json"), &create_test_move_info()).unwrap();
+        utils::write_json_file(sol_dir.join("aliases.json"), &create_test_move_aliases()).unwrap();
+        utils::write_json_file(
+            sol_dir.join("images.json"),
+            &vec![ImageLinks {
+                input: "5P".to_string(),
+                move_img: "https://example.com/5p.png".to_string(),
+                hitbox_img: vec!["https://example.com/5p_hb.png".to_string()],
+            }],
+        )
+        .unwrap();
+        utils::write_json_file(
+            dir.path().join("nicknames.json"),
+            &vec![Nicknames {
+                character: "Sol_Badguy".to_string(),
+                nicknames: vec!["sol".to_string(), "ソル".to_string()],
+            }],
+        )
+        .unwrap();
+
+        let summary = rebuild(&data_dir, "all", "test").unwrap();
+        (dir, summary)
+    }
+
+    #[test]
+    fn test_import_json() {
+        let (_dir, summary) = build_test_db();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                characters: 1,
+                moves: 2,
+                aliases: 4,
+                nicknames: 2,
+                images: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_move_info() {
+        let (dir, _) = build_test_db();
+        let data_dir = dir.path().to_str().unwrap();
+        let moves = load_move_info(data_dir, "Sol_Badguy").unwrap();
+        let expected = create_test_move_info();
+        assert_eq!(moves.len(), expected.len());
+        assert_eq!(moves[1].input, expected[1].input);
+        assert_eq!(moves[1].startup, expected[1].startup);
+        assert_eq!(moves[1].risc_gain, expected[1].risc_gain);
+        assert!(load_move_info(data_dir, "Ky_Kiske").is_err());
+        let history = query_read_only(
+            data_dir,
+            "SELECT character, kind FROM update_history",
+            10,
+            QUERY_TIME_LIMIT,
+        )
+        .unwrap();
+        assert_eq!(
+            history.rows,
+            vec![vec!["all".to_string(), "test".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_query_read_only() {
+        let (dir, _) = build_test_db();
+        let data_dir = dir.path().to_str().unwrap();
+        let result = query_read_only(
+            data_dir,
+            "SELECT m.input, a.alias FROM moves m JOIN aliases a
+             ON a.character = m.character AND a.input = m.input ORDER BY m.position, a.alias;",
+            3,
+            QUERY_TIME_LIMIT,
+        )
+        .unwrap();
+        assert_eq!(result.columns, vec!["input", "alias"]);
+        assert_eq!(result.rows.len(), 3);
+        assert!(result.truncated);
+        assert!(result.to_table().starts_with("input | alias\n------+-"));
+
+        // 書き込みは拒否
+        assert!(query_read_only(data_dir, "DELETE FROM moves", 10, QUERY_TIME_LIMIT).is_err());
+        assert!(query_read_only(data_dir, "SELECT * FROM missing", 10, QUERY_TIME_LIMIT).is_err());
+
+        // 制限時間を超える文は中断する
+        let err = query_read_only(
+            data_dir,
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT count(*) FROM c",
+            10,
+            Duration::from_millis(100),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("time limit"));
+    }
+}
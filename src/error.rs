@@ -34,9 +34,9 @@ pub enum AppError {
     #[error("Discord API エラー: {0}")]
     Discord(String),
 
-    /// Serenity APIエラー
+    /// Serenity APIエラー（Result サイズ抑制のためボックス化）
     #[error("Serenity API エラー: {0}")]
-    Serenity(#[from] serenity::Error),
+    Serenity(Box<serenity::Error>),
 
     /// 設定エラー
     #[error("設定エラー: {0}")]
@@ -46,11 +46,31 @@ pub enum AppError {
     #[error("データ処理エラー: {0}")]
     DataProcessing(String),
 
+    /// データベースエラー（sqlite 機能有効時）
+    #[cfg(feature = "sqlite")]
+    #[error("データベースエラー: {0}")]
+    Database(String),
+
     /// その他のエラー
     #[error("エラー: {0}")]
     Other(String),
 }
 
+// rusqlite::Error型からAppError型への変換
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for AppError {
+    fn from(error: rusqlite::Error) -> Self {
+        AppError::Database(error.to_string())
+    }
+}
+
+// serenity::Error型からAppError型への変換
+impl From<serenity::Error> for AppError {
+    fn from(error: serenity::Error) -> Self {
+        AppError::Serenity(Box::new(error))
+    }
+}
+
 // `Box<dyn std::error::Error>型からAppError型への変換
 impl From<Box<dyn std::error::Error>> for AppError {
     fn from(error: Box<dyn std::error::Error>) -> Self {
@@ -108,7 +128,7 @@ mod tests {
     #[test]
     fn test_app_error_from_box_dyn_error() {
         let original_error: Box<dyn std::error::Error> =
-            Box::new(IoError::new(ErrorKind::Other, "ボックス化されたエラー"));
+            Box::new(IoError::other("ボックス化されたエラー"));
 
         let app_error = AppError::from(original_error);
 
@@ -0,0 +1,291 @@
+//! `feedback_store.rs`
+//!
+//! ユーザーからのフィードバックを構造化して保存する機能を提供する。
+//! 送信者・サーバー・チャンネル・日時・分類・本文・対応状況を 1 件毎に記録し、データディレクトリ内の `feedback.json` に保存する。
+
+use crate::aliases::AliasSuggestion;
+use crate::error::Result;
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// フィードバック保存ファイル名（データディレクトリ直下）
+pub const FEEDBACK_FILE: &str = "feedback.json";
+
+/// 読み込み〜保存間の排他用ロック
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// フィードバックの分類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackCategory {
+    /// 不具合報告
+    #[name = "bug"]
+    Bug,
+    /// 機能要望
+    #[name = "request"]
+    Request,
+    /// データ（フレームデータ・画像等）の誤り
+    #[name = "data"]
+    Data,
+    /// エイリアス・愛称の追加依頼
+    #[name = "alias"]
+    Alias,
+    /// その他
+    #[name = "other"]
+    Other,
+}
+
+/// フィードバックの対応状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackStatus {
+    /// 未対応
+    #[name = "open"]
+    Open,
+    /// 対応済み
+    #[name = "resolved"]
+    Resolved,
+    /// 対応しない
+    #[name = "rejected"]
+    Rejected,
+}
+
+/// フィードバック 1 件分の記録
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackRecord {
+    /// 通し番号（1 始まり）
+    pub id: u64,
+    /// 送信者のユーザーID
+    pub user_id: u64,
+    /// 送信者の表示名（送信時点）
+    pub user_name: String,
+    /// 送信元サーバーID（DM の場合は None）
+    pub guild_id: Option<u64>,
+    /// 送信元チャンネルID
+    pub channel_id: u64,
+    /// 送信日時（UNIX 秒）
+    pub created_at: u64,
+    /// 分類
+    pub category: FeedbackCategory,
+    /// 本文
+    pub text: String,
+    /// 対応状況
+    pub status: FeedbackStatus,
+    /// 開発者からの返信（送信順）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<String>,
+    /// 対応完了日時（UNIX 秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<u64>,
+    /// エイリアス追加提案（`/alias suggest` からの送信時）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<AliasSuggestion>,
+}
+
+/// 新規フィードバックの内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewFeedback {
+    /// 送信者のユーザーID
+    pub user_id: u64,
+    /// 送信者の表示名
+    pub user_name: String,
+    /// 送信元サーバーID
+    pub guild_id: Option<u64>,
+    /// 送信元チャンネルID
+    pub channel_id: u64,
+    /// 分類
+    pub category: FeedbackCategory,
+    /// 本文
+    pub text: String,
+    /// エイリアス追加提案
+    pub alias: Option<AliasSuggestion>,
+}
+
+/// フィードバック保存データ
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackStore {
+    /// 次に採番する通し番号
+    #[serde(default)]
+    pub next_id: u64,
+    /// 全記録（送信順）
+    #[serde(default)]
+    pub records: Vec<FeedbackRecord>,
+}
+
+/// 現在時刻（UNIX 秒）取得関数
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// フィードバック保存ファイルパス生成関数
+pub fn store_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir).join(FEEDBACK_FILE)
+}
+
+impl FeedbackStore {
+    /// 保存データ読み込み関数（未保存時は空）
+    pub fn load(data_dir: &str) -> Result<Self> {
+        let path = store_path(data_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        utils::read_json_file(path)
+    }
+
+    /// 保存データ保存関数
+    pub fn save(&self, data_dir: &str) -> Result<()> {
+        utils::ensure_directory_exists(data_dir)?;
+        utils::write_json_file(store_path(data_dir), self)
+    }
+
+    /// 読み込み・変更・保存を排他的に行う関数
+    /// 変更処理がエラーを返した場合は保存しない。
+    pub fn update<T>(data_dir: &str, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = Self::load(data_dir)?;
+        let value = f(&mut store)?;
+        store.save(data_dir)?;
+        Ok(value)
+    }
+
+    /// フィードバックを追加し、通し番号を返す。
+    pub fn add(&mut self, feedback: NewFeedback) -> u64 {
+        let id = self
+            .next_id
+            .max(self.records.iter().map(|r| r.id).max().unwrap_or(0) + 1)
+            .max(1);
+        self.next_id = id + 1;
+        self.records.push(FeedbackRecord {
+            id,
+            user_id: feedback.user_id,
+            user_name: feedback.user_name,
+            guild_id: feedback.guild_id,
+            channel_id: feedback.channel_id,
+            created_at: now_secs(),
+            category: feedback.category,
+            text: feedback.text,
+            status: FeedbackStatus::Open,
+            replies: Vec::new(),
+            closed_at: None,
+            alias: feedback.alias,
+        });
+        id
+    }
+
+    /// 通し番号から記録を返す。
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut FeedbackRecord> {
+        self.records.iter_mut().find(|record| record.id == id)
+    }
+
+    /// 指定状況の記録を新しい順に返す。
+    pub fn with_status(&self, status: FeedbackStatus) -> Vec<&FeedbackRecord> {
+        self.records
+            .iter()
+            .rev()
+            .filter(|record| record.status == status)
+            .collect()
+    }
+
+    /// 同じキャラクター・エイリアスの未対応提案の通し番号を返す。
+    pub fn pending_alias(&self, character: &str, alias: &str) -> Option<u64> {
+        self.records
+            .iter()
+            .filter(|record| record.status == FeedbackStatus::Open)
+            .find(|record| {
+                record.alias.as_ref().is_some_and(|s| {
+                    s.character == character && s.alias.to_lowercase() == alias.to_lowercase()
+                })
+            })
+            .map(|record| record.id)
+    }
+}
+
+impl FeedbackRecord {
+    /// 対応状況を更新する（未対応以外は完了日時を記録）。
+    pub fn set_status(&mut self, status: FeedbackStatus) {
+        self.status = status;
+        self.closed_at = (status != FeedbackStatus::Open).then(now_secs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn new_feedback(text: &str) -> NewFeedback {
+        NewFeedback {
+            user_id: 42,
+            user_name: "tester".to_string(),
+            guild_id: Some(1),
+            channel_id: 2,
+            category: FeedbackCategory::Bug,
+            text: text.to_string(),
+            alias: None,
+        }
+    }
+
+    #[test]
+    fn test_add_and_status() {
+        let mut store = FeedbackStore::default();
+        assert_eq!(store.add(new_feedback("a")), 1);
+        assert_eq!(store.add(new_feedback("b")), 2);
+
+        let record = store.get_mut(1).unwrap();
+        record.set_status(FeedbackStatus::Resolved);
+        assert!(record.closed_at.is_some());
+
+        let open: Vec<u64> = store
+            .with_status(FeedbackStatus::Open)
+            .iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(open, vec![2]);
+        assert!(store.get_mut(3).is_none());
+    }
+
+    #[test]
+    fn test_pending_alias() {
+        let mut store = FeedbackStore::default();
+        let mut feedback = new_feedback("alias");
+        feedback.alias = Some(AliasSuggestion {
+            character: "Sol_Badguy".to_string(),
+            input: "236K".to_string(),
+            alias: "Gunflame".to_string(),
+        });
+        let id = store.add(feedback);
+        assert_eq!(store.pending_alias("Sol_Badguy", "gunflame"), Some(id));
+        assert_eq!(store.pending_alias("Ky_Kiske", "gunflame"), None);
+
+        store
+            .get_mut(id)
+            .unwrap()
+            .set_status(FeedbackStatus::Rejected);
+        assert_eq!(store.pending_alias("Sol_Badguy", "gunflame"), None);
+    }
+
+    #[test]
+    fn test_update_persists() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let id = FeedbackStore::update(data_dir, |store| Ok(store.add(new_feedback("a")))).unwrap();
+        assert_eq!(id, 1);
+
+        // エラー時は保存しない
+        let result: Result<()> = FeedbackStore::update(data_dir, |store| {
+            store.add(new_feedback("b"));
+            Err(crate::AppError::Other("abort".to_string()))
+        });
+        assert!(result.is_err());
+
+        let store = FeedbackStore::load(data_dir).unwrap();
+        assert_eq!(store.records.len(), 1);
+        assert_eq!(store.records[0].text, "a");
+        assert_eq!(store.next_id, 2);
+    }
+}
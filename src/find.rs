@@ -4,12 +4,14 @@
 //! nicknames.json 及びキャラクター JSON から、ユーザー入力に対応する正式なキャラクター名や技のインデックスを返却する。
 
 use crate::error::{AppError, Result};
+use crate::guild::GuildConfig;
 use crate::models::{MoveAliases, MoveInfo};
+use crate::ImageLinks;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 
 /// ニックネーム情報を保持する構造体
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Nicknames {
     /// 正式キャラクター名
     pub character: String,
@@ -22,17 +24,30 @@ pub struct Nicknames {
 /// # 概要
 /// nicknames.json ファイルを読み込み、ユーザーの入力文字列と比較して  
 /// 一致または部分一致するキャラクターの正式名称を返却する。
+/// サーバー設定がある場合は、サーバー独自の愛称を先に照合する。
 ///
 /// # 引数
+/// * `data_dir` - データディレクトリ
 /// * `character` - ユーザー入力のキャラクター名またはニックネーム
+/// * `guild` - 実行サーバーの設定（DM の場合は None）
 ///
 /// # 戻り値
 /// 正式なキャラクター名を含む `Result<String>` を返す
-pub async fn find_character(character: &String) -> Result<String> {
+pub async fn find_character(
+    data_dir: &str,
+    character: &str,
+    guild: Option<&GuildConfig>,
+) -> Result<String> {
+    // サーバー独自の愛称照合　結果：完全一致すれば正式名称返却
+    if let Some(name) = guild.and_then(|g| g.find_nickname(character)) {
+        return Ok(name);
+    }
+
     // nicknames.json ファイル読み込み　結果：JSON文字列取得
-    let data_from_file = fs::read_to_string("data/nicknames.json").map_err(|e| {
-        AppError::FileNotFound(format!("nicknames.jsonの読み込みに失敗しました: {e}"))
-    })?;
+    let data_from_file =
+        fs::read_to_string(Path::new(data_dir).join("nicknames.json")).map_err(|e| {
+            AppError::FileNotFound(format!("nicknames.jsonの読み込みに失敗しました: {e}"))
+        })?;
 
     // JSON文字列を Nicknames 構造体のベクターへデシリアライズ　結果：vec_nicknames
     let vec_nicknames =
@@ -82,28 +97,34 @@ pub async fn find_character(character: &String) -> Result<String> {
 /// 入力がエイリアスであれば、実際の技入力に変換する。```
 ///
 /// # 引数
+/// * `data_dir` - データディレクトリ
 /// * `character_arg_altered` - 正式なキャラクター名
 /// * `character_move` - ユーザー入力の技名、入力、またはエイリアス
 /// * `moves_info` - キャラクターの技情報のスライス
+/// * `guild` - 実行サーバーの設定（サーバー独自のエイリアスを aliases.json より先に照合する）
 ///
 /// # 戻り値
 /// 該当技のインデックスを含む `Result<usize>` を返す
 pub async fn find_move_index(
+    data_dir: &str,
     character_arg_altered: &String,
     mut character_move: String,
     moves_info: &[MoveInfo],
+    guild: Option<&GuildConfig>,
 ) -> Result<usize> {
     // 対象キャラクターの aliases.json のパス生成　結果：aliases_path
-    let aliases_path = "data/".to_owned() + character_arg_altered + "/aliases.json";
-
-    // デバッグ出力: 検索対象の技名
-    println!(
-        "Finding move: '{}' for character '{}'",
-        character_move, character_arg_altered
-    );
+    let aliases_path = Path::new(data_dir)
+        .join(character_arg_altered)
+        .join("aliases.json");
+
+    // サーバー独自のエイリアス変換処理　結果：一致した場合は実際の技入力に変換
+    let guild_input = guild.and_then(|g| g.find_alias(character_arg_altered, &character_move));
+    if let Some(input) = &guild_input {
+        character_move = input.clone();
+    }
 
     // aliases.json が存在する場合エイリアス変換処理　結果：エイリアスに対応する実際の技入力を取得
-    if Path::new(&aliases_path).exists() {
+    if guild_input.is_none() && aliases_path.exists() {
         // ファイル読み込み&デシリアライズ　結果：move_aliases
         let data_from_file = fs::read_to_string(&aliases_path).map_err(|e| {
             AppError::FileNotFound(format!(
@@ -121,67 +142,15 @@ pub async fn find_move_index(
                 if y_aliases.to_lowercase() == character_move.to_lowercase() {
                     // エイリアスが見つかった場合、対応する技入力に変換
                     character_move = x_aliases.input.clone();
-                    println!("Alias found: '{}' → '{}'", y_aliases, character_move);
                     break;
                 }
             }
         }
     }
 
-    // 大文字小文字と空白を区別しない比較のための正規化
-    let normalized_character_move = character_move.to_lowercase().replace(' ', "");
-    println!(
-        "Normalized move input for search: '{}'",
-        normalized_character_move
-    );
-
     // 技リスト内で検索　結果：該当する技のインデックスを取得
-    for (i, x_move) in moves_info.iter().enumerate() {
-        // 技名が一致した場合インデックス返却
-        if x_move.name.to_lowercase() == character_move.to_lowercase() {
-            println!("Found move by name at index {}: '{}'", i, x_move.name);
-            return Ok(i);
-        }
-    }
-
-    for (i, x_move) in moves_info.iter().enumerate() {
-        // 技入力が一致した場合インデックス返却（正規化して比較）
-        let normalized_move_input = x_move.input.to_lowercase().replace(' ', "");
-        if normalized_move_input == normalized_character_move {
-            println!("Found move by input at index {}: '{}'", i, x_move.input);
-            return Ok(i);
-        }
-    }
-
-    // 技入力の一部一致で検索　結果：部分一致した技のインデックスを取得
-    for (i, x_move) in moves_info.iter().enumerate() {
-        // 技名に入力文字列が含まれる場合インデックス返却
-        if x_move
-            .name
-            .to_lowercase()
-            .contains(&character_move.to_lowercase())
-        {
-            println!(
-                "Found move by partial name match at index {}: '{}'",
-                i, x_move.name
-            );
-            return Ok(i);
-        }
-    }
-
-    for (i, x_move) in moves_info.iter().enumerate() {
-        // 技入力に入力文字列が含まれる場合インデックス返却
-        if x_move
-            .input
-            .to_lowercase()
-            .contains(&character_move.to_lowercase())
-        {
-            println!(
-                "Found move by partial input match at index {}: '{}'",
-                i, x_move.input
-            );
-            return Ok(i);
-        }
+    if let Some(i) = match_move_index(&character_move, moves_info) {
+        return Ok(i);
     }
 
     // エラーメッセージ生成と返却　結果：技が見つからなかった場合エラー返却
@@ -189,10 +158,68 @@ pub async fn find_move_index(
         "Move `{}` was not found for character `{}`!",
         character_move, character_arg_altered
     );
-    println!("Error: {}", error_msg);
     Err(AppError::MoveNotFound(error_msg))
 }
 
+/// 検索文字列に一致する技のインデックスを返す関数
+///
+/// # 概要
+/// 技名の完全一致、技入力の完全一致（大文字小文字・空白を無視）、技名の部分一致、技入力の部分一致の順に照合する。
+/// `aliases.json` の入力（"2D" 等の短い表記）から技を特定する場合も同じ規則を用いる。
+///
+/// # 引数
+/// * `query` - 技名・入力（エイリアス変換後）
+/// * `moves_info` - キャラクターの技情報のスライス
+///
+/// # 戻り値
+/// 一致した技のインデックス、見つからない場合はNone
+pub fn match_move_index(query: &str, moves_info: &[MoveInfo]) -> Option<usize> {
+    let query_lower = query.to_lowercase();
+    let normalized_query = query_lower.replace(' ', "");
+    moves_info
+        .iter()
+        .position(|m| m.name.to_lowercase() == query_lower)
+        .or_else(|| {
+            moves_info
+                .iter()
+                .position(|m| m.input.to_lowercase().replace(' ', "") == normalized_query)
+        })
+        .or_else(|| {
+            moves_info
+                .iter()
+                .position(|m| m.name.to_lowercase().contains(&query_lower))
+        })
+        .or_else(|| {
+            moves_info
+                .iter()
+                .position(|m| m.input.to_lowercase().contains(&query_lower))
+        })
+}
+
+/// 技毎のエイリアス一覧を返す関数
+///
+/// # 概要
+/// `aliases.json` の各エントリを [`match_move_index`] で技に対応付け、技情報と同じ順序で返す。
+///
+/// # 引数
+/// * `moves_info` - キャラクターの技情報のスライス（全技）
+/// * `aliases_data` - エイリアス情報のスライス
+///
+/// # 戻り値
+/// 技情報と同じ順序のエイリアス一覧
+pub fn aliases_by_move<'a>(
+    moves_info: &[MoveInfo],
+    aliases_data: &'a [MoveAliases],
+) -> Vec<Vec<&'a str>> {
+    let mut aliases = vec![Vec::new(); moves_info.len()];
+    for entry in aliases_data {
+        if let Some(index) = match_move_index(&entry.input, moves_info) {
+            aliases[index].extend(entry.aliases.iter().map(String::as_str));
+        }
+    }
+    aliases
+}
+
 /// 文字列から括弧内のコンテンツを抽出する関数
 ///
 /// # 引数
@@ -211,6 +238,74 @@ fn extract_bracket_content(input: &str) -> Option<String> {
     None
 }
 
+/// 技入力と検索文字列の一致判定関数
+///
+/// # 概要
+/// 技入力全体、括弧前の技名（例：「足払い(2D)」→「足払い」）、括弧内のコマンド（→「2D」）の
+/// いずれかが、大文字小文字・空白を無視して検索文字列と一致するか判定する。
+///
+/// # 引数
+/// * `move_input` - 技データ側の入力文字列
+/// * `query` - 比較対象の文字列（画像データの入力、エイリアスの入力等）
+///
+/// # 戻り値
+/// 一致時 true
+pub fn move_input_matches(move_input: &str, query: &str) -> bool {
+    let normalize = |s: &str| s.to_lowercase().replace(' ', "");
+    let query = normalize(query);
+    if query.is_empty() {
+        return false;
+    }
+
+    let before_bracket = move_input.split('(').next().unwrap_or("");
+    let bracket_content = extract_bracket_content(move_input).unwrap_or_default();
+
+    [move_input, before_bracket, &bracket_content]
+        .iter()
+        .any(|candidate| !candidate.trim().is_empty() && normalize(candidate) == query)
+}
+
+/// 技入力に対応する画像リンクのインデックスを返却する関数
+///
+/// # 引数
+/// * `move_input` - 技データ側の入力文字列
+/// * `image_links` - キャラクターの画像リンク情報のスライス
+///
+/// # 戻り値
+/// 一致する画像リンクのインデックス、見つからない場合はNone
+pub fn find_image_index(move_input: &str, image_links: &[ImageLinks]) -> Option<usize> {
+    image_links
+        .iter()
+        .position(|img| move_input_matches(move_input, &img.input))
+}
+
+/// 技に対応する技画像 URL 検索関数
+///
+/// # 概要
+/// 技入力・括弧前の技名・括弧内のコマンドのいずれかと一致する画像を優先し、
+/// 無い場合は画像データの入力に技入力を含むもの（最後に一致したもの）を返す。
+/// 技画像が空の画像データは対象外とする。
+///
+/// # 引数
+/// * `move_input` - 技データ側の入力文字列
+/// * `image_links` - キャラクターの画像リンク情報のスライス
+///
+/// # 戻り値
+/// 技画像 URL、見つからない場合はNone
+pub fn find_move_image<'a>(move_input: &str, image_links: &'a [ImageLinks]) -> Option<&'a str> {
+    let with_image = || image_links.iter().filter(|img| !img.move_img.is_empty());
+    let query = move_input.to_lowercase();
+
+    with_image()
+        .find(|img| move_input_matches(move_input, &img.input))
+        .or_else(|| {
+            with_image()
+                .rev()
+                .find(|img| img.input.to_lowercase().contains(&query))
+        })
+        .map(|img| img.move_img.as_str())
+}
+
 /// エイリアスから技の入力を検索する非同期関数
 ///
 /// # 概要
@@ -247,7 +342,6 @@ mod tests {
     use crate::test_utils::{
         create_test_json_file, create_test_move_aliases, create_test_move_info,
     };
-    use std::env;
     use tempfile::TempDir;
 
     // 一時ディレクトリにnicknames.jsonを作成するヘルパー関数
@@ -272,25 +366,6 @@ mod tests {
         path
     }
 
-    // テスト実行前に一時的に現在のディレクトリを変更し、テスト後に元に戻す構造体
-    struct TempWorkingDir {
-        original_dir: std::path::PathBuf,
-    }
-
-    impl TempWorkingDir {
-        fn new(dir: &std::path::Path) -> Self {
-            let original_dir = env::current_dir().expect("現在のディレクトリを取得できません");
-            env::set_current_dir(dir).expect("ディレクトリを変更できません");
-            Self { original_dir }
-        }
-    }
-
-    impl Drop for TempWorkingDir {
-        fn drop(&mut self) {
-            env::set_current_dir(&self.original_dir).expect("元のディレクトリに戻れません");
-        }
-    }
-
     #[tokio::test]
     async fn test_find_character() {
         // テスト用ディレクトリ準備
@@ -317,28 +392,70 @@ mod tests {
 
         create_test_json_file(&nicknames_path, content).expect("nicknames.jsonの作成に失敗");
 
-        // 一時的にカレントディレクトリを変更
-        let _temp_dir_guard = TempWorkingDir::new(temp_dir.path());
+        let data_dir = data_dir.to_str().unwrap();
 
         // 正確なキャラクター名のテスト
-        let result = find_character(&"sol".to_string())
+        let result = find_character(data_dir, "sol", None)
             .await
             .expect("キャラクター検索に失敗");
         assert_eq!(result, "Sol_Badguy");
 
         // ニックネームによるテスト
-        let result = find_character(&"カイ=キスク".to_string())
+        let result = find_character(data_dir, "カイ=キスク", None)
             .await
             .expect("キャラクター検索に失敗");
         assert_eq!(result, "Ky_Kiske");
 
         // 大文字小文字の区別なくテスト
-        let result = find_character(&"SOL".to_string())
+        let result = find_character(data_dir, "SOL", None)
             .await
             .expect("キャラクター検索に失敗");
         assert_eq!(result, "Sol_Badguy");
     }
 
+    #[tokio::test]
+    async fn test_find_with_guild_overlay() {
+        let mut guild = GuildConfig::default();
+        guild.nicknames.push(Nicknames {
+            character: "Sol_Badguy".to_string(),
+            nicknames: vec!["ソルちゃん".to_string()],
+        });
+        guild.aliases.insert(
+            "Test".to_string(),
+            vec![MoveAliases {
+                input: "236K".to_string(),
+                aliases: vec!["玉".to_string()],
+            }],
+        );
+
+        // サーバー独自の愛称は nicknames.json より先に照合される
+        let result = find_character("data", "ソルちゃん", Some(&guild))
+            .await
+            .unwrap();
+        assert_eq!(result, "Sol_Badguy");
+
+        let moves_info = create_test_move_info();
+        let index = find_move_index(
+            "data",
+            &"Test".to_string(),
+            "玉".to_string(),
+            &moves_info,
+            Some(&guild),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moves_info[index].input, "236K");
+        assert!(find_move_index(
+            "data",
+            &"Test".to_string(),
+            "玉".to_string(),
+            &moves_info,
+            None
+        )
+        .await
+        .is_err());
+    }
+
     #[tokio::test]
     async fn test_find_move_index() {
         // テストデータ準備
@@ -376,4 +493,81 @@ mod tests {
         let result = find_move_by_alias("不存在技", &moves_aliases).await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_aliases_by_move() {
+        let mut moves_info = create_test_move_info();
+        moves_info[0].input = "足払い(2D)".to_string();
+        moves_info[0].name = "足払い".to_string();
+        let aliases = vec![
+            MoveAliases {
+                input: "2D".to_string(),
+                aliases: vec!["sweep".to_string()],
+            },
+            MoveAliases {
+                input: "236K".to_string(),
+                aliases: vec!["Stun Edge".to_string()],
+            },
+            MoveAliases {
+                input: "6HS".to_string(),
+                aliases: vec!["unknown".to_string()],
+            },
+        ];
+        assert_eq!(
+            aliases_by_move(&moves_info, &aliases),
+            vec![vec!["sweep"], vec!["Stun Edge"]]
+        );
+    }
+
+    #[test]
+    fn test_move_input_matches() {
+        assert!(move_input_matches("足払い(2D)", "2D"));
+        assert!(move_input_matches("足払い(2D)", "足払い"));
+        assert!(move_input_matches("足払い(2D)", "足払い(2d)"));
+        assert!(move_input_matches("5 P", "5p"));
+        assert!(!move_input_matches("足払い(2D)", "2K"));
+        assert!(!move_input_matches("足払い(2D)", ""));
+    }
+
+    #[test]
+    fn test_find_image_index() {
+        let image_links = vec![
+            ImageLinks {
+                input: "2K".to_string(),
+                move_img: String::new(),
+                hitbox_img: Vec::new(),
+            },
+            ImageLinks {
+                input: "2D".to_string(),
+                move_img: String::new(),
+                hitbox_img: Vec::new(),
+            },
+        ];
+
+        assert_eq!(find_image_index("足払い(2D)", &image_links), Some(1));
+        assert_eq!(find_image_index("236K", &image_links), None);
+    }
+
+    #[test]
+    fn test_find_move_image() {
+        let link = |input: &str, move_img: &str| ImageLinks {
+            input: input.to_string(),
+            move_img: move_img.to_string(),
+            hitbox_img: Vec::new(),
+        };
+        let image_links = vec![
+            link("2D", ""),
+            link("2D", "2d.png"),
+            link("236K", "236k.png"),
+            link("j.236K", "j236k.png"),
+        ];
+
+        // 括弧内のコマンド一致（画像が空の項目は対象外）
+        assert_eq!(find_move_image("足払い(2D)", &image_links), Some("2d.png"));
+        // 完全一致は部分一致より優先
+        assert_eq!(find_move_image("236K", &image_links), Some("236k.png"));
+        // 部分一致は最後に一致したもの
+        assert_eq!(find_move_image("36K", &image_links), Some("j236k.png"));
+        assert_eq!(find_move_image("5P", &image_links), None);
+    }
 }
@@ -0,0 +1,369 @@
+//! `guild.rs`
+//!
+//! サーバー（ギルド）毎の設定を管理する機能を提供する。
+//! 設定はデータディレクトリ内の `guilds/<サーバーID>.json` に保存し、未設定の項目は既定値を用いる。
+
+use crate::aliases;
+use crate::common::embed::{EmbedView, FieldSelection, MoveField};
+use crate::error::{AppError, Result};
+use crate::{nickname_store, utils, Context, MoveAliases, MoveInfo, Nicknames};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// サーバー設定保存ディレクトリ名（データディレクトリ直下）
+pub const GUILDS_DIR: &str = "guilds";
+
+/// サーバー設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildConfig {
+    /// simple 表示の項目（未設定は既定値）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simple_fields: Option<Vec<MoveField>>,
+    /// advanced 表示の項目（未設定は既定値）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub advanced_fields: Option<Vec<MoveField>>,
+    /// 空項目（"-"）を表示しないか否か
+    #[serde(default)]
+    pub hide_empty: bool,
+    /// サーバー独自の愛称（nicknames.json より先に照合）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nicknames: Vec<Nicknames>,
+    /// サーバー独自の技エイリアス（キャラクター名毎、aliases.json より先に照合）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, Vec<MoveAliases>>,
+}
+
+/// サーバー設定ファイルパス生成関数
+pub fn config_path(data_dir: &str, guild_id: u64) -> PathBuf {
+    Path::new(data_dir)
+        .join(GUILDS_DIR)
+        .join(format!("{guild_id}.json"))
+}
+
+impl GuildConfig {
+    /// サーバー設定読み込み関数（未保存の場合は既定値、解析失敗時はエラー）
+    pub fn load(data_dir: &str, guild_id: u64) -> Result<Self> {
+        let path = config_path(data_dir, guild_id);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        utils::read_json_file(path)
+    }
+
+    /// コマンド実行サーバーの設定読み込み関数（DM・読み込み失敗時は None）
+    ///
+    /// 読み込みに失敗した場合はログに記録し、共通データのみで検索・表示する。
+    pub fn for_context(ctx: &Context<'_>) -> Option<Self> {
+        let guild_id = ctx.guild_id()?;
+        match Self::load(&ctx.data().data_dir, guild_id.get()) {
+            Ok(config) => Some(config),
+            Err(err) => {
+                println!(
+                    "{}",
+                    format!("Guild {guild_id}: failed to load config: {err}").red()
+                );
+                None
+            }
+        }
+    }
+
+    /// サーバー設定保存関数
+    ///
+    /// 既存の設定ファイルを解析できない場合は、内容を失わないよう上書きせずにエラーを返す。
+    pub fn save(&self, data_dir: &str, guild_id: u64) -> Result<()> {
+        let path = config_path(data_dir, guild_id);
+        if path.exists() {
+            if let Err(err) = utils::read_json_file::<Self>(&path) {
+                return Err(AppError::Config(format!(
+                    "{} is corrupted and was not overwritten: {err}",
+                    path.display()
+                )));
+            }
+        }
+        utils::ensure_directory_exists(Path::new(data_dir).join(GUILDS_DIR))?;
+        utils::write_json_file(path, self)
+    }
+
+    /// 表示形式毎の設定項目への参照を返す。
+    pub fn fields_mut(&mut self, view: EmbedView) -> &mut Option<Vec<MoveField>> {
+        match view {
+            EmbedView::Simple => &mut self.simple_fields,
+            EmbedView::Advanced => &mut self.advanced_fields,
+        }
+    }
+
+    /// 表示項目の指定を返す（未設定は既定値）。
+    pub fn selection(&self, view: EmbedView) -> FieldSelection {
+        let fields = match view {
+            EmbedView::Simple => self.simple_fields.clone(),
+            EmbedView::Advanced => self.advanced_fields.clone(),
+        };
+        FieldSelection {
+            fields: fields.unwrap_or_else(|| view.default_fields()),
+            hide_empty: self.hide_empty,
+        }
+    }
+}
+
+impl GuildConfig {
+    /// サーバー独自の愛称から正式なキャラクター名を返す（大文字小文字を無視した完全一致）。
+    pub fn find_nickname(&self, character: &str) -> Option<String> {
+        let key = character.trim().to_lowercase();
+        self.nicknames
+            .iter()
+            .find(|entry| entry.nicknames.iter().any(|n| n.to_lowercase() == key))
+            .map(|entry| entry.character.clone())
+    }
+
+    /// サーバー独自のエイリアスから技入力を返す（大文字小文字を無視した完全一致）。
+    pub fn find_alias(&self, character: &str, alias: &str) -> Option<String> {
+        let key = alias.to_lowercase();
+        self.aliases
+            .get(character)?
+            .iter()
+            .find(|entry| entry.aliases.iter().any(|a| a.to_lowercase() == key))
+            .map(|entry| entry.input.clone())
+    }
+
+    /// サーバー独自の愛称を追加する。
+    ///
+    /// 共通データ・サーバー独自の愛称と正式名のいずれかと重複する場合は理由を返す。
+    pub fn add_nickname(
+        &mut self,
+        character: &str,
+        nickname: &str,
+        global: &[Nicknames],
+    ) -> std::result::Result<(), String> {
+        nickname_store::validate_nickname(nickname)?;
+        let merged: Vec<Nicknames> = global.iter().chain(&self.nicknames).cloned().collect();
+        if let Some(conflict) = nickname_store::nickname_conflict(nickname, &merged) {
+            return Err(conflict + ".");
+        }
+        let nickname = nickname.trim().to_string();
+        match self
+            .nicknames
+            .iter_mut()
+            .find(|entry| entry.character == character)
+        {
+            Some(entry) => entry.nicknames.push(nickname),
+            None => self.nicknames.push(Nicknames {
+                character: character.to_string(),
+                nicknames: vec![nickname],
+            }),
+        }
+        Ok(())
+    }
+
+    /// サーバー独自の愛称を削除し、削除した愛称を返す。
+    pub fn remove_nickname(
+        &mut self,
+        character: &str,
+        nickname: &str,
+    ) -> std::result::Result<String, String> {
+        let removed = nickname_store::remove_nickname(&mut self.nicknames, character, nickname)?;
+        self.nicknames.retain(|entry| !entry.nicknames.is_empty());
+        Ok(removed)
+    }
+
+    /// サーバー独自の技エイリアスを追加する。
+    ///
+    /// 共通データ・サーバー独自のエイリアスと技名・技入力のいずれかと重複する場合は理由を返す。
+    pub fn add_alias(
+        &mut self,
+        character: &str,
+        input: &str,
+        alias: &str,
+        global: &[MoveAliases],
+        moves: &[MoveInfo],
+    ) -> std::result::Result<(), String> {
+        aliases::validate_alias(alias)?;
+        let entries = self.aliases.entry(character.to_string()).or_default();
+        let merged: Vec<MoveAliases> = global.iter().chain(entries.iter()).cloned().collect();
+        if let Some(conflict) = aliases::alias_conflict(alias, &merged, moves) {
+            return Err(conflict + ".");
+        }
+        aliases::insert_alias(entries, moves, input, alias.trim());
+        Ok(())
+    }
+
+    /// サーバー独自の技エイリアスを削除し、対象技の入力を返す。
+    pub fn remove_alias(
+        &mut self,
+        character: &str,
+        alias: &str,
+    ) -> std::result::Result<String, String> {
+        let not_found = || format!("`{alias}` is not a server alias of `{character}`.");
+        let entries = self.aliases.get_mut(character).ok_or_else(not_found)?;
+        let key = alias.trim().to_lowercase();
+        let entry = entries
+            .iter_mut()
+            .find(|entry| entry.aliases.iter().any(|a| a.to_lowercase() == key))
+            .ok_or_else(not_found)?;
+        entry.aliases.retain(|a| a.to_lowercase() != key);
+        let input = entry.input.clone();
+        entries.retain(|entry| !entry.aliases.is_empty());
+        if entries.is_empty() {
+            self.aliases.remove(character);
+        }
+        Ok(input)
+    }
+}
+
+/// コマンド実行時の表示項目決定関数
+///
+/// # 概要
+/// コマンド引数の指定を優先し、無い場合はサーバー設定、サーバー外（DM）では既定値を用いる。
+///
+/// # 引数
+/// * `data_dir` - データディレクトリ
+/// * `guild_id` - サーバーID（DM の場合は None）
+/// * `view` - 表示形式
+/// * `fields` - コマンド引数の項目指定（"," 区切り）
+/// * `hide_empty` - コマンド引数の空項目非表示指定
+///
+/// # 戻り値
+/// 表示項目の指定（項目指定が不正な場合はエラー）
+pub fn resolve_selection(
+    data_dir: &str,
+    guild_id: Option<u64>,
+    view: EmbedView,
+    fields: Option<&str>,
+    hide_empty: Option<bool>,
+) -> Result<FieldSelection> {
+    let mut selection = guild_id.map_or_else(
+        || Ok(FieldSelection::for_view(view)),
+        |id| GuildConfig::load(data_dir, id).map(|config| config.selection(view)),
+    )?;
+    if let Some(fields) = fields {
+        selection.fields = crate::common::embed::parse_fields(fields)?;
+    }
+    if let Some(hide_empty) = hide_empty {
+        selection.hide_empty = hide_empty;
+    }
+    Ok(selection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_guild_config_round_trip() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        assert_eq!(
+            GuildConfig::load(data_dir, 1).unwrap(),
+            GuildConfig::default()
+        );
+
+        let mut config = GuildConfig::default();
+        *config.fields_mut(EmbedView::Simple) = Some(vec![MoveField::Startup]);
+        config.hide_empty = true;
+        config.save(data_dir, 1).unwrap();
+        assert_eq!(GuildConfig::load(data_dir, 1).unwrap(), config);
+        assert!(GuildConfig::load(data_dir, 2)
+            .unwrap()
+            .simple_fields
+            .is_none());
+    }
+
+    #[test]
+    fn test_guild_config_corrupt_file() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let path = config_path(data_dir, 1);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        assert!(GuildConfig::load(data_dir, 1).is_err());
+        assert!(GuildConfig::default().save(data_dir, 1).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn test_resolve_selection() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let config = GuildConfig {
+            simple_fields: Some(vec![MoveField::OnBlock]),
+            advanced_fields: None,
+            hide_empty: true,
+            ..Default::default()
+        };
+        config.save(data_dir, 7).unwrap();
+
+        // サーバー設定
+        let selection =
+            resolve_selection(data_dir, Some(7), EmbedView::Simple, None, None).unwrap();
+        assert_eq!(selection.fields, vec![MoveField::OnBlock]);
+        assert!(selection.hide_empty);
+
+        // コマンド引数が優先
+        let selection = resolve_selection(
+            data_dir,
+            Some(7),
+            EmbedView::Simple,
+            Some("startup"),
+            Some(false),
+        )
+        .unwrap();
+        assert_eq!(selection.fields, vec![MoveField::Startup]);
+        assert!(!selection.hide_empty);
+
+        // DM は既定値
+        assert_eq!(
+            resolve_selection(data_dir, None, EmbedView::Advanced, None, None).unwrap(),
+            FieldSelection::for_view(EmbedView::Advanced)
+        );
+        assert!(resolve_selection(data_dir, None, EmbedView::Simple, Some("x"), None).is_err());
+    }
+
+    #[test]
+    fn test_guild_nicknames() {
+        let global = vec![Nicknames {
+            character: "Sol_Badguy".to_string(),
+            nicknames: vec!["SO".to_string()],
+        }];
+        let mut config = GuildConfig::default();
+        assert!(config.add_nickname("Ky_Kiske", "団長", &global).is_ok());
+        assert_eq!(config.find_nickname("団長"), Some("Ky_Kiske".to_string()));
+
+        // 共通データ・サーバー独自の愛称と重複
+        assert!(config.add_nickname("Ky_Kiske", "so", &global).is_err());
+        assert!(config.add_nickname("May", "団長", &global).is_err());
+
+        assert_eq!(config.remove_nickname("Ky_Kiske", "団長").unwrap(), "団長");
+        assert!(config.nicknames.is_empty());
+        assert!(config.remove_nickname("Ky_Kiske", "団長").is_err());
+    }
+
+    #[test]
+    fn test_guild_aliases() {
+        let moves = crate::test_utils::create_test_move_info();
+        let global = crate::test_utils::create_test_move_aliases();
+        let mut config = GuildConfig::default();
+        assert!(config
+            .add_alias("Test", "236K", "玉", &global, &moves)
+            .is_ok());
+        assert_eq!(config.find_alias("Test", "玉"), Some("236K".to_string()));
+        assert_eq!(config.find_alias("Other", "玉"), None);
+
+        // 共通エイリアス・技入力・サーバー独自のエイリアスと重複
+        assert!(config
+            .add_alias("Test", "5P", "fireball", &global, &moves)
+            .is_err());
+        assert!(config
+            .add_alias("Test", "5P", "236k", &global, &moves)
+            .is_err());
+        assert!(config
+            .add_alias("Test", "5P", "玉", &global, &moves)
+            .is_err());
+
+        assert_eq!(config.remove_alias("Test", "玉").unwrap(), "236K");
+        assert!(config.aliases.is_empty());
+        assert!(config.remove_alias("Test", "玉").is_err());
+    }
+}
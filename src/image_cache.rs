@@ -0,0 +1,478 @@
+//! `image_cache.rs`
+//!
+//! 技画像・ヒットボックス画像のローカルキャッシュ機能を提供する。
+//! images.json が参照する Dustloop 画像をデータディレクトリ内へ保存し、取得できなかった画像（404 等）を記録する。
+//! リモート画像が利用不可と記録されている場合、ローカル複製を Discord 添付ファイルとして提供する。
+
+use crate::error::{AppError, Result};
+use crate::{check, utils, ImageLinks};
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// キャッシュディレクトリ名（データディレクトリ直下）
+pub const CACHE_DIR: &str = "image_cache";
+
+/// マニフェストファイル名
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// 1 画像あたりの最大取得サイズ（バイト）
+const MAX_IMAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// 画像取得のタイムアウト（秒）
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// 画像キャッシュ状態
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheStatus {
+    /// 取得成功（ローカル保存済み）
+    Cached,
+    /// リモートに存在しない（404/410）
+    NotFound,
+    /// 通信エラー等による取得失敗
+    Failed,
+}
+
+/// 画像単位のキャッシュ記録
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    /// 画像を参照するキャラクター
+    pub character: String,
+    /// 最終確認時の状態
+    pub status: CacheStatus,
+    /// キャッシュディレクトリからの相対パス（保存済みの場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// HTTP ステータスやエラー内容
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+    /// 最終確認時刻（UNIX 秒）
+    pub checked_at: u64,
+}
+
+/// キャッシュマニフェスト（画像 URL → 記録）
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CacheManifest {
+    /// 画像 URL 別の記録
+    pub entries: BTreeMap<String, CacheEntry>,
+}
+
+/// 画像取得結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchResult {
+    /// 取得成功（画像バイト列）
+    Ok(Vec<u8>),
+    /// リモートに存在しない（HTTP ステータス）
+    NotFound(u16),
+    /// 取得失敗（エラー内容）
+    Failed(String),
+}
+
+/// キャラクター単位の同期結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    /// 対象キャラクター
+    pub character: String,
+    /// 参照画像総数
+    pub total: usize,
+    /// 新規取得数
+    pub downloaded: usize,
+    /// 取得済みのため省略した数
+    pub already_cached: usize,
+    /// リモートに存在しない画像 URL
+    pub not_found: Vec<String>,
+    /// 取得失敗画像 URL
+    pub failed: Vec<String>,
+}
+
+/// キャッシュディレクトリパス生成関数
+pub fn cache_dir(data_dir: &str) -> PathBuf {
+    Path::new(data_dir).join(CACHE_DIR)
+}
+
+/// 現在時刻（UNIX 秒）取得関数
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// 画像 URL からキャッシュファイル名を生成する関数
+/// URL 形式が不正な場合は None を返す。
+pub fn cache_file_name(url: &str) -> Option<String> {
+    if !check::is_valid_image_url(url) {
+        return None;
+    }
+    url.rsplit('/').next().map(str::to_string)
+}
+
+impl CacheManifest {
+    /// マニフェスト読み込み関数
+    /// 未作成・解析不能の場合は空のマニフェストを返す。
+    pub fn load(data_dir: &str) -> Self {
+        let path = cache_dir(data_dir).join(MANIFEST_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+        utils::read_json_file(path).unwrap_or_default()
+    }
+
+    /// マニフェスト保存関数
+    pub fn save(&self, data_dir: &str) -> Result<()> {
+        let dir = cache_dir(data_dir);
+        utils::ensure_directory_exists(&dir)?;
+        utils::write_json_file(dir.join(MANIFEST_FILE), self)
+    }
+
+    /// ローカル複製パス取得関数
+    /// 保存済みかつファイルが存在する場合のみ返す。
+    pub fn local_file(&self, data_dir: &str, url: &str) -> Option<PathBuf> {
+        let file = self.entries.get(url)?.file.as_ref()?;
+        let path = cache_dir(data_dir).join(file);
+        path.is_file().then_some(path)
+    }
+
+    /// リモート利用不可（NotFound/Failed）と記録済みか否かを返す。
+    pub fn is_unavailable(&self, url: &str) -> bool {
+        self.entries
+            .get(url)
+            .is_some_and(|entry| entry.status != CacheStatus::Cached)
+    }
+
+    /// 指定キャラクターのリモート利用不可画像一覧を返す。
+    pub fn missing_for(&self, character: &str) -> Vec<(&str, &CacheEntry)> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| {
+                entry.character == character && entry.status != CacheStatus::Cached
+            })
+            .map(|(url, entry)| (url.as_str(), entry))
+            .collect()
+    }
+}
+
+/// サイズ上限付き読み込み関数（上限を超える場合はエラー）
+fn read_limited(reader: impl Read, limit: u64) -> std::result::Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    reader
+        .take(limit + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    if bytes.len() as u64 > limit {
+        return Err(format!("image exceeds {limit} bytes"));
+    }
+    Ok(bytes)
+}
+
+/// 画像取得用 HTTP エージェント（タイムアウト設定済み）
+fn agent() -> &'static ureq::Agent {
+    static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+    AGENT.get_or_init(|| {
+        ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+            .build()
+    })
+}
+
+/// Dustloop 画像取得関数（ブロッキング）
+/// [`MAX_IMAGE_BYTES`] を超える画像・タイムアウトは取得失敗とする。
+pub fn fetch_remote(url: &str) -> FetchResult {
+    match agent().get(url).call() {
+        Ok(response) => match read_limited(response.into_reader(), MAX_IMAGE_BYTES) {
+            Ok(bytes) => FetchResult::Ok(bytes),
+            Err(detail) => FetchResult::Failed(detail),
+        },
+        Err(ureq::Error::Status(code @ (404 | 410), _)) => FetchResult::NotFound(code),
+        Err(ureq::Error::Status(code, _)) => FetchResult::Failed(format!("HTTP {code}")),
+        Err(e) => FetchResult::Failed(e.to_string()),
+    }
+}
+
+/// 画像参照 URL 一覧生成関数（技画像・ヒットボックス画像、重複・不正形式除外）
+fn referenced_urls(image_links: &[ImageLinks]) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for image in image_links {
+        for url in std::iter::once(&image.move_img).chain(image.hitbox_img.iter()) {
+            if cache_file_name(url).is_some() && !urls.contains(url) {
+                urls.push(url.clone());
+            }
+        }
+    }
+    urls
+}
+
+/// キャラクター画像同期関数（取得処理指定版）
+///
+/// # 概要
+/// キャラクターの images.json が参照する画像を取得し、キャッシュへ保存してマニフェストを更新する。
+/// `force` が偽の場合、保存済みの画像は再取得しない。
+/// 再取得で利用不可となった画像の既存ローカル複製は保持する。
+///
+/// # 引数
+/// * `data_dir` - データディレクトリのパス
+/// * `character` - 正式なキャラクター名
+/// * `manifest` - 更新対象マニフェスト
+/// * `force` - 保存済み画像も再確認するか否か
+/// * `fetch` - 画像取得関数
+pub fn sync_character_with<F>(
+    data_dir: &str,
+    character: &str,
+    manifest: &mut CacheManifest,
+    force: bool,
+    mut fetch: F,
+) -> Result<SyncSummary>
+where
+    F: FnMut(&str) -> FetchResult,
+{
+    let images_path = Path::new(data_dir).join(character).join("images.json");
+    let image_links: Vec<ImageLinks> = utils::read_json_file(&images_path)?;
+    let char_cache_dir = cache_dir(data_dir).join(character);
+    utils::ensure_directory_exists(&char_cache_dir)?;
+
+    let urls = referenced_urls(&image_links);
+    let mut summary = SyncSummary {
+        character: character.to_string(),
+        total: urls.len(),
+        ..SyncSummary::default()
+    };
+
+    for url in urls {
+        if !force && !manifest.is_unavailable(&url) && manifest.local_file(data_dir, &url).is_some()
+        {
+            summary.already_cached += 1;
+            continue;
+        }
+
+        let file_name = cache_file_name(&url).unwrap_or_default();
+        let relative = format!("{character}/{file_name}");
+        let previous_file = manifest.entries.get(&url).and_then(|e| e.file.clone());
+
+        let entry = match fetch(&url) {
+            FetchResult::Ok(bytes) => {
+                utils::write_file_atomic(char_cache_dir.join(&file_name), &bytes).map_err(|e| {
+                    AppError::DataProcessing(format!("画像の保存に失敗しました: {url} - {e}"))
+                })?;
+                summary.downloaded += 1;
+                CacheEntry {
+                    character: character.to_string(),
+                    status: CacheStatus::Cached,
+                    file: Some(relative),
+                    detail: String::new(),
+                    checked_at: now_secs(),
+                }
+            }
+            FetchResult::NotFound(code) => {
+                summary.not_found.push(url.clone());
+                CacheEntry {
+                    character: character.to_string(),
+                    status: CacheStatus::NotFound,
+                    file: previous_file,
+                    detail: format!("HTTP {code}"),
+                    checked_at: now_secs(),
+                }
+            }
+            FetchResult::Failed(detail) => {
+                summary.failed.push(url.clone());
+                CacheEntry {
+                    character: character.to_string(),
+                    status: CacheStatus::Failed,
+                    file: previous_file,
+                    detail,
+                    checked_at: now_secs(),
+                }
+            }
+        };
+        manifest.entries.insert(url, entry);
+    }
+
+    Ok(summary)
+}
+
+/// キャラクター画像同期関数（Dustloop から取得、ブロッキング）
+pub fn sync_character(
+    data_dir: &str,
+    character: &str,
+    manifest: &mut CacheManifest,
+    force: bool,
+) -> Result<SyncSummary> {
+    sync_character_with(data_dir, character, manifest, force, fetch_remote)
+}
+
+/// 埋め込み画像ローカル化構造体
+///
+/// リモート利用不可と記録された画像 URL を `attachment://<ファイル名>` 参照へ置換し、
+/// 置換した画像を返信の添付ファイルとして追加する。
+pub struct LocalImages {
+    /// データディレクトリのパス
+    data_dir: String,
+    /// キャッシュマニフェスト
+    manifest: CacheManifest,
+    /// 置換済み画像（添付参照, ローカルパス）
+    replaced: Vec<(String, PathBuf)>,
+}
+
+impl LocalImages {
+    /// マニフェストを読み込み生成する。
+    pub fn load(data_dir: &str) -> Self {
+        Self {
+            data_dir: data_dir.to_string(),
+            manifest: CacheManifest::load(data_dir),
+            replaced: Vec::new(),
+        }
+    }
+
+    /// 画像 URL 解決関数
+    /// リモート利用不可かつローカル複製が存在する場合は添付参照を、それ以外は元の URL を返す。
+    pub fn url(&mut self, url: &str) -> String {
+        if !self.manifest.is_unavailable(url) {
+            return url.to_string();
+        }
+        let Some(path) = self.manifest.local_file(&self.data_dir, url) else {
+            return url.to_string();
+        };
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let reference = format!("attachment://{file_name}");
+        if !self.replaced.iter().any(|(r, _)| *r == reference) {
+            self.replaced.push((reference.clone(), path));
+        }
+        reference
+    }
+
+    /// 画像リンク群の URL を一括解決する。
+    pub fn localize_links(&mut self, image_links: &mut [ImageLinks]) {
+        for image in image_links {
+            image.move_img = self.url(&image.move_img);
+            for hitbox in &mut image.hitbox_img {
+                *hitbox = self.url(hitbox);
+            }
+        }
+    }
+
+    /// 埋め込み内で実際に参照されている置換画像の添付ファイルを生成する。
+    pub async fn files_for(&self, embeds: &[CreateEmbed]) -> Vec<CreateAttachment> {
+        let mut files = Vec::new();
+        if self.replaced.is_empty() {
+            return files;
+        }
+        let embeds_json = serde_json::to_string(embeds).unwrap_or_default();
+        for (reference, path) in &self.replaced {
+            if !embeds_json.contains(reference) {
+                continue;
+            }
+            match CreateAttachment::path(path).await {
+                Ok(attachment) => files.push(attachment),
+                Err(e) => println!("Failed to attach cached image '{}': {e}", path.display()),
+            }
+        }
+        files
+    }
+
+    /// 返信添付関数
+    /// 埋め込み内で実際に参照されている置換画像のみ添付する。
+    pub async fn attach(self, mut reply: poise::CreateReply) -> poise::CreateReply {
+        for attachment in self.files_for(&reply.embeds).await {
+            reply = reply.attachment(attachment);
+        }
+        reply
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_dir_structure, create_test_json_file};
+
+    const OK_URL: &str = "https://www.dustloop.com/wiki/images/0/0a/GGST_Sol_Badguy_2D.png";
+    const GONE_URL: &str = "https://www.dustloop.com/wiki/images/3/35/GGST_Sol_2D_Hitbox.png";
+
+    fn setup(temp_path: &Path) {
+        create_test_json_file(
+            temp_path.join("Sol_Badguy").join("images.json"),
+            &format!(
+                r#"[{{"input":"2D","move_img":"{OK_URL}","hitbox_img":["{GONE_URL}"]}},
+                    {{"input":"5P","move_img":"https://www.dustloop.com/wiki/images/d/d4/","hitbox_img":[]}}]"#
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_read_limited_rejects_oversized() {
+        assert_eq!(read_limited(&b"1234"[..], 4).unwrap(), b"1234");
+        assert!(read_limited(&b"12345"[..], 4).is_err());
+    }
+
+    #[test]
+    fn test_sync_character_records_not_found() {
+        let (_temp_dir, temp_path) = create_test_dir_structure();
+        setup(&temp_path);
+        let data_dir = temp_path.to_str().unwrap();
+        let mut manifest = CacheManifest::default();
+
+        let fetch = |url: &str| {
+            if url == OK_URL {
+                FetchResult::Ok(vec![0x89, b'P', b'N', b'G'])
+            } else {
+                FetchResult::NotFound(404)
+            }
+        };
+        let summary =
+            sync_character_with(data_dir, "Sol_Badguy", &mut manifest, false, fetch).unwrap();
+
+        // 不正形式 URL は対象外
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.downloaded, 1);
+        assert_eq!(summary.not_found, vec![GONE_URL.to_string()]);
+        assert!(manifest.local_file(data_dir, OK_URL).is_some());
+        assert_eq!(manifest.missing_for("Sol_Badguy").len(), 1);
+
+        // 保存済み画像は再取得しない
+        let summary = sync_character_with(data_dir, "Sol_Badguy", &mut manifest, false, |_| {
+            FetchResult::Failed("offline".to_string())
+        })
+        .unwrap();
+        assert_eq!(summary.already_cached, 1);
+        assert_eq!(summary.failed, vec![GONE_URL.to_string()]);
+
+        // マニフェストの保存・再読込
+        manifest.save(data_dir).unwrap();
+        let reloaded = CacheManifest::load(data_dir);
+        assert_eq!(reloaded.entries, manifest.entries);
+    }
+
+    #[test]
+    fn test_local_images_uses_cached_copy_when_unavailable() {
+        let (_temp_dir, temp_path) = create_test_dir_structure();
+        setup(&temp_path);
+        let data_dir = temp_path.to_str().unwrap();
+        let mut manifest = CacheManifest::default();
+        sync_character_with(data_dir, "Sol_Badguy", &mut manifest, false, |_| {
+            FetchResult::Ok(vec![1, 2, 3])
+        })
+        .unwrap();
+
+        // 再確認でリモート消失 → ローカル複製は保持
+        sync_character_with(data_dir, "Sol_Badguy", &mut manifest, true, |url| {
+            if url == OK_URL {
+                FetchResult::Ok(vec![1, 2, 3])
+            } else {
+                FetchResult::NotFound(404)
+            }
+        })
+        .unwrap();
+        manifest.save(data_dir).unwrap();
+
+        let mut local = LocalImages::load(data_dir);
+        assert_eq!(local.url(OK_URL), OK_URL);
+        assert_eq!(local.url(GONE_URL), "attachment://GGST_Sol_2D_Hitbox.png");
+        assert_eq!(local.replaced.len(), 1);
+    }
+}
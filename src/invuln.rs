@@ -0,0 +1,456 @@
+//! `invuln.rs`
+//!
+//! 技の無敵表記（`MoveInfo::invincibility`）を解析し、種別付きのフレーム範囲を提供する。
+//! "1-9 Strike"・"Below Crouch 10-22"・"[10-25 Guard Point]"・"18F onwards Airborne" 等の表記に対応する。
+
+/// 無敵種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InvulnKind {
+    /// 完全無敵
+    Full,
+    /// 打撃無敵
+    Strike,
+    /// 当て身・ガードポイント・パリィ
+    GuardPoint,
+    /// アーマー
+    Armor,
+    /// 飛び道具無敵
+    Projectile,
+    /// 投げ無敵
+    Throw,
+    /// 上半身無敵
+    UpperBody,
+    /// 姿勢の低さによる回避（Below Crouch / Low Profile 等）
+    LowProfile,
+    /// 空中判定
+    Airborne,
+    /// その他（Pass-through 等）
+    Other,
+}
+
+impl InvulnKind {
+    /// 表示名を返す。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Full => "Full",
+            Self::Strike => "Strike",
+            Self::GuardPoint => "Guard point",
+            Self::Armor => "Armor",
+            Self::Projectile => "Projectile",
+            Self::Throw => "Throw",
+            Self::UpperBody => "Upper body",
+            Self::LowProfile => "Low profile",
+            Self::Airborne => "Airborne",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// 未分類の範囲（開始フレーム, 終了フレーム）
+type RawRange = (u32, Option<u32>);
+
+/// 種別判定用キーワード（小文字、部分一致）
+const KIND_KEYWORDS: [(&str, InvulnKind); 17] = [
+    ("full", InvulnKind::Full),
+    ("strike", InvulnKind::Strike),
+    ("guard", InvulnKind::GuardPoint),
+    ("parry", InvulnKind::GuardPoint),
+    ("armo", InvulnKind::Armor),
+    ("projectile", InvulnKind::Projectile),
+    ("throw", InvulnKind::Throw),
+    ("above waist", InvulnKind::UpperBody),
+    ("upper body", InvulnKind::UpperBody),
+    ("crouch", InvulnKind::LowProfile),
+    ("low profile", InvulnKind::LowProfile),
+    ("low crush", InvulnKind::LowProfile),
+    ("below waist", InvulnKind::LowProfile),
+    ("below knee", InvulnKind::LowProfile),
+    ("lower body", InvulnKind::LowProfile),
+    ("foot", InvulnKind::LowProfile),
+    ("airborne", InvulnKind::Airborne),
+];
+
+/// 無敵範囲
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvulnRange {
+    /// 無敵種別
+    pub kind: InvulnKind,
+    /// 開始フレーム（1 始まり）
+    pub start: u32,
+    /// 終了フレーム（None は技の終わりまで、または "Until ..." 等の不定）
+    pub end: Option<u32>,
+}
+
+impl InvulnRange {
+    /// 指定フレームを含むか否かを返す。
+    pub fn contains(&self, frame: u32) -> bool {
+        frame >= self.start && self.end.is_none_or(|end| frame <= end)
+    }
+}
+
+/// 表記の構成要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    /// フレーム範囲
+    Range(u32, Option<u32>),
+    /// 種別表記
+    Label(String),
+}
+
+/// 種別表記から無敵種別を判定する関数
+/// "Strike/Projectile" のように複数種別を含む場合は全て返す。
+pub fn classify(label: &str) -> Vec<InvulnKind> {
+    let lower = label.to_lowercase();
+    let mut kinds: Vec<InvulnKind> = KIND_KEYWORDS
+        .iter()
+        .filter(|(keyword, _)| lower.contains(keyword))
+        .map(|(_, kind)| *kind)
+        .collect();
+    // "high profile" は下段回避扱い（空中判定相当）
+    if lower.contains("high profile") {
+        kinds.push(InvulnKind::Airborne);
+    }
+    kinds.sort();
+    kinds.dedup();
+    if kinds.is_empty() {
+        kinds.push(InvulnKind::Other);
+    }
+    kinds
+}
+
+/// 数値読み取り関数（末尾の "F" は読み飛ばす）
+fn read_number(chars: &[char], i: &mut usize) -> Option<u32> {
+    let start = *i;
+    while *i < chars.len() && chars[*i].is_ascii_digit() {
+        *i += 1;
+    }
+    let number = chars[start..*i].iter().collect::<String>().parse().ok()?;
+    if chars.get(*i) == Some(&'F') {
+        *i += 1;
+    }
+    Some(number)
+}
+
+/// 閉じ括弧までの読み飛ばし関数
+fn skip_group(chars: &[char], i: &mut usize, close: char) {
+    while *i < chars.len() && chars[*i] != close {
+        *i += 1;
+    }
+    *i += 1;
+}
+
+/// 範囲終了側の読み取り関数（"-"・"~" の直後から）
+/// "25"・"25~41"・"[20~77]"・"(Recovery 17)"・"Until Landing" に対応する（不定の終端は最大値を採用）。
+fn read_range_end(chars: &[char], i: &mut usize) -> Option<u32> {
+    match chars.get(*i) {
+        Some(c) if c.is_ascii_digit() => {
+            let end = read_number(chars, i);
+            // "25~41" は最大値を採用
+            if chars.get(*i) == Some(&'~') && chars.get(*i + 1).is_some_and(char::is_ascii_digit) {
+                *i += 1;
+                return read_number(chars, i);
+            }
+            end
+        }
+        Some('[') => {
+            *i += 1;
+            let mut end = read_number(chars, i);
+            // "[20~77]" は最大値を採用
+            if matches!(chars.get(*i), Some('~' | '-'))
+                && chars.get(*i + 1).is_some_and(char::is_ascii_digit)
+            {
+                *i += 1;
+                end = read_number(chars, i);
+            }
+            skip_group(chars, i, ']');
+            end
+        }
+        Some('(') => {
+            skip_group(chars, i, ')');
+            None
+        }
+        _ => {
+            // "Until Released" 等の単語は終了条件として読み飛ばす
+            while *i < chars.len() && (chars[*i].is_alphabetic() || chars[*i] == ' ') {
+                *i += 1;
+            }
+            None
+        }
+    }
+}
+
+/// 1 節（"," 区切り）を構成要素列へ分解する関数
+fn clause_items(clause: &str) -> Vec<Item> {
+    let chars: Vec<char> = clause.chars().collect();
+    let mut items = Vec::new();
+    let mut label = String::new();
+    let mut i = 0;
+
+    let flush = |label: &mut String, items: &mut Vec<Item>| {
+        let text = label.trim().trim_end_matches(':').trim();
+        if !text.is_empty() {
+            items.push(Item::Label(text.to_string()));
+        }
+        label.clear();
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_ascii_digit() {
+            // "Upon Reflection+18" 等の相対フレームは範囲ではなく種別表記の一部とする
+            if label.trim_end().ends_with('+') {
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    label.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            let number_start = i;
+            let start = read_number(&chars, &mut i).unwrap_or(0);
+            // "1 Hit Armor" 等の回数も種別表記の一部とする
+            let rest = chars[i..].iter().collect::<String>().to_lowercase();
+            if rest.starts_with(" hit") {
+                label.extend(&chars[number_start..i]);
+                continue;
+            }
+            flush(&mut label, &mut items);
+            let end = if matches!(chars.get(i), Some('-' | '~')) {
+                i += 1;
+                read_range_end(&chars, &mut i)
+            } else if chars[i..]
+                .iter()
+                .collect::<String>()
+                .trim_start()
+                .to_lowercase()
+                .starts_with("onwards")
+            {
+                // "18F onwards" は技の終わりまで
+                while chars.get(i) == Some(&' ') {
+                    i += 1;
+                }
+                i += "onwards".len();
+                None
+            } else {
+                Some(start)
+            };
+            items.push(Item::Range(start, end));
+            // 範囲直後の "[20-77]" 等は別表記のため読み飛ばす
+            while chars.get(i) == Some(&' ') {
+                i += 1;
+            }
+            if chars.get(i) == Some(&'[') {
+                skip_group(&chars, &mut i, ']');
+            }
+            continue;
+        }
+        match c {
+            '[' | ']' => {}
+            _ => label.push(c),
+        }
+        i += 1;
+    }
+    flush(&mut label, &mut items);
+    items
+}
+
+/// 構成要素列から範囲と種別を対応付ける関数
+/// 範囲が先頭の節は「範囲 → 後続の種別」、種別が先頭の節は「種別 → 後続の範囲」として対応付ける。
+fn pair_items(items: &[Item]) -> Vec<(String, Vec<RawRange>)> {
+    let mut pairs: Vec<(String, Vec<RawRange>)> = Vec::new();
+    let range_first = matches!(items.first(), Some(Item::Range(..)));
+    let mut pending = Vec::new();
+    for item in items {
+        match item {
+            Item::Range(start, end) => {
+                if range_first {
+                    pending.push((*start, *end));
+                } else if let Some((_, ranges)) = pairs.last_mut() {
+                    ranges.push((*start, *end));
+                }
+            }
+            Item::Label(label) => {
+                pairs.push((label.clone(), std::mem::take(&mut pending)));
+            }
+        }
+    }
+    // 種別の後ろに残った範囲は直前の種別へ
+    if let Some((_, ranges)) = pairs.last_mut() {
+        ranges.append(&mut pending);
+    }
+    pairs
+}
+
+/// 無敵表記解析関数
+///
+/// # 概要
+/// "," 区切りの各節から範囲と種別を取り出す。改行は同一種別の範囲の続きとして扱う。
+/// 種別の無い範囲、範囲の無い種別（"Low Profile" のみ等）は対象外とする。
+///
+/// # 引数
+/// * `text` - 無敵表記（例："1-3 Below Crouch, 4-15 Low Profile"）
+///
+/// # 戻り値
+/// 表記順の無敵範囲一覧
+pub fn parse_invuln(text: &str) -> Vec<InvulnRange> {
+    let mut ranges = Vec::new();
+    for clause in text.split(',') {
+        let clause = clause.replace('\n', " ");
+        for (label, frames) in pair_items(&clause_items(&clause)) {
+            for kind in classify(&label) {
+                for &(start, end) in &frames {
+                    if start == 0 || end.is_some_and(|end| end < start) {
+                        continue;
+                    }
+                    ranges.push(InvulnRange { kind, start, end });
+                }
+            }
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(kind: InvulnKind, start: u32, end: Option<u32>) -> InvulnRange {
+        InvulnRange { kind, start, end }
+    }
+
+    #[test]
+    fn test_parse_basic_orders() {
+        assert_eq!(
+            parse_invuln("1-9 Strike"),
+            vec![range(InvulnKind::Strike, 1, Some(9))]
+        );
+        assert_eq!(
+            parse_invuln("Below Crouch 10-22"),
+            vec![range(InvulnKind::LowProfile, 10, Some(22))]
+        );
+        assert_eq!(
+            parse_invuln("1-9F Full"),
+            vec![range(InvulnKind::Full, 1, Some(9))]
+        );
+        assert!(parse_invuln("-").is_empty());
+        assert!(parse_invuln("Low Profile").is_empty());
+    }
+
+    #[test]
+    fn test_parse_multiple_clauses() {
+        assert_eq!(
+            parse_invuln("1-11 Full, 12-40 Airborne"),
+            vec![
+                range(InvulnKind::Full, 1, Some(11)),
+                range(InvulnKind::Airborne, 12, Some(40)),
+            ]
+        );
+        assert_eq!(
+            parse_invuln("1-2 Strike/Projectile 3-19 Parry"),
+            vec![
+                range(InvulnKind::Strike, 1, Some(2)),
+                range(InvulnKind::Projectile, 1, Some(2)),
+                range(InvulnKind::GuardPoint, 3, Some(19)),
+            ]
+        );
+        // 改行は同一種別の範囲の続き
+        assert_eq!(
+            parse_invuln("3-6\n19-48 Below Crouch, 7-18 Low Profile"),
+            vec![
+                range(InvulnKind::LowProfile, 3, Some(6)),
+                range(InvulnKind::LowProfile, 19, Some(48)),
+                range(InvulnKind::LowProfile, 7, Some(18)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_open_and_variant_ends() {
+        assert_eq!(
+            parse_invuln("[10-25 Guard Point]"),
+            vec![range(InvulnKind::GuardPoint, 10, Some(25))]
+        );
+        assert_eq!(
+            parse_invuln("16-73 [20-77] Guardpoint"),
+            vec![range(InvulnKind::GuardPoint, 16, Some(73))]
+        );
+        assert_eq!(
+            parse_invuln("10-25~41 Guard Point"),
+            vec![range(InvulnKind::GuardPoint, 10, Some(41))]
+        );
+        assert_eq!(
+            parse_invuln("18F onwards Airborne"),
+            vec![range(InvulnKind::Airborne, 18, None)]
+        );
+        assert_eq!(
+            parse_invuln("Airborne 10-(Recovery 17)"),
+            vec![range(InvulnKind::Airborne, 10, None)]
+        );
+        assert_eq!(
+            parse_invuln("Guard Point: 3-Until Released"),
+            vec![range(InvulnKind::GuardPoint, 3, None)]
+        );
+        assert_eq!(
+            parse_invuln("Pass-through 14-Until Landing"),
+            vec![range(InvulnKind::Other, 14, None)]
+        );
+    }
+
+    #[test]
+    fn test_parse_tilde_and_relative_frames() {
+        // 同梱データの表記
+        assert_eq!(
+            parse_invuln("3~6 Full"),
+            vec![range(InvulnKind::Full, 3, Some(6))]
+        );
+        assert_eq!(
+            parse_invuln("1 Hit Armor 12~39F"),
+            vec![range(InvulnKind::Armor, 12, Some(39))]
+        );
+        assert_eq!(
+            parse_invuln("Projectile Reflection 4~35F, Upon Reflection+18 Full"),
+            vec![range(InvulnKind::Projectile, 4, Some(35))]
+        );
+        // 括弧内の不定の終端は最大値
+        assert_eq!(
+            parse_invuln("Projectile Reflection 4-[18~35F], Upon Reflection+18 Full"),
+            vec![range(InvulnKind::Projectile, 4, Some(35))]
+        );
+        assert_eq!(
+            parse_invuln("11-12 Below Crouch , 13-[20~77] Full"),
+            vec![
+                range(InvulnKind::LowProfile, 11, Some(12)),
+                range(InvulnKind::Full, 13, Some(77)),
+            ]
+        );
+        assert_eq!(
+            parse_invuln("Pass-through 9-[20~33]"),
+            vec![range(InvulnKind::Other, 9, Some(33))]
+        );
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let r = range(InvulnKind::Strike, 3, Some(5));
+        assert!(!r.contains(2) && r.contains(3) && r.contains(5) && !r.contains(6));
+        assert!(range(InvulnKind::Airborne, 3, None).contains(100));
+    }
+
+    #[test]
+    fn test_parse_invuln_shipped_data() {
+        // 同梱データで数字を含む無敵表記は少なくとも 1 範囲を得られること
+        for character in crate::CHARS {
+            let path = format!("data/{character}/{character}.json");
+            let moves: Vec<crate::MoveInfo> = crate::utils::read_json_file(&path).unwrap();
+            for move_info in &moves {
+                let text = &move_info.invincibility;
+                if text.chars().any(|c| c.is_ascii_digit()) {
+                    assert!(
+                        !parse_invuln(text).is_empty(),
+                        "{character} {}: {text:?}",
+                        move_info.input
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! `lib.rs`
+//!
+//! このファイルは、Discord Bot（`baiken`）とコマンドラインツール（`baiken-cli`）で共有するモジュール群・型定義・定数を提供する。
+//! キャラクター・技の検索、データモデル、ユーティリティ、技情報の表示項目などを各バイナリから利用できるよう公開する。
+
+// モジュール読み込み
+pub mod aliases; // 技エイリアス追加
+#[cfg(feature = "api")]
+pub mod api; // HTTP/JSON API
+pub mod async_utils; // 非同期処理ユーティリティ
+pub mod calc; // フレームデータ計算機能
+pub mod check; // 初期チェック機能
+pub mod commands; // コマンド群実装
+pub mod common; // 共通処理群
+#[cfg(feature = "sqlite")]
+pub mod database; // SQLite データベース
+pub mod error; // エラー処理
+pub mod feedback_store; // フィードバック保存
+pub mod find; // 情報検索機能
+pub mod guild; // サーバー毎の設定
+pub mod image_cache; // 画像キャッシュ機能
+pub mod invuln; // 無敵表記解析
+pub mod models; // データモデル
+pub mod move_export; // 技データ出力
+pub mod nickname_store; // キャラクター愛称管理
+pub mod render; // 画像生成機能
+#[cfg(any(test, feature = "test-utils"))]
+#[doc(hidden)]
+pub mod test_utils; // テスト用ユーティリティ（test-utils フィーチャーで公開）
+pub mod timeline; // フレーム表記解析
+pub mod utils; // 共通ユーティリティ関数
+
+// Re-export important modules and types
+pub use error::{AppError, Result};
+pub use find::Nicknames;
+pub use models::{CharInfo, GuardType, MoveAliases, MoveInfo};
+
+/// コンテキスト型定義
+///
+/// # 型説明
+/// * `Context<'a>` - poise コマンド実行時に渡されるコンテキスト型
+pub type Context<'a> = poise::Context<'a, Data, AppError>;
+
+/// 各コマンドに共通して渡されるユーザーデータ
+#[derive(Debug, Clone)]
+pub struct Data {
+    /// データディレクトリのパス
+    pub data_dir: String,
+    /// データ欠損時の自動修復（Dustloop からの再取得）有効フラグ
+    pub auto_repair: bool,
+}
+
+/// 画像リンク構造体
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct ImageLinks {
+    #[serde(deserialize_with = "crate::models::deserialize_string_or_int")]
+    pub input: String, // 入力コマンド
+    #[serde(deserialize_with = "crate::models::deserialize_string_or_int")]
+    pub move_img: String, // 技画像リンク
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hitbox_img: Vec<String>, // ヒットボックス画像リンク群
+}
+
+/// 埋め込みメッセージのカラーコード
+pub const EMBED_COLOR: u32 = 0x00FF_FF99;
+
+/// キャラクター名定数配列
+pub const CHARS: [&str; 30] = [
+    "A.B.A",
+    "Anji_Mito",
+    "Asuka_R",
+    "Axl_Low",
+    "Baiken",
+    "Bedman",
+    "Bridget",
+    "Chipp_Zanuff",
+    "Elphelt_Valentine",
+    "Faust",
+    "Giovanna",
+    "Goldlewis_Dickinson",
+    "Happy_Chaos",
+    "I-No",
+    "Jack-O",
+    "Johnny",
+    "Ky_Kiske",
+    "Leo_Whitefang",
+    "May",
+    "Millia_Rage",
+    "Nagoriyuki",
+    "Potemkin",
+    "Queen_Dizzy",
+    "Ramlethal_Valentine",
+    "Sin_Kiske",
+    "Slayer",
+    "Sol_Badguy",
+    "Testament",
+    "Venom",
+    "Zato-1",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imagelinks_serialization() {
+        let image_links = ImageLinks {
+            input: "5P".to_string(),
+            move_img: "http://example.com/5p.png".to_string(),
+            hitbox_img: vec![
+                "http://example.com/5p_hitbox1.png".to_string(),
+                "http://example.com/5p_hitbox2.png".to_string(),
+            ],
+        };
+
+        let serialized = serde_json::to_string(&image_links).unwrap();
+        let deserialized: ImageLinks = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(image_links.input, deserialized.input);
+        assert_eq!(image_links.move_img, deserialized.move_img);
+        assert_eq!(image_links.hitbox_img, deserialized.hitbox_img);
+    }
+
+    #[test]
+    fn test_chars_constant() {
+        // これは単純に配列の長さが正しいことを確認するテスト
+        assert_eq!(CHARS.len(), 30);
+        // キャラクター名が正しく含まれていることを確認
+        assert!(CHARS.contains(&"Sol_Badguy"));
+        assert!(CHARS.contains(&"Ky_Kiske"));
+        assert!(CHARS.contains(&"May"));
+    }
+
+    #[test]
+    fn test_embed_color() {
+        // カラーコードが正しいことを確認
+        assert_eq!(EMBED_COLOR, 0x00FF_FF99);
+    }
+}
@@ -2,96 +2,25 @@
 //!
 //! このファイルは、Discord Bot の起動基盤および各種機能モジュールの読み込み・初期設定を行う。
 //! poise フレームワークを利用し、コマンドの登録、エラーハンドリング、定期更新タスクなどを構成する。
+//! 共有モジュール群はライブラリクレート（`lib.rs`）から読み込む。
 
-// モジュール読み込み
-mod async_utils; // 非同期処理ユーティリティ
-mod check; // 初期チェック機能
-mod commands; // コマンド群実装
-mod common; // 共通処理群
-mod error; // エラー処理
-mod find; // 情報検索機能
-mod models; // データモデル
-mod test_utils; // テスト用ユーティリティ
-mod utils; // 共通ユーティリティ関数
-
-// Re-export important modules and types
-pub use error::{AppError, Result};
-pub use find::Nicknames;
-pub use models::{CharInfo, GuardType, MoveAliases, MoveInfo};
+// ライブラリクレート読み込み
+#[cfg(feature = "api")]
+use baiken::api;
+#[cfg(feature = "sqlite")]
+use baiken::commands::sql;
+#[cfg(feature = "sqlite")]
+use baiken::database;
+use baiken::{check, AppError, Data, Result};
 
 // 外部クレート読み込み
+use baiken::commands::{
+    alias, combo, config, doctor, export, feedback, frames, gap, gauge, help, hitboxes, imagecache,
+    moves, nicknames, oki, register, update,
+};
 use colored::Colorize; // 文字色変換用
-use commands::{feedback, frames, help, hitboxes, moves, nicknames, register, update};
-#[allow(unused_imports)]
 use poise::serenity_prelude as serenity; // Serenity 用エイリアス
 
-/// コンテキスト型定義
-///
-/// # 型説明
-/// * `Context<'a>` - poise コマンド実行時に渡されるコンテキスト型
-pub type Context<'a> = poise::Context<'a, Data, AppError>;
-
-/// 各コマンドに共通して渡されるユーザーデータ
-#[derive(Debug, Clone)]
-pub struct Data {
-    /// データディレクトリのパス
-    pub data_dir: String,
-}
-
-/// 画像リンク構造体
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-pub struct ImageLinks {
-    #[serde(deserialize_with = "crate::models::deserialize_string_or_int")]
-    pub input: String, // 入力コマンド
-    #[serde(deserialize_with = "crate::models::deserialize_string_or_int")]
-    pub move_img: String, // 技画像リンク
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub hitbox_img: Vec<String>, // ヒットボックス画像リンク群
-}
-
-/// サーバーID情報構造体
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-struct Gids {
-    id: Vec<String>, // 識別子群
-}
-
-/// 埋め込みメッセージのカラーコード
-pub const EMBED_COLOR: u32 = 0x00FF_FF99;
-
-/// キャラクター名定数配列
-pub const CHARS: [&str; 30] = [
-    "A.B.A",
-    "Anji_Mito",
-    "Asuka_R",
-    "Axl_Low",
-    "Baiken",
-    "Bedman",
-    "Bridget",
-    "Chipp_Zanuff",
-    "Elphelt_Valentine",
-    "Faust",
-    "Giovanna",
-    "Goldlewis_Dickinson",
-    "Happy_Chaos",
-    "I-No",
-    "Jack-O",
-    "Johnny",
-    "Ky_Kiske",
-    "Leo_Whitefang",
-    "May",
-    "Millia_Rage",
-    "Nagoriyuki",
-    "Potemkin",
-    "Queen_Dizzy",
-    "Ramlethal_Valentine",
-    "Sin_Kiske",
-    "Slayer",
-    "Sol_Badguy",
-    "Testament",
-    "Venom",
-    "Zato-1",
-];
-
 /// エラーハンドリング関数
 ///
 /// コマンド実行中に発生したエラーを処理し、適切なメッセージを表示する。
@@ -175,9 +104,15 @@ async fn main() -> Result<()> {
         "data".to_string()
     });
 
+    // 自動修復設定（AUTO_REPAIR=true/1 で有効）
+    let auto_repair = std::env::var("AUTO_REPAIR")
+        .map(|v| matches!(v.to_lowercase().as_str(), "true" | "1"))
+        .unwrap_or(false);
+
     // ユーザーデータを初期化
     let user_data = Data {
         data_dir: data_dir.clone(),
+        auto_repair,
     };
 
     // 初期化時の確認
@@ -191,19 +126,79 @@ async fn main() -> Result<()> {
         )));
     }
 
+    // 起動時データ診断レポート（問題があっても起動は継続）
+    let report = check::run_checks(&data_dir, check::CheckOptions::ALL);
+    if report.is_ok() {
+        println!("{}", "データ診断: 問題は検出されませんでした".green());
+    } else {
+        println!(
+            "{}",
+            format!(
+                "データ診断: エラー {} 件 / 警告 {} 件（詳細は /doctor で確認）",
+                report.count(check::Severity::Error),
+                report.count(check::Severity::Warning)
+            )
+            .yellow()
+        );
+        for (name, errors, warnings) in report.counts_by_character() {
+            println!("  {name}: errors {errors}, warnings {warnings}");
+        }
+    }
+
+    // データベース読み込み設定（DATA_BACKEND=sqlite で JSON から再構築して技データの読み込み元にする）
+    #[cfg(feature = "sqlite")]
+    if std::env::var("DATA_BACKEND").is_ok_and(|v| v.eq_ignore_ascii_case("sqlite")) {
+        let summary = database::rebuild(&data_dir, "all", "startup")?;
+        database::set_backing_store(true);
+        println!(
+            "{}",
+            format!(
+                "データベースを構築しました: キャラクター {} 件 / 技 {} 件",
+                summary.characters, summary.moves
+            )
+            .green()
+        );
+    }
+
+    // HTTP/JSON API 起動（API_ADDR=127.0.0.1:8080 等を設定した場合のみ）
+    #[cfg(feature = "api")]
+    if let Ok(addr) = std::env::var("API_ADDR") {
+        api::spawn(&addr, data_dir.clone())?;
+        println!(
+            "{}",
+            format!("HTTP API を起動しました: http://{addr}").green()
+        );
+    }
+
+    // 登録コマンド一覧
+    #[allow(unused_mut)]
+    let mut commands = vec![
+        help::help(),
+        frames::frames(),
+        gap::gap(),
+        combo::combo(),
+        gauge::gauge(),
+        oki::oki(),
+        config::config(),
+        hitboxes::hitboxes(),
+        moves::moves(),
+        export::export(),
+        nicknames::nicknames(),
+        nicknames::nickname(),
+        alias::alias(),
+        feedback::feedback(),
+        update::update(),
+        doctor::doctor(),
+        imagecache::imagecache(),
+        register::register(),
+    ];
+    #[cfg(feature = "sqlite")]
+    commands.push(sql::sql());
+
     // フレームワークの設定
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![
-                help::help(),
-                frames::frames(),
-                hitboxes::hitboxes(),
-                moves::moves(),
-                nicknames::nicknames(),
-                feedback::feedback(),
-                update::update(),
-                register::register(),
-            ],
+            commands,
             prefix_options: poise::PrefixFrameworkOptions {
                 prefix: Some("!".into()),
                 additional_prefixes: vec![poise::Prefix::Literal("！")],
@@ -222,13 +217,29 @@ async fn main() -> Result<()> {
                 })
             },
             // イベントハンドラ
-            event_handler: |_ctx, event, _framework, _data| {
+            event_handler: |ctx, event, framework, data| {
                 Box::pin(async move {
-                    if let serenity::FullEvent::Ready { data_about_bot, .. } = event {
-                        println!(
-                            "{}",
-                            format!("ログイン: {name}", name = data_about_bot.user.name).green()
-                        );
+                    match event {
+                        serenity::FullEvent::Ready { data_about_bot, .. } => {
+                            println!(
+                                "{}",
+                                format!("ログイン: {name}", name = data_about_bot.user.name)
+                                    .green()
+                            );
+                        }
+                        // エイリアス提案の審査ボタン
+                        serenity::FullEvent::InteractionCreate { interaction } => {
+                            if let Some(component) = interaction.as_message_component() {
+                                alias::handle_review(
+                                    ctx,
+                                    &framework.options().owners,
+                                    &data.data_dir,
+                                    component,
+                                )
+                                .await?;
+                            }
+                        }
+                        _ => {}
                     }
                     Ok(())
                 })
@@ -258,44 +269,3 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json;
-
-    #[test]
-    fn test_imagelinks_serialization() {
-        let image_links = ImageLinks {
-            input: "5P".to_string(),
-            move_img: "http://example.com/5p.png".to_string(),
-            hitbox_img: vec![
-                "http://example.com/5p_hitbox1.png".to_string(),
-                "http://example.com/5p_hitbox2.png".to_string(),
-            ],
-        };
-
-        let serialized = serde_json::to_string(&image_links).unwrap();
-        let deserialized: ImageLinks = serde_json::from_str(&serialized).unwrap();
-
-        assert_eq!(image_links.input, deserialized.input);
-        assert_eq!(image_links.move_img, deserialized.move_img);
-        assert_eq!(image_links.hitbox_img, deserialized.hitbox_img);
-    }
-
-    #[test]
-    fn test_chars_constant() {
-        // これは単純に配列の長さが正しいことを確認するテスト
-        assert_eq!(CHARS.len(), 29);
-        // キャラクター名が正しく含まれていることを確認
-        assert!(CHARS.contains(&"Sol_Badguy"));
-        assert!(CHARS.contains(&"Ky_Kiske"));
-        assert!(CHARS.contains(&"May"));
-    }
-
-    #[test]
-    fn test_embed_color() {
-        // カラーコードが正しいことを確認
-        assert_eq!(EMBED_COLOR, 0x00FF_FF99);
-    }
-}
@@ -221,7 +221,7 @@ pub struct MoveInfo {
 }
 
 /// 技のエイリアス情報
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct MoveAliases {
     /// 入力コマンド
     pub input: String,
@@ -364,7 +364,6 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json;
     use std::str::FromStr;
 
     #[test]
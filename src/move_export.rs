@@ -0,0 +1,329 @@
+//! `move_export.rs`
+//!
+//! キャラクターの技データを CSV・Markdown 表・JSON に変換する機能を提供する。
+//! 各技にエイリアス・技画像・ヒットボックス画像の URL を付与した正規化データを出力する。
+
+use crate::error::Result;
+use crate::{find, ImageLinks, MoveAliases, MoveInfo};
+use serde::Serialize;
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum ExportFormat {
+    /// CSV（表計算ソフト向け）
+    #[name = "csv"]
+    Csv,
+    /// Markdown の表
+    #[name = "markdown"]
+    Markdown,
+    /// JSON
+    #[name = "json"]
+    Json,
+}
+
+impl ExportFormat {
+    /// ファイル拡張子を返す。
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// 出力用の技データ（技情報＋エイリアス・画像 URL）
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportMove {
+    /// 技情報
+    #[serde(flatten)]
+    pub move_info: MoveInfo,
+    /// エイリアス一覧
+    pub aliases: Vec<String>,
+    /// 技画像 URL
+    pub image: Option<String>,
+    /// ヒットボックス画像 URL 一覧
+    pub hitboxes: Vec<String>,
+}
+
+/// 出力用データ生成関数
+///
+/// # 概要
+/// `aliases.json` の入力は "2D" 等の短い表記のため、技検索（[`find::match_move_index`]）と同じ規則で各エントリの技を特定する。
+///
+/// # 引数
+/// * `moves_info` - 技情報（キャラクターの全技）
+/// * `aliases_data` - エイリアス情報
+/// * `image_links` - 画像リンク情報
+///
+/// # 戻り値
+/// 技情報の順序のままの出力用データ
+pub fn build_export(
+    moves_info: &[MoveInfo],
+    aliases_data: &[MoveAliases],
+    image_links: &[ImageLinks],
+) -> Vec<ExportMove> {
+    let alias_targets: Vec<Option<usize>> = aliases_data
+        .iter()
+        .map(|a| find::match_move_index(&a.input, moves_info))
+        .collect();
+    moves_info
+        .iter()
+        .enumerate()
+        .map(|(index, move_info)| {
+            let aliases = aliases_data
+                .iter()
+                .zip(&alias_targets)
+                .filter(|(_, target)| **target == Some(index))
+                .flat_map(|(a, _)| a.aliases.iter().cloned())
+                .collect();
+            let links = find::find_image_index(&move_info.input, image_links)
+                .map(|index| &image_links[index]);
+            ExportMove {
+                move_info: move_info.clone(),
+                aliases,
+                image: links
+                    .map(|l| l.move_img.clone())
+                    .filter(|url| !url.trim().is_empty()),
+                hitboxes: links.map(|l| l.hitbox_img.clone()).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// 表形式の技情報列（JSON 出力と同じ項目名・順序、表示用の算出値は含めない）
+const MOVE_COLUMNS: [&str; 22] = [
+    "input",
+    "name",
+    "damage",
+    "guard",
+    "startup",
+    "active",
+    "recovery",
+    "on_hit",
+    "on_block",
+    "level",
+    "counter",
+    "move_type",
+    "risc_gain",
+    "risc_loss",
+    "wall_damage",
+    "input_tension",
+    "chip_ratio",
+    "scaling",
+    "invincibility",
+    "cancel",
+    "caption",
+    "notes",
+];
+
+/// 表形式の列名一覧
+fn table_header() -> Vec<&'static str> {
+    let mut header = MOVE_COLUMNS.to_vec();
+    header.extend(["aliases", "image", "hitboxes"]);
+    header
+}
+
+/// 値が無い項目を空文字とする文字列変換関数
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// 表形式の 1 行分の値一覧
+fn table_row(row: &ExportMove) -> Vec<String> {
+    let m = &row.move_info;
+    let mut values = vec![
+        m.input.clone(),
+        m.name.clone(),
+        optional(m.damage),
+        m.guard.clone(),
+        optional(m.startup),
+        m.active.clone(),
+        optional(m.recovery),
+        m.on_hit.clone(),
+        m.on_block.clone(),
+        m.level.clone(),
+        m.counter.clone(),
+        m.move_type.clone(),
+        optional(m.risc_gain),
+        optional(m.risc_loss),
+        optional(m.wall_damage),
+        optional(m.input_tension),
+        optional(m.chip_ratio),
+        optional(m.scaling),
+        m.invincibility.clone(),
+        m.cancel.clone(),
+        m.caption.clone(),
+        m.notes.clone(),
+    ];
+    values.push(row.aliases.join(", "));
+    values.push(row.image.clone().unwrap_or_default());
+    values.push(row.hitboxes.join(" "));
+    values
+}
+
+/// CSV の値エスケープ関数（"," `"` 改行を含む場合は引用符で囲む）
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Markdown 表のセルエスケープ関数
+fn markdown_escape(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// CSV 変換関数
+pub fn to_csv(rows: &[ExportMove]) -> String {
+    let mut csv = table_header().join(",") + "\n";
+    for row in rows {
+        let values: Vec<String> = table_row(row).iter().map(|v| csv_escape(v)).collect();
+        csv += &(values.join(",") + "\n");
+    }
+    csv
+}
+
+/// Markdown 表変換関数
+pub fn to_markdown(character: &str, rows: &[ExportMove]) -> String {
+    let header = table_header();
+    let mut markdown = format!("# {}\n\n", character.replace('_', " "));
+    markdown += &format!("| {} |\n", header.join(" | "));
+    markdown += &format!("|{}\n", " --- |".repeat(header.len()));
+    for row in rows {
+        let values: Vec<String> = table_row(row).iter().map(|v| markdown_escape(v)).collect();
+        markdown += &format!("| {} |\n", values.join(" | "));
+    }
+    markdown
+}
+
+/// JSON 変換関数（`utils::write_json_file` と同じ整形）
+pub fn to_json(rows: &[ExportMove]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(rows)?)
+}
+
+/// 指定形式への変換関数
+pub fn render(format: ExportFormat, character: &str, rows: &[ExportMove]) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(rows)),
+        ExportFormat::Markdown => Ok(to_markdown(character, rows)),
+        ExportFormat::Json => to_json(rows),
+    }
+}
+
+/// 出力ファイル名生成関数（例："Sol_Badguy.csv"）
+pub fn file_name(character: &str, format: ExportFormat) -> String {
+    format!("{character}.{}", format.extension())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_move_aliases, create_test_move_info};
+
+    fn test_rows() -> Vec<ExportMove> {
+        let images = vec![ImageLinks {
+            input: "5P".to_string(),
+            move_img: "https://example.com/5p.png".to_string(),
+            hitbox_img: vec!["https://example.com/5p_hb.png".to_string()],
+        }];
+        build_export(
+            &create_test_move_info(),
+            &create_test_move_aliases(),
+            &images,
+        )
+    }
+
+    #[test]
+    fn test_build_export() {
+        let rows = test_rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].aliases, vec!["Punch", "P"]);
+        assert_eq!(rows[0].image.as_deref(), Some("https://example.com/5p.png"));
+        assert_eq!(rows[0].hitboxes.len(), 1);
+        assert_eq!(rows[1].image, None);
+        assert!(rows[1].hitboxes.is_empty());
+    }
+
+    #[test]
+    fn test_build_export_short_alias_keys() {
+        // 実データと同じく技データは "足払い(2D)"、aliases.json は "2D" の表記
+        let moves: Vec<MoveInfo> = ["足払い(2D)", "近S(近S)"]
+            .iter()
+            .map(|input| {
+                let mut info = create_test_move_info().remove(0);
+                info.input = input.to_string();
+                info.name = input.split('(').next().unwrap().to_string();
+                info
+            })
+            .collect();
+        let aliases: Vec<MoveAliases> = serde_json::from_str(
+            r#"[
+                {"input": "2D", "aliases": ["足払い", "sweep"]},
+                {"input": "近S", "aliases": ["c.S"]}
+            ]"#,
+        )
+        .unwrap();
+        let rows = build_export(&moves, &aliases, &[]);
+        assert_eq!(rows[0].aliases, vec!["足払い", "sweep"]);
+        assert_eq!(rows[1].aliases, vec!["c.S"]);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = to_csv(&test_rows());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("input,name,damage,guard,startup,"));
+        assert!(lines[0].contains(",caption,notes,"));
+        assert!(!lines[0].contains("hitstop"));
+        assert!(lines[0].ends_with(",aliases,image,hitboxes"));
+        // エイリアスは "," を含むため引用符で囲む
+        assert!(lines[1].starts_with("5P,Punch,"));
+        assert!(lines[1].contains(",\"Punch, P\","));
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = to_markdown("Sol_Badguy", &test_rows());
+        assert!(markdown.starts_with("# Sol Badguy\n\n| input | name |"));
+        let header_cols = markdown.lines().nth(2).unwrap().matches('|').count();
+        let row_cols = markdown.lines().nth(4).unwrap().matches('|').count();
+        assert_eq!(header_cols, row_cols);
+        assert_eq!(markdown_escape("a|b\nc"), "a\\|b<br>c");
+    }
+
+    #[test]
+    fn test_table_columns_match_json() {
+        let rows = test_rows();
+        let serde_json::Value::Object(fields) = serde_json::to_value(&rows[0].move_info).unwrap()
+        else {
+            panic!("MoveInfo must serialize to an object");
+        };
+        let mut json_keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+        let mut columns = MOVE_COLUMNS.to_vec();
+        json_keys.sort_unstable();
+        columns.sort_unstable();
+        assert_eq!(columns, json_keys);
+        assert_eq!(table_row(&rows[0]).len(), table_header().len());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&test_rows()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["input"], "5P");
+        assert_eq!(value[0]["aliases"][1], "P");
+        assert_eq!(value[1]["image"], serde_json::Value::Null);
+        assert_eq!(
+            file_name("Sol_Badguy", ExportFormat::Json),
+            "Sol_Badguy.json"
+        );
+    }
+}
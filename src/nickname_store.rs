@@ -0,0 +1,229 @@
+//! `nickname_store.rs`
+//!
+//! キャラクター愛称（ニックネーム）の追加・削除と重複判定、`nicknames.json` への保存を提供する。
+//! 検索処理は呼び出し毎に `nicknames.json` を読み込むため、保存後すぐに反映される。
+
+use crate::error::Result;
+use crate::{utils, Nicknames};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 愛称ファイル名（データディレクトリ直下）
+pub const NICKNAMES_FILE: &str = "nicknames.json";
+
+/// 愛称最大文字数
+pub const MAX_NICKNAME_LEN: usize = 32;
+
+/// 検索処理で特別扱いされる予約語
+const RESERVED: [&str; 1] = ["all"];
+
+/// 読み込み〜保存間の排他用ロック
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 比較用正規化関数（大文字小文字・空白・"_"・"-" を無視）
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect()
+}
+
+/// 愛称ファイルパス生成関数
+pub fn nicknames_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir).join(NICKNAMES_FILE)
+}
+
+/// 愛称一覧読み込み関数
+pub fn load(data_dir: &str) -> Result<Vec<Nicknames>> {
+    utils::read_json_file(nicknames_path(data_dir))
+}
+
+/// 愛称一覧保存関数（既存ファイルに合わせて 4 スペースインデント）
+pub fn save(data_dir: &str, nicknames: &[Nicknames]) -> Result<()> {
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    nicknames.serialize(&mut serializer)?;
+    fs::write(nicknames_path(data_dir), buf)?;
+    Ok(())
+}
+
+/// 愛称一覧の読み込み・変更・保存を排他的に行う関数
+/// 変更処理がエラーを返した場合は保存しない。
+pub fn update<T>(data_dir: &str, f: impl FnOnce(&mut Vec<Nicknames>) -> Result<T>) -> Result<T> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut nicknames = load(data_dir)?;
+    let value = f(&mut nicknames)?;
+    save(data_dir, &nicknames)?;
+    Ok(value)
+}
+
+/// 愛称形式検証関数
+pub fn validate_nickname(nickname: &str) -> std::result::Result<(), String> {
+    let nickname = nickname.trim();
+    if nickname.is_empty() {
+        return Err("Nickname cannot be empty.".to_string());
+    }
+    if nickname.chars().count() > MAX_NICKNAME_LEN {
+        return Err(format!(
+            "Nickname must be at most {MAX_NICKNAME_LEN} characters."
+        ));
+    }
+    if RESERVED.iter().any(|r| normalize(r) == normalize(nickname)) {
+        return Err(format!("`{nickname}` is reserved."));
+    }
+    Ok(())
+}
+
+/// 愛称重複判定関数
+///
+/// # 概要
+/// 全キャラクターの愛称・正式名と（大文字小文字・空白・"_"・"-" を無視して）一致する場合に、その内容を返す。
+///
+/// # 戻り値
+/// 重複時は重複内容の説明、重複なしは None
+pub fn nickname_conflict(nickname: &str, nicknames: &[Nicknames]) -> Option<String> {
+    let key = normalize(nickname);
+    nicknames.iter().find_map(|entry| {
+        if normalize(&entry.character) == key {
+            return Some(format!("`{nickname}` is the name of `{}`", entry.character));
+        }
+        entry
+            .nicknames
+            .iter()
+            .find(|n| normalize(n) == key)
+            .map(|existing| {
+                format!(
+                    "`{existing}` is already a nickname of `{}`",
+                    entry.character
+                )
+            })
+    })
+}
+
+/// 愛称追加関数
+///
+/// # 戻り値
+/// 追加時は Ok、キャラクター未登録・重複時は理由を返す
+pub fn add_nickname(
+    nicknames: &mut [Nicknames],
+    character: &str,
+    nickname: &str,
+) -> std::result::Result<(), String> {
+    validate_nickname(nickname)?;
+    if let Some(conflict) = nickname_conflict(nickname, nicknames) {
+        return Err(conflict + ".");
+    }
+    let entry = nicknames
+        .iter_mut()
+        .find(|entry| entry.character == character)
+        .ok_or_else(|| format!("Character `{character}` has no nickname entry."))?;
+    entry.nicknames.push(nickname.trim().to_string());
+    Ok(())
+}
+
+/// 愛称削除関数（大文字小文字を無視して一致するものを削除）
+///
+/// # 戻り値
+/// 削除した愛称、見つからない場合は理由を返す
+pub fn remove_nickname(
+    nicknames: &mut [Nicknames],
+    character: &str,
+    nickname: &str,
+) -> std::result::Result<String, String> {
+    let not_found = || format!("`{nickname}` is not a nickname of `{character}`.");
+    let entry = nicknames
+        .iter_mut()
+        .find(|entry| entry.character == character)
+        .ok_or_else(not_found)?;
+    let index = entry
+        .nicknames
+        .iter()
+        .position(|n| n.to_lowercase() == nickname.trim().to_lowercase())
+        .ok_or_else(not_found)?;
+    Ok(entry.nicknames.remove(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_nicknames() -> Vec<Nicknames> {
+        vec![
+            Nicknames {
+                character: "Sol_Badguy".to_string(),
+                nicknames: vec!["SO".to_string(), "ソル".to_string()],
+            },
+            Nicknames {
+                character: "Ky_Kiske".to_string(),
+                nicknames: vec!["KY".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_nickname_conflict() {
+        let nicknames = test_nicknames();
+        assert!(nickname_conflict("so", &nicknames).is_some());
+        assert!(nickname_conflict("ky kiske", &nicknames).is_some());
+        assert!(nickname_conflict("solbadguy", &nicknames).is_some());
+        assert!(nickname_conflict("カイ", &nicknames).is_none());
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let mut nicknames = test_nicknames();
+        assert!(add_nickname(&mut nicknames, "Ky_Kiske", "カイ").is_ok());
+        assert_eq!(nicknames[1].nicknames, vec!["KY", "カイ"]);
+
+        // 他キャラクターの愛称・予約語・未登録キャラクター
+        assert!(add_nickname(&mut nicknames, "Ky_Kiske", "so").is_err());
+        assert!(add_nickname(&mut nicknames, "Ky_Kiske", "ALL").is_err());
+        assert!(add_nickname(&mut nicknames, "May", "メイ").is_err());
+
+        assert_eq!(
+            remove_nickname(&mut nicknames, "Sol_Badguy", "so").unwrap(),
+            "SO"
+        );
+        assert!(remove_nickname(&mut nicknames, "Sol_Badguy", "so").is_err());
+        assert!(remove_nickname(&mut nicknames, "Ky_Kiske", "ソル").is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        save(data_dir, &test_nicknames()).unwrap();
+        let loaded = load(data_dir).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].nicknames, vec!["SO", "ソル"]);
+    }
+
+    #[test]
+    fn test_update_concurrent() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap().to_string();
+        save(&data_dir, &test_nicknames()).unwrap();
+
+        // 同時に追加しても互いの変更を失わない
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let data_dir = data_dir.clone();
+                std::thread::spawn(move || {
+                    update(&data_dir, |entries| {
+                        Ok(add_nickname(entries, "Ky_Kiske", &format!("k{i}")))
+                    })
+                    .unwrap()
+                    .unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(load(&data_dir).unwrap()[1].nicknames.len(), 1 + 8);
+    }
+}
@@ -0,0 +1,300 @@
+//! `hitbox.rs`
+//!
+//! 複数段技のヒットボックス画像を 1 枚に合成する機能を提供する。
+//! 全フレームを格子状に並べた PNG、または順に再生する GIF アニメーションを生成し、データディレクトリ内へ保存する。
+
+use super::{encode_png, BACKGROUND};
+use crate::error::{AppError, Result};
+use crate::image_cache::{self, CacheManifest, FetchResult};
+use crate::utils;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::{self, FilterType},
+    Delay, Frame, RgbaImage,
+};
+use md5::{Digest, Md5};
+use std::fs;
+use std::path::PathBuf;
+
+/// 合成画像保存ディレクトリ名（画像キャッシュディレクトリ直下）
+pub const COMPOSITE_DIR: &str = "composite";
+
+/// 格子合成時の 1 マスの最大辺長（ピクセル）
+const MAX_CELL_SIZE: u32 = 400;
+
+/// GIF 合成時の最大辺長（ピクセル）
+const MAX_GIF_SIZE: u32 = 480;
+
+/// GIF 1 フレームの表示時間（ミリ秒）
+pub const GIF_FRAME_DELAY_MS: u32 = 700;
+
+/// 格子の余白（ピクセル）
+const GRID_PADDING: u32 = 8;
+
+/// 合成形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum CompositeMode {
+    /// 格子状に並べた静止画
+    #[name = "grid"]
+    Grid,
+    /// 順に再生するアニメーション
+    #[name = "gif"]
+    Gif,
+}
+
+impl CompositeMode {
+    /// 出力ファイル拡張子を返す。
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Grid => "png",
+            Self::Gif => "gif",
+        }
+    }
+}
+
+/// 長辺が `max` 以下となるよう縮小する関数（拡大はしない）
+fn fit_within(frame: &RgbaImage, max: u32) -> RgbaImage {
+    let (width, height) = frame.dimensions();
+    if width <= max && height <= max {
+        return frame.clone();
+    }
+    let scale = f64::from(max) / f64::from(width.max(height));
+    let new_width = ((f64::from(width) * scale).round() as u32).max(1);
+    let new_height = ((f64::from(height) * scale).round() as u32).max(1);
+    imageops::resize(frame, new_width, new_height, FilterType::Triangle)
+}
+
+/// 格子の列数・行数計算関数（列数 = ⌈√n⌉）
+pub fn grid_dimensions(count: usize) -> (u32, u32) {
+    if count == 0 {
+        return (0, 0);
+    }
+    let columns = (count as f64).sqrt().ceil() as usize;
+    let rows = count.div_ceil(columns);
+    (columns as u32, rows as u32)
+}
+
+/// 格子合成関数
+///
+/// # 概要
+/// 各フレームを [`MAX_CELL_SIZE`] 以内に縮小し、左上から順に格子状へ配置する。
+/// マスの大きさは最大フレームに合わせ、小さいフレームはマス中央へ配置する。
+///
+/// # 引数
+/// * `frames` - ヒットボックス画像群（表示順）
+pub fn compose_grid(frames: &[RgbaImage]) -> RgbaImage {
+    let frames: Vec<RgbaImage> = frames
+        .iter()
+        .map(|f| fit_within(f, MAX_CELL_SIZE))
+        .collect();
+    let (columns, rows) = grid_dimensions(frames.len());
+    let cell_width = frames.iter().map(RgbaImage::width).max().unwrap_or(1);
+    let cell_height = frames.iter().map(RgbaImage::height).max().unwrap_or(1);
+
+    let width = columns * cell_width + (columns + 1) * GRID_PADDING;
+    let height = rows * cell_height + (rows + 1) * GRID_PADDING;
+    let mut canvas = RgbaImage::from_pixel(width.max(1), height.max(1), BACKGROUND);
+
+    for (i, frame) in frames.iter().enumerate() {
+        let column = i as u32 % columns;
+        let row = i as u32 / columns;
+        let x =
+            GRID_PADDING + column * (cell_width + GRID_PADDING) + (cell_width - frame.width()) / 2;
+        let y =
+            GRID_PADDING + row * (cell_height + GRID_PADDING) + (cell_height - frame.height()) / 2;
+        imageops::overlay(&mut canvas, frame, i64::from(x), i64::from(y));
+    }
+    canvas
+}
+
+/// GIF アニメーション合成関数
+///
+/// # 概要
+/// 全フレームを共通サイズの背景へ中央配置し、無限ループの GIF としてエンコードする。
+///
+/// # 引数
+/// * `frames` - ヒットボックス画像群（再生順）
+/// * `delay_ms` - 1 フレームの表示時間（ミリ秒）
+pub fn compose_gif(frames: &[RgbaImage], delay_ms: u32) -> Result<Vec<u8>> {
+    let frames: Vec<RgbaImage> = frames.iter().map(|f| fit_within(f, MAX_GIF_SIZE)).collect();
+    let width = frames.iter().map(RgbaImage::width).max().unwrap_or(1);
+    let height = frames.iter().map(RgbaImage::height).max().unwrap_or(1);
+
+    let gif_error =
+        |e: image::ImageError| AppError::DataProcessing(format!("GIF の生成に失敗しました: {e}"));
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut bytes, 10);
+        encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+        for frame in &frames {
+            let mut canvas = RgbaImage::from_pixel(width, height, BACKGROUND);
+            let x = (width - frame.width()) / 2;
+            let y = (height - frame.height()) / 2;
+            imageops::overlay(&mut canvas, frame, i64::from(x), i64::from(y));
+            encoder
+                .encode_frame(Frame::from_parts(
+                    canvas,
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(delay_ms, 1),
+                ))
+                .map_err(gif_error)?;
+        }
+    }
+    Ok(bytes)
+}
+
+/// 合成画像保存パス生成関数
+/// 画像 URL 群と合成形式のハッシュをファイル名とする。
+pub fn composite_path(
+    data_dir: &str,
+    character: &str,
+    urls: &[String],
+    mode: CompositeMode,
+) -> PathBuf {
+    let mut hasher = Md5::new();
+    for url in urls {
+        hasher.update(url.as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher.update(mode.extension().as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+
+    image_cache::cache_dir(data_dir)
+        .join(COMPOSITE_DIR)
+        .join(character)
+        .join(format!("{hash}.{}", mode.extension()))
+}
+
+/// ヒットボックス画像読み込み関数（ブロッキング）
+/// ローカル複製を優先し、無い場合は Dustloop から取得する。
+fn load_frame(data_dir: &str, manifest: &CacheManifest, url: &str) -> Result<RgbaImage> {
+    let bytes = match manifest.local_file(data_dir, url) {
+        Some(path) => fs::read(path)?,
+        None => match image_cache::fetch_remote(url) {
+            FetchResult::Ok(bytes) => bytes,
+            FetchResult::NotFound(code) => {
+                return Err(AppError::FileNotFound(format!("{url} (HTTP {code})")))
+            }
+            FetchResult::Failed(detail) => {
+                return Err(AppError::DataProcessing(format!("{url}: {detail}")))
+            }
+        },
+    };
+    image::load_from_memory(&bytes)
+        .map(|img| img.to_rgba8())
+        .map_err(|e| AppError::DataProcessing(format!("{url}: 画像の解析に失敗しました: {e}")))
+}
+
+/// 合成画像取得関数（ブロッキング）
+///
+/// # 概要
+/// 保存済みの合成画像があればそれを返し、無ければ全フレームを読み込んで合成・保存する。
+///
+/// # 引数
+/// * `data_dir` - データディレクトリ
+/// * `character` - 正式なキャラクター名
+/// * `urls` - ヒットボックス画像 URL 群（表示順）
+/// * `mode` - 合成形式
+///
+/// # 戻り値
+/// 合成画像ファイルのパス
+pub fn render_composite(
+    data_dir: &str,
+    character: &str,
+    urls: &[String],
+    mode: CompositeMode,
+) -> Result<PathBuf> {
+    let path = composite_path(data_dir, character, urls, mode);
+    if path.is_file() {
+        return Ok(path);
+    }
+    if urls.is_empty() {
+        return Err(AppError::DataProcessing(
+            "合成対象のヒットボックス画像がありません".to_string(),
+        ));
+    }
+
+    let manifest = CacheManifest::load(data_dir);
+    let frames = urls
+        .iter()
+        .map(|url| load_frame(data_dir, &manifest, url))
+        .collect::<Result<Vec<_>>>()?;
+
+    let bytes = match mode {
+        CompositeMode::Grid => encode_png(&compose_grid(&frames))?,
+        CompositeMode::Gif => compose_gif(&frames, GIF_FRAME_DELAY_MS)?,
+    };
+    utils::write_file_atomic(&path, &bytes)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn solid(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255]))
+    }
+
+    #[test]
+    fn test_grid_dimensions() {
+        assert_eq!(grid_dimensions(0), (0, 0));
+        assert_eq!(grid_dimensions(1), (1, 1));
+        assert_eq!(grid_dimensions(2), (2, 1));
+        assert_eq!(grid_dimensions(4), (2, 2));
+        assert_eq!(grid_dimensions(5), (3, 2));
+    }
+
+    #[test]
+    fn test_compose_grid_layout() {
+        let grid = compose_grid(&[solid(10, 20), solid(10, 20), solid(10, 10)]);
+        // 2 列 × 2 行、マス 10×20
+        assert_eq!(grid.width(), 2 * 10 + 3 * GRID_PADDING);
+        assert_eq!(grid.height(), 2 * 20 + 3 * GRID_PADDING);
+        assert_eq!(
+            grid.get_pixel(GRID_PADDING, GRID_PADDING).0,
+            [255, 0, 0, 255]
+        );
+        assert_eq!(*grid.get_pixel(0, 0), BACKGROUND);
+    }
+
+    #[test]
+    fn test_compose_grid_shrinks_large_frames() {
+        let grid = compose_grid(&[solid(800, 400)]);
+        assert_eq!(grid.width(), MAX_CELL_SIZE + 2 * GRID_PADDING);
+        assert_eq!(grid.height(), MAX_CELL_SIZE / 2 + 2 * GRID_PADDING);
+    }
+
+    #[test]
+    fn test_compose_gif_frames() {
+        let bytes = compose_gif(&[solid(10, 10), solid(20, 10)], 100).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (20, 10));
+    }
+
+    #[test]
+    fn test_render_composite_uses_saved_file() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        let urls = vec!["https://www.dustloop.com/wiki/images/a/ab/Test.png".to_string()];
+
+        // 保存済みファイルがあれば取得処理を行わずに返す
+        let path = composite_path(data_dir, "Sol_Badguy", &urls, CompositeMode::Grid);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"cached").unwrap();
+        assert_eq!(
+            render_composite(data_dir, "Sol_Badguy", &urls, CompositeMode::Grid).unwrap(),
+            path
+        );
+
+        // 形式が異なれば別ファイル
+        assert_ne!(
+            composite_path(data_dir, "Sol_Badguy", &urls, CompositeMode::Gif),
+            path
+        );
+        assert!(render_composite(data_dir, "Sol_Badguy", &[], CompositeMode::Gif).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! `meter.rs`
+//!
+//! ゲーム内トレーニングモード風のフレームメーター画像を描画する機能を提供する。
+//! 1 フレームを 1 マスとして発生・持続・硬直・無敵・空白区間を色分けし、長い技は複数行へ折り返す。
+
+use super::BACKGROUND;
+use crate::invuln::InvulnKind;
+use crate::timeline::FrameKind;
+use image::{Rgba, RgbaImage};
+
+/// 1 マスの幅（ピクセル）
+const CELL_WIDTH: u32 = 9;
+
+/// 1 マスの高さ（ピクセル）
+const CELL_HEIGHT: u32 = 28;
+
+/// マス間の区切り線幅（ピクセル）
+const CELL_GAP: u32 = 1;
+
+/// 1 行あたりのフレーム数
+pub const FRAMES_PER_ROW: usize = 60;
+
+/// 画像外周の余白（ピクセル）
+const MARGIN: u32 = 10;
+
+/// 目盛り領域の高さ（ピクセル）
+const TICK_AREA: u32 = 10;
+
+/// 区切り線・枠色
+const BORDER: Rgba<u8> = Rgba([0x11, 0x12, 0x14, 0xFF]);
+
+/// 目盛り色
+const TICK: Rgba<u8> = Rgba([0x9A, 0x9C, 0xA3, 0xFF]);
+
+/// フレーム種別毎の表示色を返す。
+pub fn kind_color(kind: FrameKind) -> Rgba<u8> {
+    match kind {
+        FrameKind::Startup => Rgba([0x3D, 0xD6, 0x8C, 0xFF]),
+        FrameKind::Active => Rgba([0xE2, 0x4B, 0x5F, 0xFF]),
+        FrameKind::Gap => Rgba([0x4A, 0x4D, 0x55, 0xFF]),
+        FrameKind::Recovery => Rgba([0x2F, 0x8F, 0xD8, 0xFF]),
+        FrameKind::Opening => Rgba([0x8B, 0x5A, 0x2B, 0xFF]),
+    }
+}
+
+/// 無敵種別毎の表示色を返す。
+pub fn invuln_color(kind: InvulnKind) -> Rgba<u8> {
+    match kind {
+        InvulnKind::Full => Rgba([0xF2, 0xF2, 0xF2, 0xFF]),
+        InvulnKind::Strike => Rgba([0xFF, 0xD2, 0x3F, 0xFF]),
+        InvulnKind::GuardPoint => Rgba([0xFF, 0x8C, 0x42, 0xFF]),
+        InvulnKind::Armor => Rgba([0xA8, 0x6B, 0x3C, 0xFF]),
+        InvulnKind::Projectile => Rgba([0xB0, 0x7C, 0xFF, 0xFF]),
+        InvulnKind::Throw => Rgba([0xFF, 0x6F, 0xB5, 0xFF]),
+        InvulnKind::UpperBody | InvulnKind::LowProfile | InvulnKind::Airborne => {
+            Rgba([0x7F, 0xDB, 0xFF, 0xFF])
+        }
+        InvulnKind::Other => Rgba([0x9A, 0x9C, 0xA3, 0xFF]),
+    }
+}
+
+/// 1 フレーム分の表示内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameCell {
+    /// フレーム種別
+    pub kind: FrameKind,
+    /// 無敵種別（無敵無しは None）
+    pub invuln: Option<InvulnKind>,
+}
+
+/// 矩形塗りつぶし関数（画像範囲外は無視）
+fn fill_rect(canvas: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..(y + height).min(canvas.height()) {
+        for px in x..(x + width).min(canvas.width()) {
+            canvas.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// フレームメーター描画関数
+///
+/// # 概要
+/// 1 フレーム目を左端として各マスを描画し、[`FRAMES_PER_ROW`] フレーム毎に折り返す。
+/// 各行の下に 5 フレーム毎の短い目盛り、10 フレーム毎の長い目盛りを描く。
+///
+/// # 引数
+/// * `cells` - フレーム毎の表示内容（1 フレーム目から順）
+pub fn render_meter(cells: &[FrameCell]) -> RgbaImage {
+    let columns = cells.len().clamp(1, FRAMES_PER_ROW) as u32;
+    let rows = cells.len().div_ceil(FRAMES_PER_ROW).max(1) as u32;
+    let row_height = CELL_HEIGHT + 2 * CELL_GAP + TICK_AREA;
+    let width = 2 * MARGIN + columns * (CELL_WIDTH + CELL_GAP) + CELL_GAP;
+    let height = 2 * MARGIN + rows * row_height;
+    let mut canvas = RgbaImage::from_pixel(width, height, BACKGROUND);
+
+    for row in 0..rows {
+        let start = row as usize * FRAMES_PER_ROW;
+        let row_cells = &cells[start.min(cells.len())..(start + FRAMES_PER_ROW).min(cells.len())];
+        let top = MARGIN + row * row_height;
+
+        // 行の枠（区切り線を兼ねる）
+        let row_width = row_cells.len() as u32 * (CELL_WIDTH + CELL_GAP) + CELL_GAP;
+        fill_rect(
+            &mut canvas,
+            MARGIN,
+            top,
+            row_width,
+            CELL_HEIGHT + 2 * CELL_GAP,
+            BORDER,
+        );
+
+        for (i, cell) in row_cells.iter().enumerate() {
+            let x = MARGIN + CELL_GAP + i as u32 * (CELL_WIDTH + CELL_GAP);
+            let y = top + CELL_GAP;
+            fill_rect(
+                &mut canvas,
+                x,
+                y,
+                CELL_WIDTH,
+                CELL_HEIGHT,
+                kind_color(cell.kind),
+            );
+            // 無敵は帯で重ねる（姿勢の低さによる回避は下側、それ以外は上側）
+            if let Some(kind) = cell.invuln {
+                let band = CELL_HEIGHT / 4;
+                let band_y = if kind == InvulnKind::LowProfile {
+                    y + CELL_HEIGHT - band
+                } else {
+                    y
+                };
+                fill_rect(&mut canvas, x, band_y, CELL_WIDTH, band, invuln_color(kind));
+            }
+
+            // 目盛り（通算フレーム番号基準）
+            let frame = start + i + 1;
+            let tick = if frame.is_multiple_of(10) {
+                TICK_AREA - 2
+            } else if frame.is_multiple_of(5) {
+                TICK_AREA / 2 - 1
+            } else {
+                0
+            };
+            if tick > 0 {
+                fill_rect(
+                    &mut canvas,
+                    x + CELL_WIDTH / 2,
+                    top + CELL_HEIGHT + 2 * CELL_GAP + 1,
+                    1,
+                    tick,
+                    TICK,
+                );
+            }
+        }
+    }
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(kinds: &[(FrameKind, usize)]) -> Vec<FrameCell> {
+        kinds
+            .iter()
+            .flat_map(|&(kind, n)| std::iter::repeat_n(FrameCell { kind, invuln: None }, n))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_meter_colors() {
+        let image = render_meter(&cells(&[
+            (FrameKind::Startup, 2),
+            (FrameKind::Active, 1),
+            (FrameKind::Recovery, 1),
+        ]));
+        let y = MARGIN + CELL_GAP + CELL_HEIGHT / 2;
+        let x_of = |i: u32| MARGIN + CELL_GAP + i * (CELL_WIDTH + CELL_GAP) + 1;
+        assert_eq!(*image.get_pixel(x_of(0), y), kind_color(FrameKind::Startup));
+        assert_eq!(*image.get_pixel(x_of(2), y), kind_color(FrameKind::Active));
+        assert_eq!(
+            *image.get_pixel(x_of(3), y),
+            kind_color(FrameKind::Recovery)
+        );
+        assert_eq!(*image.get_pixel(0, 0), BACKGROUND);
+    }
+
+    #[test]
+    fn test_render_meter_wraps_rows() {
+        let one_row = render_meter(&cells(&[(FrameKind::Active, FRAMES_PER_ROW)]));
+        let two_rows = render_meter(&cells(&[(FrameKind::Active, FRAMES_PER_ROW + 1)]));
+        assert_eq!(one_row.width(), two_rows.width());
+        assert!(two_rows.height() > one_row.height());
+    }
+
+    #[test]
+    fn test_render_meter_invuln_band() {
+        let mut frames = cells(&[(FrameKind::Startup, 2)]);
+        frames[0].invuln = Some(InvulnKind::Strike);
+        frames[1].invuln = Some(InvulnKind::LowProfile);
+        let image = render_meter(&frames);
+        let top = MARGIN + CELL_GAP;
+        let bottom = MARGIN + CELL_GAP + CELL_HEIGHT - 1;
+        let x = MARGIN + CELL_GAP + 1;
+        assert_eq!(*image.get_pixel(x, top), invuln_color(InvulnKind::Strike));
+        assert_eq!(*image.get_pixel(x, bottom), kind_color(FrameKind::Startup));
+        let x = x + CELL_WIDTH + CELL_GAP;
+        assert_eq!(*image.get_pixel(x, top), kind_color(FrameKind::Startup));
+        assert_eq!(
+            *image.get_pixel(x, bottom),
+            invuln_color(InvulnKind::LowProfile)
+        );
+    }
+
+    #[test]
+    fn test_render_meter_empty() {
+        let image = render_meter(&[]);
+        assert!(image.width() > 0 && image.height() > 0);
+    }
+}
@@ -0,0 +1,27 @@
+//! `render/mod.rs`
+//!
+//! 画像生成機能群を提供する。
+//! ヒットボックス画像の合成やフレームメーターなど、Discord へ添付する画像をサーバー側で描画する処理を含む。
+
+pub mod hitbox; // ヒットボックス画像合成
+pub mod meter; // フレームメーター描画
+
+use crate::error::{AppError, Result};
+use image::{codecs::png::PngEncoder, ImageEncoder, RgbaImage};
+
+/// 描画背景色（Discord ダークテーマ近似色）
+pub const BACKGROUND: image::Rgba<u8> = image::Rgba([0x2B, 0x2D, 0x31, 0xFF]);
+
+/// PNG エンコード関数
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    PngEncoder::new(&mut bytes)
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .map_err(|e| AppError::DataProcessing(format!("PNG の生成に失敗しました: {e}")))?;
+    Ok(bytes)
+}
@@ -3,8 +3,9 @@
 //! このファイルでは、テスト用のユーティリティ関数とモックデータを提供します。
 //! ユニットテストや結合テストで使用するためのヘルパー関数、テストデータ生成機能などを含みます。
 
+use crate::models::MoveInfo;
 #[cfg(test)]
-use crate::models::{CharInfo, MoveAliases, MoveInfo};
+use crate::models::{CharInfo, MoveAliases};
 #[cfg(test)]
 use std::fs;
 #[cfg(test)]
@@ -74,6 +75,105 @@ pub fn _create_test_char_info() -> CharInfo {
     }
 }
 
+/// テスト用の技情報ビルダー
+///
+/// 5P 相当の技情報を基に、テストで必要な項目だけを上書きして技情報を生成します。
+/// `baiken-cli` のテストからは `test-utils` フィーチャー経由で利用します。
+#[derive(Debug, Clone)]
+pub struct MoveInfoBuilder(MoveInfo);
+
+impl MoveInfoBuilder {
+    /// 入力を指定してビルダーを生成する
+    pub fn new(input: &str) -> Self {
+        Self(MoveInfo {
+            input: input.to_string(),
+            name: input.to_string(),
+            damage: Some(26),
+            guard: "Mid".to_string(),
+            startup: Some(4),
+            active: "3".to_string(),
+            recovery: Some(9),
+            on_hit: "+2".to_string(),
+            on_block: "-1".to_string(),
+            level: "0".to_string(),
+            counter: "3".to_string(),
+            move_type: "Normal".to_string(),
+            risc_gain: Some(23.0),
+            risc_loss: Some(18.0),
+            wall_damage: Some(9),
+            input_tension: Some(0.0),
+            chip_ratio: Some(0.0),
+            scaling: Some(0.8),
+            invincibility: "None".to_string(),
+            cancel: "Special, Super".to_string(),
+            caption: String::new(),
+            notes: String::new(),
+        })
+    }
+
+    /// 技種別を設定する
+    pub fn move_type(mut self, move_type: &str) -> Self {
+        self.0.move_type = move_type.to_string();
+        self
+    }
+
+    /// 発生・持続・硬直を設定する
+    pub fn frames(mut self, startup: i32, active: &str, recovery: i32) -> Self {
+        self.0.startup = Some(startup);
+        self.0.active = active.to_string();
+        self.0.recovery = Some(recovery);
+        self
+    }
+
+    /// 発生を設定する
+    pub fn startup(mut self, startup: Option<i32>) -> Self {
+        self.0.startup = startup;
+        self
+    }
+
+    /// ヒット時の硬直差を設定する
+    pub fn on_hit(mut self, on_hit: &str) -> Self {
+        self.0.on_hit = on_hit.to_string();
+        self
+    }
+
+    /// ガード時の硬直差を設定する
+    pub fn on_block(mut self, on_block: &str) -> Self {
+        self.0.on_block = on_block.to_string();
+        self
+    }
+
+    /// カウンター種別を設定する
+    pub fn counter(mut self, counter: &str) -> Self {
+        self.0.counter = counter.to_string();
+        self
+    }
+
+    /// キャンセル欄を設定する
+    pub fn cancel(mut self, cancel: &str) -> Self {
+        self.0.cancel = cancel.to_string();
+        self
+    }
+
+    /// RISC 増加量・減少量を設定する
+    pub fn risc(mut self, risc_gain: Option<f64>, risc_loss: Option<f64>) -> Self {
+        self.0.risc_gain = risc_gain;
+        self.0.risc_loss = risc_loss;
+        self
+    }
+
+    /// 壁ダメージを設定する
+    pub fn wall_damage(mut self, wall_damage: Option<i32>) -> Self {
+        self.0.wall_damage = wall_damage;
+        self
+    }
+
+    /// 技情報を生成する
+    pub fn build(self) -> MoveInfo {
+        self.0
+    }
+}
+
 /// テスト用の技情報を生成する
 ///
 /// テスト用のダミー技情報を生成します。
@@ -172,54 +272,3 @@ pub fn create_test_json_file<P: AsRef<Path>>(
     fs::write(path, content)?;
     Ok(())
 }
-
-/// モック用のDiscordコンテキストを作成する
-///
-/// テスト用のモックDiscordコンテキストを作成します。
-/// 実際のAPIは呼び出さず、内部処理のテストに使用します。
-///
-/// このモックは実際のコンテキストと同じインターフェースを持ちますが、
-/// 常に成功するレスポンスを返します。
-#[cfg(test)]
-pub struct _MockDiscordContext {
-    pub messages: Vec<String>,
-    pub embeds: Vec<String>,
-}
-
-#[cfg(test)]
-impl _MockDiscordContext {
-    /// 新しいモックコンテキストを作成する
-    ///
-    /// # 戻り値
-    /// `MockDiscordContext` - 新しいモックコンテキスト
-    pub fn new() -> Self {
-        Self {
-            messages: Vec::new(),
-            embeds: Vec::new(),
-        }
-    }
-
-    /// メッセージを送信する（モック）
-    ///
-    /// # 引数
-    /// * `content` - メッセージ内容
-    ///
-    /// # 戻り値
-    /// `Result<(), ()>` - 常に `Ok(())`
-    pub fn send_message(&mut self, content: &str) -> Result<(), ()> {
-        self.messages.push(content.to_string());
-        Ok(())
-    }
-
-    /// 埋め込みメッセージを送信する（モック）
-    ///
-    /// # 引数
-    /// * `content` - 埋め込みメッセージ内容
-    ///
-    /// # 戻り値
-    /// `Result<(), ()>` - 常に `Ok(())`
-    pub fn send_embed(&mut self, content: &str) -> Result<(), ()> {
-        self.embeds.push(content.to_string());
-        Ok(())
-    }
-}
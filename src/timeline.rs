@@ -0,0 +1,940 @@
+//! `timeline.rs`
+//!
+//! Dustloop 形式のフレーム表記（発生・持続・硬直）を解析し、型付きのタイムラインを提供する。
+//! 多段技の空白区間 `3(5)4`、範囲 `10~14`、繰り返し `[1(3)]×5`、着地硬直 `+3`、
+//! `total`、`Until Landing`、別表記 `[...]`・`or` に対応し、フレームメーター・比較・計算処理で共有する。
+
+use crate::error::{AppError, Result};
+use crate::invuln::{self, InvulnKind, InvulnRange};
+use crate::MoveInfo;
+
+/// 繰り返し指定（"×N"）の最大回数
+const MAX_REPEAT: u32 = 100;
+
+/// 1 表記あたりの最大区間数
+const MAX_SEGMENTS: usize = 256;
+
+/// 1 表記あたりの最大フレーム数（範囲は上限値、着地硬直・total を含む）
+const MAX_FRAMES: u64 = 1000;
+
+/// フレーム種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKind {
+    /// 発生前
+    Startup,
+    /// 攻撃判定持続
+    Active,
+    /// 持続中の攻撃判定が無い区間
+    Gap,
+    /// 硬直
+    Recovery,
+    /// 連係の隙間（次の技の発生前のうち相手が行動可能なフレーム）
+    Opening,
+}
+
+/// フレーム数（範囲・上限不明を含む）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frames {
+    /// 最小フレーム数
+    pub min: u32,
+    /// 最大フレーム数（None は上限不明）
+    pub max: Option<u32>,
+}
+
+impl Frames {
+    /// 固定フレーム数を生成する。
+    pub fn fixed(frames: u32) -> Self {
+        Self {
+            min: frames,
+            max: Some(frames),
+        }
+    }
+
+    /// 上限不明のフレーム数を生成する。
+    pub fn open(min: u32) -> Self {
+        Self { min, max: None }
+    }
+
+    /// 固定値か否かを返す。
+    pub fn is_fixed(self) -> bool {
+        self.max == Some(self.min)
+    }
+}
+
+/// 区間種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// 判定あり区間（1 区間 = 1 ヒット）
+    Hit,
+    /// 判定なし区間（括弧表記）
+    Gap,
+}
+
+/// タイムライン区間
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// 区間種別
+    pub kind: SegmentKind,
+    /// フレーム数
+    pub frames: Frames,
+    /// 終了条件（"Landing" 等。`Until` 表記の場合のみ）
+    pub condition: Option<String>,
+}
+
+impl Segment {
+    /// 固定フレーム数の区間を生成する。
+    fn fixed(kind: SegmentKind, frames: u32) -> Self {
+        Self {
+            kind,
+            frames: Frames::fixed(frames),
+            condition: None,
+        }
+    }
+}
+
+/// フレーム表記の解析結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameSpec {
+    /// 区間列（表記順）
+    pub segments: Vec<Segment>,
+    /// 着地硬直等の追加フレーム（`+N` 表記）
+    pub landing: Option<u32>,
+    /// 全体フレーム（`total N` 表記）
+    pub total: Option<u32>,
+    /// 別表記（`[...]` または `or` 以降）
+    pub variants: Vec<FrameSpec>,
+    /// 解釈対象外の注記（"(52 Flash)" 等）
+    pub notes: Vec<String>,
+}
+
+impl FrameSpec {
+    /// 区間・着地硬直・全体フレームのいずれも無いか否かを返す。
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty() && self.landing.is_none() && self.total.is_none()
+    }
+
+    /// 最小フレーム数（区間合計 + 着地硬直）を返す。
+    pub fn min_frames(&self) -> u32 {
+        self.segments.iter().map(|s| s.frames.min).sum::<u32>() + self.landing.unwrap_or(0)
+    }
+
+    /// 最大フレーム数を返す（上限不明の区間を含む場合は None）。
+    pub fn max_frames(&self) -> Option<u32> {
+        self.segments
+            .iter()
+            .map(|s| s.frames.max)
+            .sum::<Option<u32>>()
+            .map(|max| max + self.landing.unwrap_or(0))
+    }
+
+    /// ヒット数（判定あり区間数）を返す。
+    pub fn hits(&self) -> usize {
+        self.segments
+            .iter()
+            .filter(|s| s.kind == SegmentKind::Hit)
+            .count()
+    }
+
+    /// 終了条件一覧を返す。
+    pub fn conditions(&self) -> Vec<&str> {
+        self.segments
+            .iter()
+            .filter_map(|s| s.condition.as_deref())
+            .collect()
+    }
+}
+
+/// 字句
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// 数値
+    Num(u32),
+    /// 単語
+    Word(String),
+    /// 区切り（"," または "→"）
+    Sep,
+    /// "("
+    LParen,
+    /// ")"
+    RParen,
+    /// "["
+    LBracket,
+    /// "]"
+    RBracket,
+    /// "~"
+    Tilde,
+    /// "+"
+    Plus,
+    /// 繰り返し（"×"・"x"・"*"）
+    Times,
+}
+
+/// 字句解析関数
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let num = chars[start..i]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .map_err(|_| parse_error(text, "数値が大きすぎます"))?;
+            tokens.push(Token::Num(num));
+            continue;
+        }
+        // "2x5"・"]x2" の x は繰り返し記号
+        let after_count = matches!(tokens.last(), Some(Token::Num(_) | Token::RBracket));
+        if c == 'x' && after_count && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+            tokens.push(Token::Times);
+            i += 1;
+            continue;
+        }
+        if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphabetic() {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+            continue;
+        }
+        let token = match c {
+            ',' | '→' => Token::Sep,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '~' => Token::Tilde,
+            '+' => Token::Plus,
+            '×' | '*' => Token::Times,
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            _ => return Err(parse_error(text, &format!("不明な文字 '{c}'"))),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// 解析エラー生成関数
+fn parse_error(text: &str, reason: &str) -> AppError {
+    AppError::DataProcessing(format!("フレーム表記 '{text}' を解析できません: {reason}"))
+}
+
+/// 字句列の表示用文字列化関数（注記用）
+fn tokens_to_text(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    let mut previous_word = false;
+    for token in tokens {
+        let part = match token {
+            Token::Num(n) => n.to_string(),
+            Token::Word(w) => w.clone(),
+            Token::Sep => ",".to_string(),
+            Token::LParen => "(".to_string(),
+            Token::RParen => ")".to_string(),
+            Token::LBracket => "[".to_string(),
+            Token::RBracket => "]".to_string(),
+            Token::Tilde => "~".to_string(),
+            Token::Plus => "+".to_string(),
+            Token::Times => "×".to_string(),
+        };
+        // 数値・単語が連続する場合のみ空白で区切る
+        let is_word = matches!(token, Token::Num(_) | Token::Word(_));
+        if previous_word && is_word {
+            text.push(' ');
+        }
+        previous_word = is_word;
+        text += &part;
+    }
+    text
+}
+
+/// 構文解析器
+struct Parser<'a> {
+    /// 元の表記（エラー表示用）
+    text: &'a str,
+    /// 字句列
+    tokens: Vec<Token>,
+    /// 読み取り位置
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// 現在の字句を返す。
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// 現在の字句を返して読み進める。
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// 現在の字句が単語 `word`（大文字小文字無視）か否かを返す。
+    fn peek_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(word))
+    }
+
+    /// 数値を読み取る。
+    fn expect_num(&mut self) -> Result<u32> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(n),
+            _ => Err(parse_error(self.text, "数値が必要です")),
+        }
+    }
+
+    /// 連続する単語を空白区切りで連結して読み取る。
+    fn words(&mut self) -> String {
+        let mut words = Vec::new();
+        while let Some(Token::Word(w)) = self.peek() {
+            words.push(w.clone());
+            self.pos += 1;
+        }
+        words.join(" ")
+    }
+
+    /// 繰り返し指定（"×N" / "×N~M"）を読み取る。
+    fn repeat(&mut self, spec: &mut FrameSpec) -> Result<Option<u32>> {
+        if self.peek() != Some(&Token::Times) {
+            return Ok(None);
+        }
+        self.pos += 1;
+        let count = self.expect_num()?;
+        if count > MAX_REPEAT {
+            return Err(parse_error(
+                self.text,
+                &format!("繰り返し回数が上限（{MAX_REPEAT}）を超えています"),
+            ));
+        }
+        if self.peek() == Some(&Token::Tilde) {
+            self.pos += 1;
+            let max = self.expect_num()?;
+            spec.notes.push(format!("repeats {count}~{max} times"));
+        }
+        Ok(Some(count))
+    }
+
+    /// 区間を指定回数追加する（区間数が上限を超える場合はエラー）。
+    fn push_segments(&self, spec: &mut FrameSpec, segments: &[Segment], count: u32) -> Result<()> {
+        if spec.segments.len() + segments.len() * count as usize > MAX_SEGMENTS {
+            return Err(parse_error(
+                self.text,
+                &format!("区間数が上限（{MAX_SEGMENTS}）を超えています"),
+            ));
+        }
+        for _ in 0..count {
+            spec.segments.extend(segments.iter().cloned());
+        }
+        Ok(())
+    }
+
+    /// 区間列を解析する。
+    ///
+    /// # 引数
+    /// * `nested` - 角括弧内（"]" で終了）か否か
+    fn spec(&mut self, nested: bool) -> Result<FrameSpec> {
+        let spec = self.spec_body(nested)?;
+        let frames = spec
+            .segments
+            .iter()
+            .map(|s| u64::from(s.frames.max.unwrap_or(s.frames.min)))
+            .sum::<u64>()
+            + u64::from(spec.landing.unwrap_or(0));
+        if frames.max(u64::from(spec.total.unwrap_or(0))) > MAX_FRAMES {
+            return Err(parse_error(
+                self.text,
+                &format!("フレーム数が上限（{MAX_FRAMES}）を超えています"),
+            ));
+        }
+        Ok(spec)
+    }
+
+    /// 区間列を解析する（上限確認前の本体）。
+    fn spec_body(&mut self, nested: bool) -> Result<FrameSpec> {
+        let mut spec = FrameSpec::default();
+        while let Some(token) = self.peek().cloned() {
+            match token {
+                Token::RBracket if nested => break,
+                Token::Sep | Token::Tilde => self.pos += 1,
+                Token::Num(n) => {
+                    self.pos += 1;
+                    // "N total"
+                    if self.peek_word("total") {
+                        self.pos += 1;
+                        spec.total = Some(n);
+                        let note = self.words();
+                        if !note.is_empty() {
+                            spec.notes.push(note);
+                        }
+                        continue;
+                    }
+                    // 範囲 "N~M" / "N to M"
+                    let mut frames = Frames::fixed(n);
+                    if self.peek() == Some(&Token::Tilde) || self.peek_word("to") {
+                        self.pos += 1;
+                        let max = self.expect_num()?;
+                        frames = Frames {
+                            min: n.min(max),
+                            max: Some(n.max(max)),
+                        };
+                    }
+                    let segment = Segment {
+                        kind: SegmentKind::Hit,
+                        frames,
+                        condition: None,
+                    };
+                    let count = self.repeat(&mut spec)?.unwrap_or(1);
+                    self.push_segments(&mut spec, &[segment], count)?;
+                }
+                Token::Word(word) => match word.to_lowercase().as_str() {
+                    "or" => {
+                        self.pos += 1;
+                        spec.variants.push(self.spec(nested)?);
+                    }
+                    "total" => {
+                        self.pos += 1;
+                        spec.total = Some(self.expect_num()?);
+                    }
+                    _ => {
+                        // "Until Landing" 等の上限不明区間（"Until" 以外の単語も条件として扱う）
+                        let mut condition = self.words();
+                        if let Some(rest) = condition.strip_prefix("Until ") {
+                            condition = rest.to_string();
+                        } else if let Some(rest) = condition.strip_prefix("until ") {
+                            condition = rest.to_string();
+                        }
+                        let segment = Segment {
+                            kind: SegmentKind::Hit,
+                            frames: Frames::open(0),
+                            condition: Some(condition),
+                        };
+                        let count = self.repeat(&mut spec)?.unwrap_or(1);
+                        self.push_segments(&mut spec, &[segment], count)?;
+                    }
+                },
+                Token::Plus => {
+                    self.pos += 1;
+                    let landing = self.expect_num()?;
+                    spec.landing = Some(spec.landing.unwrap_or(0).saturating_add(landing));
+                }
+                Token::LParen => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    let close = self.tokens[start..]
+                        .iter()
+                        .position(|t| *t == Token::RParen)
+                        .map(|offset| start + offset)
+                        .ok_or_else(|| parse_error(self.text, "')' がありません"))?;
+                    match &self.tokens[start..close] {
+                        // "(5)" は判定なし区間、"(22+)" は上限不明の判定なし区間
+                        [Token::Num(n)] => self.push_segments(
+                            &mut spec,
+                            &[Segment::fixed(SegmentKind::Gap, *n)],
+                            1,
+                        )?,
+                        [Token::Num(n), Token::Plus] => self.push_segments(
+                            &mut spec,
+                            &[Segment {
+                                kind: SegmentKind::Gap,
+                                frames: Frames::open(*n),
+                                condition: None,
+                            }],
+                            1,
+                        )?,
+                        inner => spec.notes.push(tokens_to_text(inner)),
+                    }
+                    self.pos = close + 1;
+                }
+                Token::LBracket => {
+                    self.pos += 1;
+                    let inner = self.spec(true)?;
+                    if self.next() != Some(Token::RBracket) {
+                        return Err(parse_error(self.text, "']' がありません"));
+                    }
+                    match self.repeat(&mut spec)? {
+                        // "[1(3)]×5" は繰り返し
+                        Some(count) => self.push_segments(&mut spec, &inner.segments, count)?,
+                        // "36 [79]" は別表記
+                        None => spec.variants.push(inner),
+                    }
+                }
+                Token::RParen | Token::RBracket | Token::Times => {
+                    return Err(parse_error(self.text, "予期しない記号があります"));
+                }
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// フレーム表記解析関数
+///
+/// # 概要
+/// Dustloop のフレーム表記を区間列へ変換する。
+/// 空文字・"-"・"~" はデータ無しとして空の結果を返す。
+///
+/// # 引数
+/// * `text` - フレーム表記（例："3(5)4"・"16~32"・"Until Landing+3"）
+///
+/// # 戻り値
+/// 解析結果。解釈できない記号を含む場合・繰り返し回数や区間数、フレーム数が上限を超える場合はエラー
+pub fn parse_frames(text: &str) -> Result<FrameSpec> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() || trimmed == "-" {
+        return Ok(FrameSpec::default());
+    }
+    let mut parser = Parser {
+        text,
+        tokens: tokenize(trimmed)?,
+        pos: 0,
+    };
+    let spec = parser.spec(false)?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parse_error(text, "予期しない ']' があります"));
+    }
+    Ok(spec)
+}
+
+/// 技 1 つ分のタイムライン
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeline {
+    /// 発生フレーム（最初の攻撃判定フレームを含む）
+    pub startup: FrameSpec,
+    /// 持続フレーム
+    pub active: FrameSpec,
+    /// 硬直フレーム
+    pub recovery: FrameSpec,
+    /// 無敵範囲
+    pub invuln: Vec<InvulnRange>,
+}
+
+/// 数値フレーム項目の表記化関数（None は "-"）
+fn optional_frames(value: Option<i32>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+impl Timeline {
+    /// 技情報からタイムラインを生成する。
+    pub fn from_move(move_info: &MoveInfo) -> Result<Self> {
+        Ok(Self {
+            startup: parse_frames(&optional_frames(move_info.startup))?,
+            active: parse_frames(&move_info.active)?,
+            recovery: parse_frames(&optional_frames(move_info.recovery))?,
+            invuln: invuln::parse_invuln(&move_info.invincibility),
+        })
+    }
+
+    /// 指定フレーム（1 始まり）の無敵種別を返す。
+    /// 複数の無敵が重なる場合は完全無敵・打撃無敵等の優先度の高い種別を返す。
+    pub fn invuln_at(&self, frame: u32) -> Option<InvulnKind> {
+        self.invuln
+            .iter()
+            .filter(|range| range.contains(frame))
+            .map(|range| range.kind)
+            .min()
+    }
+
+    /// 発生前フレーム数（発生フレーム - 1）を返す。
+    pub fn startup_frames(&self) -> u32 {
+        self.startup.min_frames().saturating_sub(1)
+    }
+
+    /// 全体フレーム数（最小値）を返す。
+    /// `total` 表記がある場合はそれを優先する。
+    pub fn total_frames(&self) -> u32 {
+        self.active.total.unwrap_or_else(|| {
+            self.startup_frames() + self.active.min_frames() + self.recovery.min_frames()
+        })
+    }
+
+    /// フレーム毎の種別列（1 フレーム目から順）を返す。
+    /// 上限不明の区間は最小フレーム数分のみ含む。
+    pub fn frame_kinds(&self) -> Vec<FrameKind> {
+        let mut kinds = vec![FrameKind::Startup; self.startup_frames() as usize];
+        for segment in &self.active.segments {
+            let kind = match segment.kind {
+                SegmentKind::Hit => FrameKind::Active,
+                SegmentKind::Gap => FrameKind::Gap,
+            };
+            kinds.extend(std::iter::repeat_n(kind, segment.frames.min as usize));
+        }
+        kinds.extend(std::iter::repeat_n(
+            FrameKind::Active,
+            self.active.landing.unwrap_or(0) as usize,
+        ));
+        kinds.extend(std::iter::repeat_n(
+            FrameKind::Recovery,
+            self.recovery.min_frames() as usize,
+        ));
+        kinds
+    }
+}
+
+/// 技の繋ぎ方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// キャンセル（前の技の硬直を破棄して持続終了直後に次の技を出す）
+    Cancel,
+    /// 目押し（前の技の硬直終了後に次の技を出す）
+    Link,
+}
+
+/// 硬直差解析関数
+/// "+4 (IAD)"・"-2~+6 [+5~+12]" 等の表記から先頭の値を返す（"-" 等は None）。
+pub fn parse_advantage(text: &str) -> Option<i32> {
+    let text = text.trim();
+    let (sign, rest) = match text.chars().next()? {
+        '+' => (1, &text[1..]),
+        '-' => (-1, &text[1..]),
+        _ => (1, text),
+    };
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse::<i32>().ok().map(|value| sign * value)
+}
+
+/// キャンセル可否判定関数
+///
+/// # 概要
+/// キャンセル欄（N: 通常技、S: 必殺技・覚醒必殺技）を優先し、記載が無い場合は技種別から判定する。
+/// 記載が無い通常技は別の通常技（ガトリング）・必殺技・覚醒必殺技へ、必殺技は覚醒必殺技へキャンセル可能とみなす。
+///
+/// # 引数
+/// * `from` - 先に出す技
+/// * `to` - 次に出す技
+pub fn can_cancel(from: &MoveInfo, to: &MoveInfo) -> bool {
+    let from_type = from.move_type.to_lowercase();
+    let letters = Some(from.cancel.trim()).filter(|c| !c.is_empty() && *c != "-");
+    let from_normal = from_type == "normal";
+    match to.move_type.to_lowercase().as_str() {
+        "normal" => {
+            letters.is_some_and(|l| l.contains('N')) || (from_normal && from.input != to.input)
+        }
+        "super" => letters.map_or(from_normal || from_type == "special", |l| l.contains('S')),
+        _ => letters.map_or(from_normal, |l| l.contains('S')),
+    }
+}
+
+/// 連係の 1 繋ぎ分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceStep {
+    /// 繋ぎ方
+    pub transition: Transition,
+    /// ガード時の隙間フレーム（正の値は相手が行動可能なフレーム数、0 以下は連続ガード）
+    pub gap: Option<i32>,
+}
+
+/// 連係（"5K > 近S > 2HS" 等）のタイムライン
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sequence {
+    /// 各技のタイムライン（入力順）
+    pub timelines: Vec<Timeline>,
+    /// 技間の繋ぎ（`timelines.len() - 1` 個）
+    pub steps: Vec<SequenceStep>,
+}
+
+impl Sequence {
+    /// 技情報列から連係を生成する。
+    ///
+    /// # 概要
+    /// 隙間フレームは前の技のガード硬直差と次の技の発生から計算する。
+    /// 目押しは `発生 - 1 - 硬直差`、キャンセルは更に前の技の硬直分を差し引く。
+    pub fn from_moves(moves: &[MoveInfo]) -> Result<Self> {
+        let timelines = moves
+            .iter()
+            .map(Timeline::from_move)
+            .collect::<Result<Vec<_>>>()?;
+        let steps = moves
+            .windows(2)
+            .zip(timelines.windows(2))
+            .map(|(pair, lines)| {
+                let transition = if can_cancel(&pair[0], &pair[1]) {
+                    Transition::Cancel
+                } else {
+                    Transition::Link
+                };
+                let gap = parse_advantage(&pair[0].on_block)
+                    .filter(|_| !lines[1].startup.is_empty())
+                    .map(|advantage| {
+                        let startup = lines[1].startup.min_frames() as i32;
+                        let skipped = match transition {
+                            Transition::Cancel => lines[0].recovery.min_frames() as i32,
+                            Transition::Link => 0,
+                        };
+                        startup - 1 - advantage - skipped
+                    });
+                SequenceStep { transition, gap }
+            })
+            .collect();
+        Ok(Self { timelines, steps })
+    }
+
+    /// フレーム毎の種別と無敵種別（1 フレーム目から順）を返す。
+    /// キャンセルで繋ぐ技は持続終了までで打ち切り、隙間は次の技の発生前の末尾を [`FrameKind::Opening`] とする。
+    pub fn frames(&self) -> Vec<(FrameKind, Option<InvulnKind>)> {
+        let mut frames = Vec::new();
+        for (i, timeline) in self.timelines.iter().enumerate() {
+            let mut kinds = timeline.frame_kinds();
+            let opening = i
+                .checked_sub(1)
+                .and_then(|prev| self.steps.get(prev))
+                .and_then(|step| step.gap)
+                .filter(|gap| *gap > 0)
+                .map_or(0, |gap| {
+                    (gap as u32).min(timeline.startup_frames()) as usize
+                });
+            let startup = timeline.startup_frames() as usize;
+            kinds[startup - opening..startup].fill(FrameKind::Opening);
+            if self
+                .steps
+                .get(i)
+                .is_some_and(|step| step.transition == Transition::Cancel)
+            {
+                kinds.truncate((timeline.startup_frames() + timeline.active.min_frames()) as usize);
+            }
+            frames.extend(
+                kinds
+                    .into_iter()
+                    .zip(1..)
+                    .map(|(kind, frame)| (kind, timeline.invuln_at(frame))),
+            );
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_move_info;
+
+    fn hit(frames: u32) -> Segment {
+        Segment::fixed(SegmentKind::Hit, frames)
+    }
+
+    fn gap(frames: u32) -> Segment {
+        Segment::fixed(SegmentKind::Gap, frames)
+    }
+
+    #[test]
+    fn test_parse_simple_and_empty() {
+        assert_eq!(parse_frames("3").unwrap().segments, vec![hit(3)]);
+        assert!(parse_frames("-").unwrap().is_empty());
+        assert!(parse_frames("").unwrap().is_empty());
+        assert!(parse_frames("~").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_multi_hit() {
+        assert_eq!(
+            parse_frames("3(5)4").unwrap().segments,
+            vec![hit(3), gap(5), hit(4)]
+        );
+        assert_eq!(
+            parse_frames("4, (5), 30").unwrap().segments,
+            vec![hit(4), gap(5), hit(30)]
+        );
+        assert_eq!(parse_frames("6, 3").unwrap().segments, vec![hit(6), hit(3)]);
+        assert_eq!(
+            parse_frames("8→9→66").unwrap().segments,
+            vec![hit(8), hit(9), hit(66)]
+        );
+        let spec = parse_frames("3×4(20)2").unwrap();
+        assert_eq!(spec.hits(), 5);
+        assert_eq!(spec.min_frames(), 3 * 4 + 20 + 2);
+        assert_eq!(parse_frames("2x5").unwrap().segments, vec![hit(2); 5]);
+    }
+
+    #[test]
+    fn test_parse_ranges_and_open() {
+        let spec = parse_frames("10~14").unwrap();
+        assert_eq!((spec.min_frames(), spec.max_frames()), (10, Some(14)));
+        assert_eq!(parse_frames("15 to 34").unwrap().max_frames(), Some(34));
+
+        let spec = parse_frames("12(22+)10").unwrap();
+        assert_eq!(spec.min_frames(), 44);
+        assert_eq!(spec.max_frames(), None);
+
+        let spec = parse_frames("Until Landing").unwrap();
+        assert_eq!(spec.conditions(), vec!["Landing"]);
+        assert_eq!(spec.max_frames(), None);
+    }
+
+    #[test]
+    fn test_parse_landing_total_variants() {
+        let spec = parse_frames("Until Landing+3").unwrap();
+        assert_eq!(spec.landing, Some(3));
+        assert_eq!(spec.min_frames(), 3);
+
+        let spec = parse_frames("Until hit or corner+1").unwrap();
+        assert_eq!(spec.conditions(), vec!["hit or corner"]);
+        assert_eq!(spec.landing, Some(1));
+
+        assert_eq!(parse_frames("total 40").unwrap().total, Some(40));
+
+        let spec = parse_frames("4,4,4 [22 Total on whiff]").unwrap();
+        assert_eq!(spec.hits(), 3);
+        assert_eq!(spec.variants[0].total, Some(22));
+        assert_eq!(spec.variants[0].notes, vec!["on whiff"]);
+
+        let spec = parse_frames("31 or Until Hit*2").unwrap();
+        assert_eq!(spec.segments, vec![hit(31)]);
+        assert_eq!(spec.variants[0].hits(), 2);
+
+        let spec = parse_frames("[1(3)]×5, 1").unwrap();
+        assert_eq!(spec.hits(), 6);
+        assert_eq!(spec.min_frames(), 5 * 4 + 1);
+
+        let spec = parse_frames("71 (26×4)").unwrap();
+        assert_eq!(spec.segments, vec![hit(71)]);
+        assert_eq!(spec.notes, vec!["26×4"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_frames("3(5").is_err());
+        assert!(parse_frames("[3").is_err());
+        assert!(parse_frames("3]").is_err());
+        assert!(parse_frames("3 % 4").is_err());
+        assert!(parse_frames("3~").is_err());
+    }
+
+    #[test]
+    fn test_timeline_frame_kinds() {
+        let mut move_info = create_test_move_info().remove(0);
+        move_info.startup = Some(3);
+        move_info.active = "1(1)1".to_string();
+        move_info.recovery = Some(2);
+
+        let timeline = Timeline::from_move(&move_info).unwrap();
+        assert_eq!(
+            timeline.frame_kinds(),
+            vec![
+                FrameKind::Startup,
+                FrameKind::Startup,
+                FrameKind::Active,
+                FrameKind::Gap,
+                FrameKind::Active,
+                FrameKind::Recovery,
+                FrameKind::Recovery,
+            ]
+        );
+        assert_eq!(timeline.total_frames(), 7);
+        assert_eq!(timeline.invuln_at(1), None);
+
+        move_info.invincibility = "1-5 Airborne, 1-2 Strike".to_string();
+        let timeline = Timeline::from_move(&move_info).unwrap();
+        assert_eq!(timeline.invuln_at(1), Some(InvulnKind::Strike));
+        assert_eq!(timeline.invuln_at(3), Some(InvulnKind::Airborne));
+        assert_eq!(timeline.invuln_at(6), None);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_frames("[1(3)]×100").unwrap().segments.len(), 200);
+        assert!(parse_frames("3×99999999").is_err());
+        assert!(parse_frames("[[1]×100]×100").is_err());
+        assert!(parse_frames("99999999").is_err());
+        assert!(parse_frames("4294967295+4294967295").is_err());
+        assert!(parse_frames("1~4294967295").is_err());
+        assert!(parse_frames("3 99999 total").is_err());
+    }
+
+    #[test]
+    fn test_timeline_shipped_data() {
+        // 同梱データの全技が解析でき、タイムラインと合計が一致すること
+        let mut checked = 0;
+        for character in crate::CHARS {
+            let path = format!("data/{character}/{character}.json");
+            let moves: Vec<MoveInfo> = crate::utils::read_json_file(&path).unwrap();
+            for move_info in &moves {
+                let timeline = Timeline::from_move(move_info).unwrap_or_else(|e| {
+                    panic!("{character} {}: {e}", move_info.input);
+                });
+                if timeline.active.total.is_none() {
+                    assert_eq!(
+                        timeline.frame_kinds().len() as u32,
+                        timeline.total_frames(),
+                        "{character} {}",
+                        move_info.input
+                    );
+                }
+                if move_info.active.trim().chars().all(|c| c.is_ascii_digit())
+                    && !move_info.active.trim().is_empty()
+                {
+                    assert_eq!(
+                        timeline.active.min_frames().to_string(),
+                        move_info.active.trim()
+                    );
+                }
+                checked += 1;
+            }
+        }
+        assert!(checked > 1000);
+    }
+
+    #[test]
+    fn test_parse_advantage() {
+        assert_eq!(parse_advantage("+4 (IAD)"), Some(4));
+        assert_eq!(parse_advantage("-2~+6 [+5~+12]"), Some(-2));
+        assert_eq!(parse_advantage("0"), Some(0));
+        assert_eq!(parse_advantage("-"), None);
+        assert_eq!(parse_advantage(""), None);
+    }
+
+    #[test]
+    fn test_sequence_cancel_and_link() {
+        let base = create_test_move_info().remove(0);
+        let make =
+            |input: &str, move_type: &str, startup, active: &str, recovery, on_block: &str| {
+                let mut m = base.clone();
+                m.input = input.to_string();
+                m.move_type = move_type.to_string();
+                m.cancel = "-".to_string();
+                m.startup = Some(startup);
+                m.active = active.to_string();
+                m.recovery = Some(recovery);
+                m.on_block = on_block.to_string();
+                m.invincibility = "-".to_string();
+                m
+            };
+        let k = make("5K", "normal", 5, "3", 10, "-1");
+        let s = make("近S", "normal", 7, "6", 10, "+3");
+        let sp = make("236K", "special", 12, "3", 20, "-10");
+
+        // 通常技同士はガトリング
+        let seq = Sequence::from_moves(&[k.clone(), s.clone()]).unwrap();
+        assert_eq!(seq.steps[0].transition, Transition::Cancel);
+        assert_eq!(seq.steps[0].gap, Some(7 - 1 + 1 - 10));
+        assert_eq!(seq.frames().len(), 4 + 3 + 6 + 6 + 10);
+
+        // 同じ技・必殺技から通常技は目押し
+        let seq = Sequence::from_moves(&[k.clone(), k.clone()]).unwrap();
+        assert_eq!(seq.steps[0].transition, Transition::Link);
+        assert_eq!(seq.steps[0].gap, Some(5 - 1 + 1));
+        // 隙間は次の技の発生前（4F）に収まる範囲で描く
+        let frames = seq.frames();
+        assert_eq!(frames[4 + 3 + 10 - 1].0, FrameKind::Recovery);
+        assert!(frames[4 + 3 + 10..4 + 3 + 10 + 4]
+            .iter()
+            .all(|(kind, _)| *kind == FrameKind::Opening));
+        let seq = Sequence::from_moves(&[sp.clone(), k.clone()]).unwrap();
+        assert_eq!(seq.steps[0].transition, Transition::Link);
+
+        // キャンセル欄の記載を優先
+        let mut no_special = k.clone();
+        no_special.cancel = "RP".to_string();
+        assert!(can_cancel(&k, &sp));
+        assert!(!can_cancel(&no_special, &sp));
+    }
+}
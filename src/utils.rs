@@ -13,6 +13,7 @@ use std::{
 
 use crate::error::{AppError, Result};
 use crate::models::{CharInfo, MoveAliases, MoveInfo};
+use crate::ImageLinks;
 use colored::Colorize;
 
 /// JSONファイルを読み込む
@@ -40,14 +41,11 @@ where
 
     let mut contents = String::new();
     file.read_to_string(&mut contents).map_err(|e| {
-        AppError::Io(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "ファイルの読み込みに失敗しました: {} - {}",
-                path.display(),
-                e
-            ),
-        ))
+        AppError::Io(io::Error::other(format!(
+            "ファイルの読み込みに失敗しました: {} - {}",
+            path.display(),
+            e
+        )))
     })?;
 
     serde_json::from_str(&contents).map_err(|e| {
@@ -82,23 +80,17 @@ where
     })?;
 
     let mut file = File::create(path).map_err(|e| {
-        AppError::Io(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "ファイルの作成に失敗しました: {path_display} - {e}",
-                path_display = path.display()
-            ),
-        ))
+        AppError::Io(io::Error::other(format!(
+            "ファイルの作成に失敗しました: {path_display} - {e}",
+            path_display = path.display()
+        )))
     })?;
 
     file.write_all(json.as_bytes()).map_err(|e| {
-        AppError::Io(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "ファイルの書き込みに失敗しました: {path_display} - {e}",
-                path_display = path.display()
-            ),
-        ))
+        AppError::Io(io::Error::other(format!(
+            "ファイルの書き込みに失敗しました: {path_display} - {e}",
+            path_display = path.display()
+        )))
     })?;
 
     Ok(())
@@ -130,17 +122,16 @@ where
         ))
     })? {
         let entry = entry.map_err(|e| {
-            AppError::Io(io::Error::new(
-                io::ErrorKind::Other,
-                format!("ディレクトリエントリの読み込みに失敗しました: {e}"),
-            ))
+            AppError::Io(io::Error::other(format!(
+                "ディレクトリエントリの読み込みに失敗しました: {e}"
+            )))
         })?;
 
         let path = entry.path();
         if path.is_file()
             && path
                 .extension()
-                .map_or(false, |ext| ext == extension.unwrap_or("json"))
+                .is_some_and(|ext| ext == extension.unwrap_or("json"))
         {
             let data = read_json_file(&path)?;
             result.push(data);
@@ -160,7 +151,6 @@ where
 ///
 /// # 戻り値
 /// `Result<CharInfo>` - キャラクター情報
-#[allow(dead_code)]
 pub fn load_character_info(data_dir: &str, char_name: &str) -> Result<CharInfo> {
     let char_path = Path::new(data_dir).join(char_name).join("character.json");
 
@@ -175,7 +165,8 @@ pub fn load_character_info(data_dir: &str, char_name: &str) -> Result<CharInfo>
 
 /// 技情報を読み込む
 ///
-/// 指定されたキャラクターの技情報をJSONファイルから読み込みます。
+/// 指定されたキャラクターの技情報をJSONファイル（`<キャラクター名>/<キャラクター名>.json`）から読み込みます。
+/// `sqlite` 機能有効時にデータベースを読み込み元に設定している場合は、データベースから読み込みます。
 ///
 /// # 引数
 /// * `data_dir` - データディレクトリのパス
@@ -183,9 +174,15 @@ pub fn load_character_info(data_dir: &str, char_name: &str) -> Result<CharInfo>
 ///
 /// # 戻り値
 /// `Result<Vec<MoveInfo>>` - 技情報のベクター
-#[allow(dead_code)]
 pub fn load_move_info(data_dir: &str, char_name: &str) -> Result<Vec<MoveInfo>> {
-    let moves_path = Path::new(data_dir).join(char_name).join("moves.json");
+    #[cfg(feature = "sqlite")]
+    if crate::database::backing_store_enabled() {
+        return crate::database::load_move_info(data_dir, char_name);
+    }
+
+    let moves_path = Path::new(data_dir)
+        .join(char_name)
+        .join(char_name.to_owned() + ".json");
 
     if !moves_path.exists() {
         return Err(AppError::CharacterNotFound(format!(
@@ -206,7 +203,6 @@ pub fn load_move_info(data_dir: &str, char_name: &str) -> Result<Vec<MoveInfo>>
 ///
 /// # 戻り値
 /// `Result<Vec<MoveAliases>>` - 技エイリアス情報のベクター
-#[allow(dead_code)]
 pub fn load_move_aliases(data_dir: &str, char_name: &str) -> Result<Vec<MoveAliases>> {
     let aliases_path = Path::new(data_dir).join(char_name).join("aliases.json");
 
@@ -217,6 +213,55 @@ pub fn load_move_aliases(data_dir: &str, char_name: &str) -> Result<Vec<MoveAlia
     read_json_file(aliases_path)
 }
 
+/// 画像リンク情報を読み込む
+///
+/// 指定されたキャラクターの画像リンク情報を `images.json` から読み込みます。
+///
+/// # 引数
+/// * `data_dir` - データディレクトリのパス
+/// * `char_name` - キャラクター名
+///
+/// # 戻り値
+/// `Result<Vec<ImageLinks>>` - 画像リンク情報のベクター（ファイルが無い場合は空）
+pub fn load_image_links(data_dir: &str, char_name: &str) -> Result<Vec<ImageLinks>> {
+    let images_path = Path::new(data_dir).join(char_name).join("images.json");
+
+    if !images_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    read_json_file(images_path)
+}
+
+/// ファイルを置き換えで書き込む
+///
+/// 同じディレクトリの一時ファイルへ書き込んだ後に名前を変更するため、
+/// 書き込み途中のファイルが読み込まれることはありません。
+///
+/// # 引数
+/// * `path` - 書き込み先ファイルのパス
+/// * `bytes` - 書き込む内容
+///
+/// # 戻り値
+/// `Result<()>` - 書き込み結果
+pub fn write_file_atomic(path: impl AsRef<Path>, bytes: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    ensure_directory_exists(dir)?;
+
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(bytes)?;
+    file.persist(path).map_err(|e| {
+        AppError::Io(io::Error::other(format!(
+            "ファイルの書き込みに失敗しました: {} - {}",
+            path.display(),
+            e.error
+        )))
+    })?;
+
+    Ok(())
+}
+
 /// 入力文字列を正規化する
 ///
 /// 入力文字列から空白や記号を削除し、小文字に変換します。
@@ -354,14 +399,11 @@ pub fn calculate_file_hash(path: impl AsRef<Path>) -> Result<String> {
 
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer).map_err(|e| {
-        AppError::Io(io::Error::new(
-            io::ErrorKind::Other,
-            format!(
-                "ファイルの読み込みに失敗しました: {} - {}",
-                path.display(),
-                e
-            ),
-        ))
+        AppError::Io(io::Error::other(format!(
+            "ファイルの読み込みに失敗しました: {} - {}",
+            path.display(),
+            e
+        )))
     })?;
 
     let mut hasher = Md5::new();
@@ -386,14 +428,11 @@ pub fn ensure_directory_exists(dir_path: impl AsRef<Path>) -> Result<()> {
 
     if !dir_path.exists() {
         fs::create_dir_all(dir_path).map_err(|e| {
-            AppError::Io(io::Error::new(
-                io::ErrorKind::Other,
-                format!(
-                    "ディレクトリの作成に失敗しました: {} - {}",
-                    dir_path.display(),
-                    e
-                ),
-            ))
+            AppError::Io(io::Error::other(format!(
+                "ディレクトリの作成に失敗しました: {} - {}",
+                dir_path.display(),
+                e
+            )))
         })?;
         println!(
             "{}",
@@ -429,6 +468,25 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
+/// 連係・コンボの区切り文字
+pub const SEQUENCE_SEPARATOR: char = '>';
+
+/// 連係・コンボ指定を技毎に分割する
+///
+/// "5K > 近S > 2HS" のような ">" 区切りの指定を前後の空白を除いて分割します（空要素は除外）。
+///
+/// # 引数
+/// * `text` - 連係・コンボ指定
+///
+/// # 戻り値
+/// `Vec<&str>` - 技名・入力の一覧（入力順）
+pub fn split_sequence(text: &str) -> Vec<&str> {
+    text.split(SEQUENCE_SEPARATOR)
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -573,4 +631,26 @@ mod tests {
         let read_data = read_result.unwrap();
         assert_eq!(read_data, test_data);
     }
+
+    #[test]
+    fn test_write_file_atomic() {
+        let (_temp_dir, temp_path) = create_test_dir_structure();
+        let file_path = temp_path.join("cache/Sol_Badguy/grid.png");
+
+        // 親ディレクトリの作成と上書き
+        write_file_atomic(&file_path, b"first").unwrap();
+        write_file_atomic(&file_path, b"second").unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), b"second");
+
+        // 一時ファイルは残らない
+        let entries = fs::read_dir(file_path.parent().unwrap()).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn test_split_sequence() {
+        assert_eq!(split_sequence("5K > 近S >2HS"), vec!["5K", "近S", "2HS"]);
+        assert_eq!(split_sequence("5K"), vec!["5K"]);
+        assert!(split_sequence(" > ").is_empty());
+    }
 }